@@ -1,16 +1,68 @@
+use crate::curve_tessellation::CurveTessellation;
+use crate::curves::{
+    arc_curve::ArcCurve, clothoid_curve::ClothoidCurve, curve::Curve,
+    ellipse_arc_curve::EllipseArcCurve, expression_curve::ExpressionCurve, line_curve::LineCurve,
+    meridian_curve::MeridianCurve, nurbs_curve::NurbsCurve,
+};
+use crate::domain::Domain;
+use crate::expression::parse_parameters;
+use crate::plane::Plane;
 use crate::surface_tessellation::SurfaceTessellation;
-use crate::{
-    curves::curve::Curve,
-    surfaces::{
-        cylinder::Cylinder, elliptic_paraboloid::EllipticParaboloid, helicoid::Helicoid,
-        hyperbolic_paraboloid::HyperbolicParaboloid, mobius::Mobius,
-        single_leaf_paraboloid::SingleLeafParaboloid, sphere::Sphere, surface::Surface,
-        torus::Torus,
-    },
+use crate::surfaces::curvature_line::LineFamily;
+use crate::surfaces::gauss_bonnet::GaussBonnet;
+use crate::surfaces::geodesic;
+use crate::surfaces::surface_curve::SurfaceCurve;
+use crate::surfaces::uv;
+use crate::surfaces::{
+    bezier_patch::BezierPatch,
+    classical_surface::ClassicalSurface,
+    coons_patch::{CoonsBlending, CoonsPatch},
+    cylinder::Cylinder,
+    elliptic_paraboloid::EllipticParaboloid,
+    expression_surface::ExpressionSurface,
+    gregory_patch::GregoryPatch,
+    helicoid::Helicoid,
+    hyperbolic_paraboloid::HyperbolicParaboloid,
+    implicit_surface::ImplicitSurface,
+    loft_surface::{LoftContinuity, LoftMatching, LoftSurface},
+    minimal_surface::MinimalSurface,
+    mobius::Mobius,
+    nurbs_surface::NurbsSurface,
+    revolution_surface::RevolutionSurface,
+    ruled_surface::RuledSurface,
+    sine_gordon_surface::SineGordonSurface,
+    single_leaf_paraboloid::SingleLeafParaboloid,
+    sphere::Sphere,
+    surface::Surface,
+    sweep_surface::SweepSurface,
+    torus::Torus,
 };
+use crate::triangle_mesh::TriangleMesh;
+use crate::types::{Point2f, Point3f};
+use crate::umbilic_tessellation::UmbilicTessellation;
+use crate::utils::set_panic_hook;
+use crate::vector_field_tessellation::VectorFieldTessellation;
+use core::f64::consts::TAU;
 use wasm_bindgen::prelude::*;
 
-pub enum CurveType {}
+#[wasm_bindgen]
+pub enum CurveType {
+    Arc,
+    EllipseArc,
+    Clothoid,
+    Expression,
+    // the meridian of the unduloid
+    Meridian,
+    Line,
+    // the rational arc
+    Nurbs,
+}
+
+#[wasm_bindgen]
+pub enum LineType {
+    Curvature,
+    Asymptotic,
+}
 
 #[wasm_bindgen]
 pub enum SurfaceType {
@@ -22,16 +74,608 @@ pub enum SurfaceType {
     EllipticParaboloid,
     HyperbolicParaboloid,
     SingleLeafParaboloid,
+    // the surface last set by set_surface_expression
+    Expression,
+    // the profile last set by set_revolution_profile revolved around the z axis
+    Revolution,
+    // the rulings between the rails last set by set_ruled_rails
+    Ruled,
+    // the sections last set by set_loft_sections
+    Loft,
+    // the patch last set by set_nurbs_surface, a rational sphere by default
+    Nurbs,
+    // a bicubic hill
+    Bezier,
+    // the loop last set by set_coons_boundaries
+    Coons,
+    // the Bézier hill with split interior points
+    Gregory,
+    // the Weierstrass data last set by set_weierstrass_data, Enneper's surface by default
+    Minimal,
+    Ellipsoid,
+    HyperboloidOfOneSheet,
+    // the upper sheet
+    HyperboloidOfTwoSheets,
+    Cone,
+    // the figure eight immersion
+    KleinBottle,
+    Boy,
+    DupinCyclide,
+    MonkeySaddle,
+    Enneper,
+    // the partner of the helicoid
+    Catenoid,
+    RomanSurface,
+    Pseudosphere,
+    Dini,
+    Kuen,
+    Breather,
+    // the angle last set by set_sine_gordon_angle, Kuen's surface by default
+    SineGordon,
 }
 
 #[wasm_bindgen]
-pub struct App {}
+pub enum ImplicitType {
+    Sphere,
+    Gyroid,
+    Tanglecube,
+    Metaballs,
+    // the function last set by set_implicit_expression
+    Expression,
+}
+
+// the curve of CurveType with the parameters of App::create_curve
+fn curve(t: CurveType, parameters: &[f64]) -> Box<dyn Curve> {
+    let p = |i: usize, default: f64| *parameters.get(i).unwrap_or(&default);
+    match t {
+        CurveType::Arc => {
+            let c = ArcCurve::default();
+            Box::new(ArcCurve::new(
+                Plane::default(),
+                p(0, c.radius),
+                p(1, c.angle),
+            ))
+        }
+        CurveType::EllipseArc => {
+            let c = EllipseArcCurve::default();
+            Box::new(EllipseArcCurve::new(
+                Plane::default(),
+                p(0, c.a),
+                p(1, c.b),
+                p(2, c.angle),
+            ))
+        }
+        CurveType::Clothoid => {
+            let d = ClothoidCurve::default().domain();
+            Box::new(ClothoidCurve::new(
+                Plane::default(),
+                p(0, 1.0),
+                p(1, d.start()),
+                p(2, d.end()),
+                Some(1e-3),
+            ))
+        }
+        CurveType::Expression => {
+            let d = ExpressionCurve::default().domain();
+            Box::new(ExpressionCurve::expression(
+                String::from("cos(t)"),
+                String::from("sin(t)"),
+                String::from("t"),
+                Domain::new(p(0, d.start()), p(1, d.end())),
+            ))
+        }
+        CurveType::Meridian => Box::new(MeridianCurve::unduloid(
+            Plane::default(),
+            p(0, 1.0),
+            p(1, 0.5),
+            p(2, 2.0),
+        )),
+        CurveType::Line => {
+            let c = LineCurve::default();
+            Box::new(LineCurve::new(
+                Point3f::new(p(0, c.a.x), p(1, c.a.y), p(2, c.a.z)),
+                Point3f::new(p(3, c.b.x), p(4, c.b.y), p(5, c.b.z)),
+            ))
+        }
+        CurveType::Nurbs => {
+            let c = ArcCurve::default();
+            Box::new(NurbsCurve::arc(
+                &Plane::default(),
+                p(0, c.radius),
+                p(1, c.angle),
+            ))
+        }
+    }
+}
+
+// curves "x(t); y(t); z(t)" over t in [0, 1] separated by '|',
+// None if any of them cannot be evaluated
+fn parse_curves(text: &str) -> Option<Vec<[String; 3]>> {
+    text.split('|')
+        .map(|curve| {
+            let xyz: Vec<String> = curve.split(';').map(|s| s.trim().to_string()).collect();
+            match xyz.as_slice() {
+                [x, y, z] => {
+                    ExpressionCurve::try_expression(x.clone(), y.clone(), z.clone(), Domain::unit())
+                        .ok()
+                        .map(|_| [x.clone(), y.clone(), z.clone()])
+                }
+                _ => None,
+            }
+        })
+        .collect()
+}
+
+fn unit_curves(curves: &[[String; 3]]) -> Vec<Box<dyn Curve>> {
+    curves
+        .iter()
+        .map(|[x, y, z]| -> Box<dyn Curve> {
+            Box::new(ExpressionCurve::expression(
+                x.clone(),
+                y.clone(),
+                z.clone(),
+                Domain::unit(),
+            ))
+        })
+        .collect()
+}
+
+// the Coons patch of the bottom, right, top and left sides,
+// None unless there are four of them forming a loop
+fn coons_patch(boundaries: &[[String; 3]], blending: CoonsBlending) -> Option<CoonsPatch> {
+    let mut sides = unit_curves(boundaries);
+    if sides.len() != 4 {
+        return None;
+    }
+    let left = sides.pop()?;
+    let top = sides.pop()?;
+    let right = sides.pop()?;
+    let bottom = sides.pop()?;
+    CoonsPatch::new(bottom, right, top, left, blending)
+}
+
+#[wasm_bindgen]
+pub struct App {
+    expression: ExpressionSurface,
+    // x(t), y(t), z(t) of the revolution profile over its domain
+    profile: [String; 3],
+    profile_domain: Domain,
+    // x(t), y(t), z(t) of the two rails of the ruled surface over t in [0, 1]
+    rails: [[String; 3]; 2],
+    // x(t), y(t), z(t) of each loft section over t in [0, 1]
+    sections: Vec<[String; 3]>,
+    continuity: LoftContinuity,
+    matching: LoftMatching,
+    nurbs: NurbsSurface,
+    // x(t), y(t), z(t) of the bottom, right, top and left sides of the Coons patch
+    boundaries: Vec<[String; 3]>,
+    blending: CoonsBlending,
+    // f(w), g(w) of the minimal surface over its u and v domains and the associate angle
+    weierstrass: [String; 2],
+    weierstrass_domains: [Domain; 2],
+    associate: f64,
+    // ω(u, v) of the constant negative curvature surface over its u and v domains
+    sine_gordon: String,
+    sine_gordon_domains: [Domain; 2],
+    // f(x, y, z) of the implicit surface and the corners of its box
+    implicit: String,
+    implicit_bounds: [Point3f; 2],
+}
+
+impl Default for App {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl App {
+    fn surface(&self, t: SurfaceType) -> Box<dyn Surface> {
+        match t {
+            SurfaceType::Sphere => Box::new(Sphere::default()),
+            SurfaceType::Cylinder => Box::new(Cylinder::default()),
+            SurfaceType::Torus => Box::new(Torus::default()),
+            SurfaceType::Mobius => Box::new(Mobius::default()),
+            SurfaceType::Helicoid => Box::new(Helicoid::default()),
+            SurfaceType::EllipticParaboloid => Box::new(EllipticParaboloid::default()),
+            SurfaceType::HyperbolicParaboloid => Box::new(HyperbolicParaboloid::default()),
+            SurfaceType::SingleLeafParaboloid => Box::new(SingleLeafParaboloid::default()),
+            SurfaceType::Expression => Box::new(self.expression.clone()),
+            SurfaceType::Ruled => Box::new(self.ruled()),
+            SurfaceType::Nurbs => Box::new(self.nurbs.clone()),
+            SurfaceType::Loft => Box::new(
+                LoftSurface::new(unit_curves(&self.sections), self.continuity, self.matching)
+                    .unwrap(),
+            ),
+            SurfaceType::Bezier => Box::new(BezierPatch::default()),
+            SurfaceType::Coons => Box::new(self.coons()),
+            SurfaceType::Gregory => Box::new(GregoryPatch::default()),
+            SurfaceType::Minimal => Box::new(self.minimal().unwrap()),
+            SurfaceType::Ellipsoid => Box::new(ClassicalSurface::ellipsoid()),
+            SurfaceType::HyperboloidOfOneSheet => {
+                Box::new(ClassicalSurface::hyperboloid_of_one_sheet())
+            }
+            SurfaceType::HyperboloidOfTwoSheets => {
+                Box::new(ClassicalSurface::hyperboloid_of_two_sheets())
+            }
+            SurfaceType::Cone => Box::new(ClassicalSurface::cone()),
+            SurfaceType::KleinBottle => Box::new(ClassicalSurface::klein_bottle()),
+            SurfaceType::Boy => Box::new(ClassicalSurface::boy()),
+            SurfaceType::DupinCyclide => Box::new(ClassicalSurface::dupin_cyclide()),
+            SurfaceType::MonkeySaddle => Box::new(ClassicalSurface::monkey_saddle()),
+            SurfaceType::Enneper => Box::new(ClassicalSurface::enneper()),
+            SurfaceType::Catenoid => Box::new(ClassicalSurface::catenoid()),
+            SurfaceType::RomanSurface => Box::new(ClassicalSurface::roman_surface()),
+            SurfaceType::Pseudosphere => Box::new(SineGordonSurface::pseudosphere()),
+            SurfaceType::Dini => Box::new(SineGordonSurface::dini(0.6)),
+            SurfaceType::Kuen => Box::new(SineGordonSurface::kuen()),
+            SurfaceType::Breather => Box::new(SineGordonSurface::breather(0.5)),
+            SurfaceType::SineGordon => {
+                let [u, v] = self.sine_gordon_domains.clone();
+                Box::new(SineGordonSurface::try_expression(&self.sine_gordon, u, v).unwrap())
+            }
+            SurfaceType::Revolution => {
+                let [x, y, z] = self.profile.clone();
+                let profile = ExpressionCurve::expression(x, y, z, self.profile_domain.clone());
+                Box::new(RevolutionSurface::new(
+                    Box::new(profile),
+                    Plane::default(),
+                    None,
+                ))
+            }
+        }
+    }
+
+    fn minimal(&self) -> Result<MinimalSurface, fasteval::Error> {
+        let [f, g] = &self.weierstrass;
+        let [u, v] = self.weierstrass_domains.clone();
+        MinimalSurface::try_expression(f, g, u, v).map(|s| s.associate(self.associate))
+    }
+
+    fn coons(&self) -> CoonsPatch {
+        coons_patch(&self.boundaries, self.blending).unwrap()
+    }
+
+    fn ruled(&self) -> RuledSurface {
+        let rail = |[x, y, z]: [String; 3]| -> Box<dyn Curve> {
+            Box::new(ExpressionCurve::expression(x, y, z, Domain::unit()))
+        };
+        let [rail0, rail1] = self.rails.clone();
+        RuledSurface::between(rail(rail0), rail(rail1))
+    }
+}
 
 #[wasm_bindgen]
 impl App {
     pub fn new() -> App {
-        std::panic::set_hook(Box::new(console_error_panic_hook::hook));
-        App {}
+        set_panic_hook();
+        App {
+            expression: ExpressionSurface::default(),
+            profile: [
+                String::from("1 + 0.5 * sin(t * 1.5)"),
+                String::from("0"),
+                String::from("t"),
+            ],
+            profile_domain: Domain::new(0.0, 4.0),
+            rails: [
+                [
+                    String::from("2 * cos(t * 3)"),
+                    String::from("2 * sin(t * 3)"),
+                    String::from("-1"),
+                ],
+                [
+                    String::from("2 * cos(t * 3 + 1)"),
+                    String::from("2 * sin(t * 3 + 1)"),
+                    String::from("1"),
+                ],
+            ],
+            sections: [
+                ["cos(t * 2 * pi())", "sin(t * 2 * pi())", "-2"],
+                ["2 * cos(t * 2 * pi())", "0.8 * sin(t * 2 * pi())", "-0.7"],
+                ["0.8 * cos(t * 2 * pi())", "1.6 * sin(t * 2 * pi())", "0.7"],
+                ["1.2 * cos(t * 2 * pi())", "1.2 * sin(t * 2 * pi())", "2"],
+            ]
+            .iter()
+            .map(|s| [String::from(s[0]), String::from(s[1]), String::from(s[2])])
+            .collect(),
+            continuity: LoftContinuity::Cubic,
+            matching: LoftMatching::Parameter,
+            nurbs: NurbsSurface::default(),
+            boundaries: [
+                ["t * 4 - 2", "-2", "sin(t * pi())"],
+                ["2", "t * 4 - 2", "-0.5 * sin(t * 2 * pi())"],
+                ["2 - t * 4", "2", "0.8 * sin(t * pi())"],
+                ["-2", "2 - t * 4", "-sin(t * pi())"],
+            ]
+            .iter()
+            .map(|s| [String::from(s[0]), String::from(s[1]), String::from(s[2])])
+            .collect(),
+            blending: CoonsBlending::Bicubic,
+            weierstrass: [String::from("1"), String::from("w")],
+            weierstrass_domains: [Domain::new(-1.5, 1.5), Domain::new(-1.5, 1.5)],
+            associate: 0.0,
+            sine_gordon: String::from("4 * atan((u - v) / cosh(u + v))"),
+            sine_gordon_domains: [Domain::new(1e-2, 4.0), Domain::new(-4.0, -1e-2)],
+            implicit: String::from("x^4 + y^4 + z^4 - (x^2 + y^2 + z^2) * 2 + 0.5"),
+            implicit_bounds: [Point3f::new(-2.0, -2.0, -2.0), Point3f::new(2.0, 2.0, 2.0)],
+        }
+    }
+
+    // f(x, y, z) over the box [x0, x1] x [y0, y1] x [z0, z1] given as bounds for ImplicitType::Expression,
+    // returns false and keeps the previous function if it cannot be evaluated
+    pub fn set_implicit_expression(&mut self, f: String, bounds: Vec<f64>) -> bool {
+        let b = |i: usize| *bounds.get(i).unwrap_or(&[-2.0, 2.0][i % 2]);
+        let corners = [
+            Point3f::new(b(0), b(2), b(4)),
+            Point3f::new(b(1), b(3), b(5)),
+        ];
+        match ImplicitSurface::try_expression(&f, corners[0], corners[1]) {
+            Ok(_) => {
+                self.implicit = f;
+                self.implicit_bounds = corners;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // the level set f = 0 meshed by marching cubes over count^3 cells,
+    // colored by the curvatures from the gradient and Hessian of f
+    pub fn create_implicit_surface(&self, t: ImplicitType, count: usize) -> SurfaceTessellation {
+        let surface = match t {
+            ImplicitType::Sphere => ImplicitSurface::sphere(2.0),
+            ImplicitType::Gyroid => ImplicitSurface::gyroid(1.5, 2.0),
+            ImplicitType::Tanglecube => ImplicitSurface::tanglecube(),
+            ImplicitType::Metaballs => ImplicitSurface::metaballs(vec![
+                (Point3f::new(-0.9, 0.0, 0.0), 1.0),
+                (Point3f::new(0.9, 0.3, 0.0), 0.9),
+                (Point3f::new(0.0, -0.2, 1.1), 0.8),
+            ]),
+            ImplicitType::Expression => {
+                let [min, max] = self.implicit_bounds;
+                ImplicitSurface::try_expression(&self.implicit, min, max).unwrap()
+            }
+        };
+        surface.tessellate(count)
+    }
+
+    // the angle ω(u, v) between the asymptotic lines over [u0, u1] x [v0, v1] given as domain
+    // for SurfaceType::SineGordon, a solution of ω_uv = sin ω such as "4 * atan(e()^(u + v))",
+    // returns false and keeps the previous angle if it cannot be evaluated
+    pub fn set_sine_gordon_angle(&mut self, angle: String, domain: Vec<f64>) -> bool {
+        let d = |i: usize| *domain.get(i).unwrap_or(&[0.0, 1.0][i % 2]);
+        let domains = [Domain::new(d(0), d(1)), Domain::new(d(2), d(3))];
+        let [u, v] = domains.clone();
+        match SineGordonSurface::try_expression(&angle, u, v) {
+            Ok(_) => {
+                self.sine_gordon = angle;
+                self.sine_gordon_domains = domains;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // f(w) and g(w) of w = u + i v over [u0, u1] x [v0, v1] given as domain for SurfaceType::Minimal,
+    // e.g. "exp(-w)" and "exp(w)" over [-1.5, 1.5] x [0, 2 pi] for the catenoid,
+    // with f turned by e^(i angle) through the associate family,
+    // returns false and keeps the previous data if either cannot be evaluated
+    pub fn set_weierstrass_data(
+        &mut self,
+        f: String,
+        g: String,
+        domain: Vec<f64>,
+        angle: f64,
+    ) -> bool {
+        let d = |i: usize| *domain.get(i).unwrap_or(&[0.0, 1.0][i % 2]);
+        let domains = [Domain::new(d(0), d(1)), Domain::new(d(2), d(3))];
+        let [u, v] = domains.clone();
+        match MinimalSurface::try_expression(&f, &g, u, v) {
+            Ok(_) => {
+                self.weierstrass = [f, g];
+                self.weierstrass_domains = domains;
+                self.associate = angle;
+                true
+            }
+            Err(_) => false,
+        }
+    }
+
+    // the bottom, right, top and left sides "x(t); y(t); z(t)" over t in [0, 1] separated by '|'
+    // for SurfaceType::Coons, each starting where the previous one ends,
+    // returns false and keeps the previous sides if they cannot be evaluated or do not close
+    pub fn set_coons_boundaries(&mut self, boundaries: String, bicubic: bool) -> bool {
+        let boundaries = match parse_curves(&boundaries) {
+            Some(b) if b.len() == 4 => b,
+            _ => return false,
+        };
+        let blending = if bicubic {
+            CoonsBlending::Bicubic
+        } else {
+            CoonsBlending::Bilinear
+        };
+        if coons_patch(&boundaries, blending).is_none() {
+            return false;
+        }
+        self.boundaries = boundaries;
+        self.blending = blending;
+        true
+    }
+
+    // the control net of the Bézier and Gregory patches, the boundary loop of the Coons patch,
+    // empty for the other surfaces
+    pub fn create_control_net(&self, t: SurfaceType) -> Vec<CurveTessellation> {
+        let net = match t {
+            SurfaceType::Bezier => BezierPatch::default().control_net(),
+            SurfaceType::Gregory => GregoryPatch::default().control_net(),
+            SurfaceType::Coons => self.coons().control_net(64),
+            _ => vec![],
+        };
+        net.iter()
+            .map(|points| CurveTessellation::polyline(points))
+            .collect()
+    }
+
+    // a NURBS patch for SurfaceType::Nurbs, e.g. imported from CAD,
+    // points holds x, y, z of the control points row by row along u, one weight each,
+    // returns false and keeps the previous patch if the knots do not fit the net
+    pub fn set_nurbs_surface(
+        &mut self,
+        u_degree: usize,
+        v_degree: usize,
+        u_knots: Vec<f64>,
+        v_knots: Vec<f64>,
+        points: Vec<f64>,
+        weights: Vec<f64>,
+    ) -> bool {
+        let columns = v_knots.len().saturating_sub(v_degree + 1);
+        if columns == 0
+            || points.len() != weights.len() * 3
+            || !weights.len().is_multiple_of(columns)
+        {
+            return false;
+        }
+        let control_points = points
+            .chunks(3)
+            .map(|p| Point3f::new(p[0], p[1], p[2]))
+            .collect::<Vec<_>>()
+            .chunks(columns)
+            .map(|row| row.to_vec())
+            .collect();
+        let weights = weights.chunks(columns).map(|row| row.to_vec()).collect();
+        match NurbsSurface::new(
+            u_degree,
+            v_degree,
+            u_knots,
+            v_knots,
+            control_points,
+            weights,
+        ) {
+            Some(surface) => {
+                self.nurbs = surface;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // sections "x(t); y(t); z(t)" over t in [0, 1] separated by '|' for SurfaceType::Loft,
+    // at least two of them, interpolated by straight rulings or cubic splines
+    // and matched by the parameter or the fraction of the arc length,
+    // returns false and keeps the previous sections if any of them cannot be evaluated
+    pub fn set_loft_sections(&mut self, sections: String, cubic: bool, arc_length: bool) -> bool {
+        match parse_curves(&sections) {
+            Some(sections) if sections.len() >= 2 => {
+                self.sections = sections;
+                self.continuity = if cubic {
+                    LoftContinuity::Cubic
+                } else {
+                    LoftContinuity::Ruled
+                };
+                self.matching = if arc_length {
+                    LoftMatching::ArcLength
+                } else {
+                    LoftMatching::Parameter
+                };
+                true
+            }
+            _ => false,
+        }
+    }
+
+    // x(t), y(t), z(t) of both rails over t in [0, 1] for SurfaceType::Ruled,
+    // returns false and keeps the previous rails if any of them cannot be evaluated
+    pub fn set_ruled_rails(
+        &mut self,
+        x0: String,
+        y0: String,
+        z0: String,
+        x1: String,
+        y1: String,
+        z1: String,
+    ) -> bool {
+        let rails = [[x0, y0, z0], [x1, y1, z1]];
+        let valid = rails.iter().all(|[x, y, z]| {
+            ExpressionCurve::try_expression(x.clone(), y.clone(), z.clone(), Domain::unit()).is_ok()
+        });
+        if valid {
+            self.rails = rails;
+        }
+        valid
+    }
+
+    // the striction curve of the ruled surface where it crosses the patch
+    pub fn create_striction_curve(&self, count: usize) -> Option<CurveTessellation> {
+        let s = self.ruled();
+        let uv = s.striction_curve(count);
+        if uv.len() < 2 {
+            None
+        } else {
+            Some(geodesic::tessellate(&s, &uv))
+        }
+    }
+
+    // whether the ruled surface is developable (K ≡ 0), e.g. between parallel rails
+    pub fn is_ruled_developable(&self) -> bool {
+        self.ruled().is_developable(64)
+    }
+
+    // the distribution parameter of the ruled surface at normalized u, NaN for parallel rulings
+    pub fn distribution_parameter(&self, u: f64) -> f64 {
+        self.ruled()
+            .distribution_parameter_at(u)
+            .unwrap_or(f64::NAN)
+    }
+
+    // x(t), y(t), z(t) of the profile over [start, end] for SurfaceType::Revolution,
+    // returns false and keeps the previous profile if any of them cannot be evaluated
+    pub fn set_revolution_profile(
+        &mut self,
+        x: String,
+        y: String,
+        z: String,
+        start: f64,
+        end: f64,
+    ) -> bool {
+        let domain = Domain::new(start, end);
+        let valid =
+            ExpressionCurve::try_expression(x.clone(), y.clone(), z.clone(), domain.clone());
+        if valid.is_err() {
+            return false;
+        }
+        self.profile = [x, y, z];
+        self.profile_domain = domain;
+        true
+    }
+
+    // x(u, v), y(u, v), z(u, v) formulas over [u0, u1] x [v0, v1] given as domain,
+    // with named parameters such as "a = 1, b = 3",
+    // returns false and keeps the previous surface if any of them cannot be evaluated
+    pub fn set_surface_expression(
+        &mut self,
+        x: String,
+        y: String,
+        z: String,
+        domain: Vec<f64>,
+        parameters: String,
+    ) -> bool {
+        let d = |i: usize| *domain.get(i).unwrap_or(&[0.0, 1.0][i % 2]);
+        let surface = parse_parameters(&parameters).and_then(|p| {
+            ExpressionSurface::try_expression(
+                &x,
+                &y,
+                &z,
+                Domain::new(d(0), d(1)),
+                Domain::new(d(2), d(3)),
+                p,
+            )
+        });
+        match surface {
+            Ok(surface) => {
+                self.expression = surface;
+                true
+            }
+            Err(_) => false,
+        }
     }
 
     pub fn create_surface(
@@ -39,22 +683,212 @@ impl App {
         t: SurfaceType,
         delta: Option<f64>,
     ) -> Option<SurfaceTessellation> {
-        match t {
-            SurfaceType::Sphere => Some(Sphere::default().tessellate(delta)),
-            SurfaceType::Cylinder => Some(Cylinder::default().tessellate(delta)),
-            SurfaceType::Torus => Some(Torus::default().tessellate(delta)),
-            SurfaceType::Mobius => Some(Mobius::default().tessellate(delta)),
-            SurfaceType::Helicoid => Some(Helicoid::default().tessellate(delta)),
-            SurfaceType::EllipticParaboloid => {
-                Some(EllipticParaboloid::default().tessellate(delta))
-            }
-            SurfaceType::HyperbolicParaboloid => {
-                Some(HyperbolicParaboloid::default().tessellate(delta))
-            }
-            SurfaceType::SingleLeafParaboloid => {
-                Some(SingleLeafParaboloid::default().tessellate(delta))
-            }
-            _ => None,
+        Some(self.surface(t).tessellate(delta))
+    }
+
+    // max |K_extrinsic - K_intrinsic| over a count x count grid inside the surface,
+    // the gaussian curvature from the metric alone agrees with the one from the normal
+    pub fn gaussian_curvature_deviation(&self, t: SurfaceType, count: usize) -> f64 {
+        let s = self.surface(t);
+        uv::grid(count)
+            .into_iter()
+            .map(|(u, v)| (s.curvature_at(u, v).gaussian - s.intrinsic_gaussian_at(u, v)).abs())
+            .fold(0.0, f64::max)
+    }
+
+    // [K, H] of TriangleMesh::curvature_deviation on the tessellation, NaN if it is not a manifold mesh
+    pub fn discrete_curvature_deviation(&self, t: SurfaceType, delta: Option<f64>) -> Vec<f64> {
+        let tessellation = self.surface(t).tessellate(delta);
+        match TriangleMesh::curvature_deviation(&tessellation) {
+            Some((gaussian, mean)) => vec![gaussian, mean],
+            None => vec![f64::NAN, f64::NAN],
         }
     }
+
+    pub fn surface_area(&self, t: SurfaceType) -> f64 {
+        self.surface(t).area()
+    }
+
+    // enclosed volume, meaningful for closed surfaces
+    pub fn surface_volume(&self, t: SurfaceType) -> f64 {
+        self.surface(t).volume().abs()
+    }
+
+    pub fn total_gaussian_curvature(&self, t: SurfaceType) -> f64 {
+        self.surface(t).total_gaussian_curvature()
+    }
+
+    pub fn total_mean_curvature(&self, t: SurfaceType) -> f64 {
+        self.surface(t).total_mean_curvature()
+    }
+
+    // Gauss-Bonnet over the patch [u0, u1] x [v0, v1], the whole surface for [0, 1] x [0, 1]
+    pub fn gauss_bonnet(&self, t: SurfaceType, u0: f64, u1: f64, v0: f64, v1: f64) -> GaussBonnet {
+        self.surface(t)
+            .gauss_bonnet(&Domain::new(u0, u1), &Domain::new(v0, v1))
+    }
+
+    // shoot a geodesic from (u, v) on the surface,
+    // angle is measured from the u direction in the tangent plane
+    pub fn create_geodesic(
+        &self,
+        t: SurfaceType,
+        u: f64,
+        v: f64,
+        angle: f64,
+        length: f64,
+    ) -> Option<CurveTessellation> {
+        let s = self.surface(t);
+        let x = s.du_at(u, v).normalize();
+        let y = s.normal_at(u, v).cross(&x);
+        let direction = x * angle.cos() + y * angle.sin();
+        s.geodesic_from(u, v, &direction, length)
+            .map(|g| g.tessellation)
+    }
+
+    // the shortest geodesic between (u0, v0) and (u1, v1) on the surface
+    pub fn create_shortest_geodesic(
+        &self,
+        t: SurfaceType,
+        u0: f64,
+        v0: f64,
+        u1: f64,
+        v1: f64,
+    ) -> Option<CurveTessellation> {
+        self.surface(t)
+            .shortest_geodesic(&Point2f::new(u0, v0), &Point2f::new(u1, v1))
+            .map(|g| g.tessellation)
+    }
+
+    // both families of lines of curvature or asymptotic lines,
+    // count lines on each side of the ones through (u, v) with the spacing in arc length
+    pub fn create_line_net(
+        &self,
+        t: SurfaceType,
+        line: LineType,
+        u: f64,
+        v: f64,
+        spacing: f64,
+        count: usize,
+    ) -> Vec<CurveTessellation> {
+        let s = self.surface(t);
+        let family = match line {
+            LineType::Curvature => LineFamily::Principal1,
+            LineType::Asymptotic => LineFamily::Asymptotic1,
+        };
+        let seed = Point2f::new(u, v);
+        // long enough to cross the surface, closed lines and boundaries stop earlier
+        let length = spacing * (count + 1) as f64 * 16.0;
+        [family, family.transversal()]
+            .iter()
+            .flat_map(|f| s.curvature_line_net(*f, &seed, spacing, count, length))
+            .map(|l| l.tessellate(s.as_ref()))
+            .collect()
+    }
+
+    // umbilics found on a count x count grid with their UmbilicType
+    pub fn create_umbilics(&self, t: SurfaceType, count: usize) -> UmbilicTessellation {
+        let s = self.surface(t);
+        let umbilics = s.umbilics(count);
+        UmbilicTessellation::new(
+            umbilics
+                .iter()
+                .map(|u| s.point_at(u.uv.x, u.uv.y).cast())
+                .collect(),
+            umbilics
+                .iter()
+                .map(|u| s.normal_at(u.uv.x, u.uv.y).cast())
+                .collect(),
+            umbilics.iter().map(|u| u.kind as u32).collect(),
+        )
+    }
+
+    // transport the u direction around a circle of the radius centered at (u, v) in uv space,
+    // the holonomy angle can be compared with the enclosed integral of gaussian curvature
+    pub fn create_parallel_transport(
+        &self,
+        t: SurfaceType,
+        u: f64,
+        v: f64,
+        radius: f64,
+        count: usize,
+    ) -> Option<VectorFieldTessellation> {
+        let s = self.surface(t);
+        // the circle in the uv plane, starting at (u + radius, v)
+        let plane = Plane {
+            origin: Point3f::new(u, v, 0.0),
+            ..Plane::default()
+        };
+        let path = ArcCurve::new(plane, radius, TAU);
+        let curve = SurfaceCurve::new(s.as_ref(), &path);
+        let transport = curve.parallel_transport(&s.du_at(u + radius, v), count);
+        Some(VectorFieldTessellation::new(
+            transport.points.iter().map(|p| p.cast()).collect(),
+            transport
+                .vectors
+                .iter()
+                .map(|v| v.normalize().cast())
+                .collect(),
+            transport.holonomy_angle(),
+            curve.enclosed_curvature(count),
+        ))
+    }
+
+    // parameters (missing values fall back to the defaults)
+    // Arc: [radius, angle]
+    // EllipseArc: [a, b, angle]
+    // Clothoid: [a, start_angle, end_angle]
+    // Expression: [start, end] of the helix cos(t), sin(t), t
+    // Meridian: [a, b, periods]
+    // Line: [x0, y0, z0, x1, y1, z1] of the ends
+    // Nurbs: [radius, angle]
+    pub fn create_curve(
+        &self,
+        t: CurveType,
+        parameters: Vec<f64>,
+        delta: Option<f64>,
+    ) -> Option<CurveTessellation> {
+        Some(curve(t, &parameters).tessellate(delta))
+    }
+
+    // the tube of the radius around the curve of create_curve, colored by its surface curvature
+    pub fn create_tube(
+        &self,
+        t: CurveType,
+        parameters: Vec<f64>,
+        radius: f64,
+        delta: Option<f64>,
+    ) -> Option<SurfaceTessellation> {
+        Some(SweepSurface::tube(curve(t, &parameters), radius).tessellate(delta))
+    }
+
+    // the tube of the radius around the curve of create_expression_curve
+    pub fn create_expression_tube(
+        &self,
+        x: String,
+        y: String,
+        z: String,
+        start: f64,
+        end: f64,
+        radius: f64,
+    ) -> Option<SurfaceTessellation> {
+        let c = ExpressionCurve::try_expression(x, y, z, Domain::new(start, end)).ok()?;
+        Some(SweepSurface::tube(Box::new(c), radius).tessellate(Some(1.0 / 64.0)))
+    }
+
+    // x(t), y(t), z(t) formulas evaluated on [start, end],
+    // returns None if any of them cannot be evaluated
+    pub fn create_expression_curve(
+        &self,
+        x: String,
+        y: String,
+        z: String,
+        start: f64,
+        end: f64,
+        delta: Option<f64>,
+    ) -> Option<CurveTessellation> {
+        ExpressionCurve::try_expression(x, y, z, Domain::new(start, end))
+            .ok()
+            .map(|c| c.tessellate(delta))
+    }
 }
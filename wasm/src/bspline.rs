@@ -0,0 +1,154 @@
+use crate::types::{Point3f, Vector4f};
+
+// B-spline basis functions over a knot vector, following The NURBS Book (Piegl & Tiller)
+// https://en.wikipedia.org/wiki/B-spline
+
+// the span i with knots[i] <= t < knots[i + 1] among the count control points,
+// the last nonempty span at the end of the domain (A2.1)
+pub fn find_span(degree: usize, knots: &[f64], count: usize, t: f64) -> usize {
+    let n = count - 1;
+    if t >= knots[n + 1] {
+        // skip repeated end knots
+        let mut i = n;
+        while i > degree && knots[i] == knots[n + 1] {
+            i -= 1;
+        }
+        return i;
+    }
+    if t <= knots[degree] {
+        return degree;
+    }
+    let (mut low, mut high) = (degree, n + 1);
+    let mut mid = (low + high) / 2;
+    while t < knots[mid] || t >= knots[mid + 1] {
+        if t < knots[mid] {
+            high = mid;
+        } else {
+            low = mid;
+        }
+        mid = (low + high) / 2;
+    }
+    mid
+}
+
+// the nonzero basis functions N_{span - degree + j} and their derivatives up to order,
+// ders[k][j] is the k-th derivative (A2.3)
+pub fn basis_derivatives(
+    span: usize,
+    t: f64,
+    degree: usize,
+    order: usize,
+    knots: &[f64],
+) -> Vec<Vec<f64>> {
+    let p = degree;
+    let mut ndu = vec![vec![0.0; p + 1]; p + 1];
+    let mut left = vec![0.0; p + 1];
+    let mut right = vec![0.0; p + 1];
+    ndu[0][0] = 1.0;
+    for j in 1..=p {
+        left[j] = t - knots[span + 1 - j];
+        right[j] = knots[span + j] - t;
+        let mut saved = 0.0;
+        for r in 0..j {
+            // lower triangle holds the knot differences
+            ndu[j][r] = right[r + 1] + left[j - r];
+            let temp = ndu[r][j - 1] / ndu[j][r];
+            ndu[r][j] = saved + right[r + 1] * temp;
+            saved = left[j - r] * temp;
+        }
+        ndu[j][j] = saved;
+    }
+
+    let mut ders = vec![vec![0.0; p + 1]; order + 1];
+    for j in 0..=p {
+        ders[0][j] = ndu[j][p];
+    }
+    for r in 0..=p {
+        let (mut s1, mut s2) = (0, 1);
+        let mut a = vec![vec![0.0; p + 1]; 2];
+        a[0][0] = 1.0;
+        for k in 1..=order.min(p) {
+            let mut d = 0.0;
+            let rk = r as isize - k as isize;
+            let pk = p - k;
+            if r >= k {
+                a[s2][0] = a[s1][0] / ndu[pk + 1][rk as usize];
+                d = a[s2][0] * ndu[rk as usize][pk];
+            }
+            let j1 = if rk >= -1 { 1 } else { (-rk) as usize };
+            let j2 = if r as isize - 1 <= pk as isize {
+                k - 1
+            } else {
+                p - r
+            };
+            for j in j1..=j2 {
+                let index = (rk + j as isize) as usize;
+                a[s2][j] = (a[s1][j] - a[s1][j - 1]) / ndu[pk + 1][index];
+                d += a[s2][j] * ndu[index][pk];
+            }
+            if r <= pk {
+                a[s2][k] = -a[s1][k - 1] / ndu[pk + 1][r];
+                d += a[s2][k] * ndu[r][pk];
+            }
+            ders[k][r] = d;
+            std::mem::swap(&mut s1, &mut s2);
+        }
+    }
+    // multiply by p! / (p - k)!
+    let mut factor = p as f64;
+    for (k, row) in ders.iter_mut().enumerate().take(order.min(p) + 1).skip(1) {
+        row.iter_mut().for_each(|d| *d *= factor);
+        factor *= (p - k) as f64;
+    }
+    ders
+}
+
+// the Bernstein polynomials B_{j, degree}(t) and their derivatives up to order,
+// the basis over the knots [0, ..., 0, 1, ..., 1]
+pub fn bernstein(degree: usize, t: f64, order: usize) -> Vec<Vec<f64>> {
+    let knots: Vec<f64> = (0..2 * (degree + 1))
+        .map(|i| if i <= degree { 0.0 } else { 1.0 })
+        .collect();
+    basis_derivatives(degree, t, degree, order, &knots)
+}
+
+// Boehm's insertion of the knot t once into the homogeneous control points (A5.1),
+// the curve is unchanged
+pub fn insert_knot(
+    degree: usize,
+    knots: &[f64],
+    points: &[Vector4f],
+    t: f64,
+) -> (Vec<f64>, Vec<Vector4f>) {
+    let p = degree;
+    let k = find_span(p, knots, points.len(), t);
+    let mut inserted = knots.to_vec();
+    inserted.insert(k + 1, t);
+    let q = (0..=points.len())
+        .map(|i| {
+            if i + p <= k {
+                points[i]
+            } else if i > k {
+                points[i - 1]
+            } else {
+                let alpha = (t - knots[i]) / (knots[i + p] - knots[i]);
+                points[i] * alpha + points[i - 1] * (1.0 - alpha)
+            }
+        })
+        .collect();
+    (inserted, q)
+}
+
+// a clamped knot vector is valid for the degree and the count of control points
+pub fn is_valid(degree: usize, knots: &[f64], count: usize) -> bool {
+    degree >= 1
+        && count > degree
+        && knots.len() == count + degree + 1
+        && knots.windows(2).all(|w| w[0] <= w[1])
+        && knots[degree] < knots[count]
+}
+
+// (w x, w y, w z, w) of the control point with the weight
+pub fn homogeneous(p: &Point3f, w: f64) -> Vector4f {
+    Vector4f::new(p.x * w, p.y * w, p.z * w, w)
+}
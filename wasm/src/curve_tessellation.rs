@@ -1,4 +1,5 @@
 use crate::frenet_frame::FrenetFrame;
+use crate::types::{Point3f, Vector3f};
 use nalgebra::{Point3, Vector3};
 use wasm_bindgen::prelude::*;
 
@@ -32,6 +33,26 @@ impl CurveTessellation {
             curvatures,
         }
     }
+
+    // straight segments through the points, e.g. a row of a control net,
+    // the tangents follow the chords and the curvature is zero
+    pub fn polyline(points: &[Point3f]) -> Self {
+        let count = points.len();
+        let frames = (0..count)
+            .map(|i| {
+                let chord = points[(i + 1).min(count - 1)] - points[i.saturating_sub(1)];
+                let tangent = chord.try_normalize(0.0).unwrap_or_else(Vector3f::x);
+                let helper = if tangent.x.abs() < 0.9 {
+                    Vector3f::x()
+                } else {
+                    Vector3f::y()
+                };
+                let normal = (helper - tangent * tangent.dot(&helper)).normalize();
+                FrenetFrame::new(&points[i], &tangent, &normal, &tangent.cross(&normal))
+            })
+            .collect();
+        Self::new(frames, vec![0.0; count])
+    }
 }
 
 #[wasm_bindgen]
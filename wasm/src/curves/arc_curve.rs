@@ -49,16 +49,18 @@ impl Curve for ArcCurve {
 
     fn velocity_at(&self, s: f64) -> Vector3f {
         let r = s * self.angle;
+        let l = self.angle; // dr/ds
         let dx = -self.radius * r.sin();
         let dy = self.radius * r.cos();
-        self.plane.x_axis * dx + self.plane.y_axis * dy
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 
     fn acceleration_at(&self, s: f64) -> Vector3f {
         let r = s * self.angle;
+        let l = self.angle * self.angle; // (dr/ds)^2
         let dx = -self.radius * r.cos();
         let dy = -self.radius * r.sin();
-        self.plane.x_axis * dx + self.plane.y_axis * dy
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 
     /*
@@ -41,36 +41,43 @@ impl Curve for ClothoidCurve {
         self.integral_length(None)
     }
 
+    // integrate the Fresnel integrals with the midpoint rule,
+    // steps are fitted to |t| so that the point moves continuously with t
     fn point_at(&self, s: f64) -> Point3f {
-        let t = s * (self.end_angle - self.start_angle) + self.start_angle;
+        let t = self.domain().map(s);
         let sine = t.signum();
-        let t = (t / self.delta) as i64;
         let abs = t.abs();
-        let parameters = (0..=abs).map(|i| {
-            let tt = (i as f64) * self.delta;
+        let steps = (abs / self.delta).ceil().max(1.0) as usize;
+        let h = abs / steps as f64;
+        let parameters = (0..steps).map(|i| {
+            let tt = (i as f64 + 0.5) * h;
             self.a * tt * tt * 0.5
         });
-        let dx: f64 = parameters.clone().map(|p| p.cos() * self.delta).sum();
-        let dy: f64 = parameters.into_iter().map(|p| p.sin() * self.delta).sum();
+        let dx: f64 = parameters.clone().map(|p| p.cos() * h).sum();
+        let dy: f64 = parameters.into_iter().map(|p| p.sin() * h).sum();
         self.plane.origin + self.plane.x_axis * dx * sine + self.plane.y_axis * dy * sine
     }
 
+    // the curve is point symmetric (P(-t) = -P(t)),
+    // so the derivative P'(t) = (cos(at^2/2), sin(at^2/2)) holds for negative t as well
     fn velocity_at(&self, s: f64) -> Vector3f {
-        let t = s * (self.end_angle - self.start_angle) + self.start_angle;
-        let sine = t.signum();
+        let d = self.domain();
+        let t = d.map(s);
+        let l = d.length(); // dt/ds
         let p = self.a * t * t * 0.5;
         let dx = p.cos();
         let dy = p.sin();
-        self.plane.x_axis * dx * sine + self.plane.y_axis * dy * sine
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 
     fn acceleration_at(&self, s: f64) -> Vector3f {
-        let t = s * (self.end_angle - self.start_angle) + self.start_angle;
-        let sine = t.signum();
+        let d = self.domain();
+        let t = d.map(s);
+        let l = d.length() * d.length(); // (dt/ds)^2
         let p = self.a * t * t * 0.5;
         let k = self.a * t; // difference of p
         let dx = k * -p.sin();
         let dy = k * p.cos();
-        self.plane.x_axis * dx * sine + self.plane.y_axis * dy * sine
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 }
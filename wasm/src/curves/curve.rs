@@ -23,21 +23,46 @@ pub trait Curve {
         l
     }
 
+    // s is the normalized parameter in [0, 1],
+    // derivatives are taken with respect to s (d/ds = d/dt * domain length)
     fn point_at(&self, s: f64) -> Point3f;
     fn velocity_at(&self, s: f64) -> Vector3f;
     fn acceleration_at(&self, s: f64) -> Vector3f;
 
+    // t is the native parameter in domain()
+    fn point_at_parameter(&self, t: f64) -> Point3f {
+        self.point_at(self.domain().normalize(t))
+    }
+
+    fn velocity_at_parameter(&self, t: f64) -> Vector3f {
+        let d = self.domain();
+        self.velocity_at(d.normalize(t)) / d.length()
+    }
+
+    fn acceleration_at_parameter(&self, t: f64) -> Vector3f {
+        let d = self.domain();
+        let l = d.length();
+        self.acceleration_at(d.normalize(t)) / (l * l)
+    }
+
     fn tangent_at(&self, s: f64) -> Vector3f {
         self.velocity_at(s).normalize()
     }
 
+    // principal normal: the component of acceleration orthogonal to the tangent
     fn normal_at(&self, s: f64) -> Vector3f {
-        self.acceleration_at(s).normalize()
+        let t = self.tangent_at(s);
+        let a = self.acceleration_at(s);
+        (a - t * a.dot(&t)).normalize()
     }
 
+    // https://mathworld.wolfram.com/Curvature.html
+    // k = |r' x r''| / |r'|^3
     fn curvature_at(&self, s: f64) -> f64 {
-        let n = self.acceleration_at(s);
-        n.norm()
+        let v = self.velocity_at(s);
+        let a = self.acceleration_at(s);
+        let speed = v.norm();
+        v.cross(&a).norm() / (speed * speed * speed)
     }
 
     fn curvature_radius_at(&self, s: f64) -> f64 {
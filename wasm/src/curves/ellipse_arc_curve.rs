@@ -43,15 +43,17 @@ impl Curve for EllipseArcCurve {
 
     fn velocity_at(&self, s: f64) -> Vector3f {
         let r = s * self.angle;
+        let l = self.angle; // dr/ds
         let dx = -self.a * r.sin();
         let dy = self.b * r.cos();
-        self.plane.x_axis * dx + self.plane.y_axis * dy
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 
     fn acceleration_at(&self, s: f64) -> Vector3f {
         let r = s * self.angle;
+        let l = self.angle * self.angle; // (dr/ds)^2
         let dx = -self.a * r.cos();
         let dy = -self.b * r.sin();
-        self.plane.x_axis * dx + self.plane.y_axis * dy
+        (self.plane.x_axis * dx + self.plane.y_axis * dy) * l
     }
 }
@@ -18,6 +18,12 @@ fn expression(expr: String) -> Box<F> {
     })
 }
 
+fn validate(expr: &str, t: f64) -> Result<f64, fasteval::Error> {
+    let mut map: BTreeMap<String, f64> = BTreeMap::new();
+    map.insert("t".to_string(), t);
+    fasteval::ez_eval(expr, &mut map)
+}
+
 impl ExpressionCurve {
     pub fn expression(x: String, y: String, z: String, d: Domain) -> Self {
         Self {
@@ -27,6 +33,19 @@ impl ExpressionCurve {
             domain: d,
         }
     }
+
+    // check that every formula parses and only refers to t before building the curve
+    pub fn try_expression(
+        x: String,
+        y: String,
+        z: String,
+        d: Domain,
+    ) -> Result<Self, fasteval::Error> {
+        validate(&x, d.start())?;
+        validate(&y, d.start())?;
+        validate(&z, d.start())?;
+        Ok(ExpressionCurve::expression(x, y, z, d))
+    }
 }
 
 impl Default for ExpressionCurve {
@@ -44,13 +63,13 @@ impl ParametricCurve for ExpressionCurve {
     fn domain(&self) -> Domain {
         self.domain.clone()
     }
-    fn x_expr(&self) -> &Box<F> {
+    fn x_expr(&self) -> &F {
         &self.x_expr
     }
-    fn y_expr(&self) -> &Box<F> {
+    fn y_expr(&self) -> &F {
         &self.y_expr
     }
-    fn z_expr(&self) -> &Box<F> {
+    fn z_expr(&self) -> &F {
         &self.z_expr
     }
 }
@@ -0,0 +1,55 @@
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+
+// the segment from a to b, a point if they coincide
+pub struct LineCurve {
+    pub a: Point3f,
+    pub b: Point3f,
+}
+
+impl Default for LineCurve {
+    fn default() -> Self {
+        LineCurve::new(Point3f::new(0.0, 0.0, 0.0), Point3f::new(1.0, 0.0, 0.0))
+    }
+}
+
+impl LineCurve {
+    pub fn new(a: Point3f, b: Point3f) -> Self {
+        Self { a, b }
+    }
+}
+
+impl Curve for LineCurve {
+    fn domain(&self) -> Domain {
+        Domain::new(0.0, self.length())
+    }
+
+    fn length(&self) -> f64 {
+        (self.b - self.a).norm()
+    }
+
+    fn point_at(&self, s: f64) -> Point3f {
+        self.a + (self.b - self.a) * s
+    }
+
+    fn velocity_at(&self, _s: f64) -> Vector3f {
+        self.b - self.a
+    }
+
+    fn acceleration_at(&self, _s: f64) -> Vector3f {
+        Vector3f::zeros()
+    }
+
+    // a line has no principal normal, any fixed unit vector across it keeps the frames finite
+    fn normal_at(&self, _s: f64) -> Vector3f {
+        let d = (self.b - self.a).normalize();
+        let axis = if d.x.abs() < 0.9 {
+            Vector3f::x()
+        } else {
+            Vector3f::y()
+        };
+        d.cross(&axis).normalize()
+    }
+}
@@ -0,0 +1,155 @@
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::plane::Plane;
+use crate::quadrature::gauss_legendre;
+use crate::types::*;
+use core::f64::consts::TAU;
+
+// (f, f', f'') of t
+pub type Jets = dyn Fn(f64) -> [f64; 3];
+
+// the height is either given with its derivatives,
+// or by (h', h'') when it has no closed form (e.g. the unduloid)
+pub enum Height {
+    Function(Box<Jets>),
+    Derivative(Box<dyn Fn(f64) -> [f64; 2]>),
+}
+
+// a profile (radius(t), height(t)) in the half plane spanned by the x axis and the normal of the plane,
+// point = origin + radius x_axis + height normal
+pub struct MeridianCurve {
+    pub plane: Plane,
+    radius: Box<Jets>,
+    height: Height,
+    domain: Domain,
+}
+
+impl MeridianCurve {
+    pub fn new(plane: Plane, radius: Box<Jets>, height: Height, domain: Domain) -> Self {
+        Self {
+            plane,
+            radius,
+            height,
+            domain,
+        }
+    }
+
+    // the radius and the height with their derivatives
+    pub fn function<R, H>(plane: Plane, radius: R, height: H, domain: Domain) -> Self
+    where
+        R: Fn(f64) -> [f64; 3] + 'static,
+        H: Fn(f64) -> [f64; 3] + 'static,
+    {
+        Self::new(
+            plane,
+            Box::new(radius),
+            Height::Function(Box::new(height)),
+            domain,
+        )
+    }
+
+    // the meridian of Delaunay's unduloid for 0 < b < 1,
+    // r = a q, h' = a (1 + b sin t) / q with q = √(1 + b^2 + 2b sin t)
+    pub fn unduloid(plane: Plane, a: f64, b: f64, periods: f64) -> Self {
+        // (q, q', q'')
+        let root = move |t: f64| {
+            let (s, c) = t.sin_cos();
+            let q = (1.0 + b * b + 2.0 * b * s).sqrt();
+            let dq = b * c / q;
+            [q, dq, -(b * s + dq * dq) / q]
+        };
+        let slope = move |t: f64| {
+            let (s, c) = t.sin_cos();
+            let [q, dq, _] = root(t);
+            [
+                a * (1.0 + b * s) / q,
+                a * (b * c * q - (1.0 + b * s) * dq) / (q * q),
+            ]
+        };
+        Self::new(
+            plane,
+            Box::new(move |t| {
+                let [q, dq, ddq] = root(t);
+                [a * q, a * dq, a * ddq]
+            }),
+            Height::Derivative(Box::new(slope)),
+            Domain::new(0.0, TAU * periods),
+        )
+    }
+
+    // (r, r', r'') and (h, h', h'') in the native parameter t
+    fn jets(&self, t: f64) -> ([f64; 3], [f64; 3]) {
+        let h = match &self.height {
+            Height::Function(f) => f(t),
+            Height::Derivative(f) => {
+                let dh = f(t);
+                [self.integrate_height(t), dh[0], dh[1]]
+            }
+        };
+        ((self.radius)(t), h)
+    }
+
+    // ∫ h' dt from the start of the domain
+    fn integrate_height(&self, t: f64) -> f64 {
+        let f = match &self.height {
+            Height::Derivative(f) => f,
+            Height::Function(_) => return 0.0,
+        };
+        let (nodes, weights) = gauss_legendre(16);
+        let t0 = self.domain.start();
+        // a cell per unit of t keeps periodic integrands resolved
+        let cells = ((t - t0).abs().ceil() as usize).max(1);
+        let size = (t - t0) / cells as f64;
+        (0..cells)
+            .map(|i| {
+                nodes
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(x, w)| w * f(t0 + (i as f64 + x) * size)[0])
+                    .sum::<f64>()
+                    * size
+            })
+            .sum()
+    }
+
+    fn vector(&self, r: f64, h: f64) -> Vector3f {
+        self.plane.x_axis * r + self.plane.normal * h
+    }
+}
+
+impl Curve for MeridianCurve {
+    fn domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
+    fn length(&self) -> f64 {
+        let (nodes, weights) = gauss_legendre(16);
+        let cells = 64;
+        (0..cells)
+            .flat_map(|i| {
+                nodes
+                    .iter()
+                    .zip(weights.iter())
+                    .map(move |(x, w)| (i, x, w))
+            })
+            .map(|(i, x, w)| w * self.velocity_at((i as f64 + x) / cells as f64).norm())
+            .sum::<f64>()
+            / cells as f64
+    }
+
+    fn point_at(&self, s: f64) -> Point3f {
+        let (r, h) = self.jets(self.domain.map(s));
+        self.plane.origin + self.vector(r[0], h[0])
+    }
+
+    fn velocity_at(&self, s: f64) -> Vector3f {
+        let (r, h) = self.jets(self.domain.map(s));
+        self.vector(r[1], h[1]) * self.domain.length()
+    }
+
+    fn acceleration_at(&self, s: f64) -> Vector3f {
+        let (r, h) = self.jets(self.domain.map(s));
+        let l = self.domain.length();
+        self.vector(r[2], h[2]) * l * l
+    }
+}
@@ -3,4 +3,7 @@ pub mod clothoid_curve;
 pub mod curve;
 pub mod ellipse_arc_curve;
 pub mod expression_curve;
+pub mod line_curve;
+pub mod meridian_curve;
+pub mod nurbs_curve;
 pub mod parametric_curve;
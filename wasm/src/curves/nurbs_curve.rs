@@ -0,0 +1,228 @@
+use crate::bspline::{basis_derivatives, find_span, homogeneous, insert_knot, is_valid};
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::plane::Plane;
+use crate::quadrature::gauss_legendre;
+use crate::types::*;
+use core::f64::consts::FRAC_PI_2;
+
+// rational B-spline curve, C(t) = Σ N_i(t) w_i P_i / Σ N_i(t) w_i
+// https://en.wikipedia.org/wiki/Non-uniform_rational_B-spline
+#[derive(Clone)]
+pub struct NurbsCurve {
+    degree: usize,
+    knots: Vec<f64>,
+    control_points: Vec<Point3f>,
+    weights: Vec<f64>,
+}
+
+impl NurbsCurve {
+    // None unless there are degree + count + 1 nondecreasing knots and positive weights
+    pub fn new(
+        degree: usize,
+        knots: Vec<f64>,
+        control_points: Vec<Point3f>,
+        weights: Vec<f64>,
+    ) -> Option<Self> {
+        if !is_valid(degree, &knots, control_points.len())
+            || weights.len() != control_points.len()
+            || weights.iter().any(|w| *w <= 0.0)
+        {
+            return None;
+        }
+        Some(Self {
+            degree,
+            knots,
+            control_points,
+            weights,
+        })
+    }
+
+    // the exact circular arc from the x axis of the plane toward its y axis,
+    // made of quadratic segments of at most 90 degrees (A7.1)
+    pub fn arc(plane: &Plane, radius: f64, angle: f64) -> Self {
+        let arcs = ((angle.abs() / FRAC_PI_2).ceil() as usize).clamp(1, 4);
+        let delta = angle / arcs as f64;
+        let w1 = (delta * 0.5).cos();
+        let at = |theta: f64, r: f64| {
+            plane.origin + (plane.x_axis * theta.cos() + plane.y_axis * theta.sin()) * r
+        };
+        let mut control_points = vec![at(0.0, radius)];
+        let mut weights = vec![1.0];
+        let mut knots = vec![0.0; 3];
+        for i in 1..=arcs {
+            let theta = delta * i as f64;
+            // the corner where the tangents of both ends meet
+            control_points.push(at(theta - delta * 0.5, radius / w1));
+            control_points.push(at(theta, radius));
+            weights.push(w1);
+            weights.push(1.0);
+            let k = i as f64 / arcs as f64;
+            if i < arcs {
+                knots.push(k);
+                knots.push(k);
+            }
+        }
+        knots.extend_from_slice(&[1.0; 3]);
+        Self::new(2, knots, control_points, weights).unwrap()
+    }
+
+    pub fn circle(plane: &Plane, radius: f64) -> Self {
+        Self::arc(plane, radius, core::f64::consts::TAU)
+    }
+
+    pub fn degree(&self) -> usize {
+        self.degree
+    }
+
+    pub fn knots(&self) -> &[f64] {
+        &self.knots
+    }
+
+    pub fn control_points(&self) -> &[Point3f] {
+        &self.control_points
+    }
+
+    pub fn weights(&self) -> &[f64] {
+        &self.weights
+    }
+
+    // (w x, w y, w z, w) of each control point
+    pub(crate) fn homogeneous_points(&self) -> Vec<Vector4f> {
+        self.control_points
+            .iter()
+            .zip(self.weights.iter())
+            .map(|(p, w)| homogeneous(p, *w))
+            .collect()
+    }
+
+    pub(crate) fn from_homogeneous(degree: usize, knots: Vec<f64>, points: &[Vector4f]) -> Self {
+        Self {
+            degree,
+            knots,
+            control_points: points
+                .iter()
+                .map(|p| Point3f::from(p.xyz() / p.w))
+                .collect(),
+            weights: points.iter().map(|p| p.w).collect(),
+        }
+    }
+
+    // the same curve with the knot t in the domain inserted once
+    pub fn insert_knot(&self, t: f64) -> Self {
+        let (knots, points) = insert_knot(self.degree, &self.knots, &self.homogeneous_points(), t);
+        Self::from_homogeneous(self.degree, knots, &points)
+    }
+
+    // C, C', C'' in the native parameter t, by the quotient rule on the homogeneous curve (A4.2)
+    fn derivatives(&self, t: f64) -> (Point3f, Vector3f, Vector3f) {
+        let p = self.degree;
+        let span = find_span(p, &self.knots, self.control_points.len(), t);
+        let ders = basis_derivatives(span, t, p, 2, &self.knots);
+        let a: Vec<Vector4f> = (0..3)
+            .map(|k| {
+                (0..=p)
+                    .map(|j| {
+                        let i = span - p + j;
+                        homogeneous(&self.control_points[i], self.weights[i]) * ders[k][j]
+                    })
+                    .sum::<Vector4f>()
+            })
+            .collect();
+        let (w, w1, w2) = (a[0].w, a[1].w, a[2].w);
+        let c = a[0].xyz() / w;
+        let c1 = (a[1].xyz() - c * w1) / w;
+        let c2 = (a[2].xyz() - c1 * 2.0 * w1 - c * w2) / w;
+        (Point3f::from(c), c1, c2)
+    }
+}
+
+impl Curve for NurbsCurve {
+    fn domain(&self) -> Domain {
+        let n = self.control_points.len();
+        Domain::new(self.knots[self.degree], self.knots[n])
+    }
+
+    // Gauss-Legendre on each nonempty knot span
+    fn length(&self) -> f64 {
+        let (nodes, weights) = gauss_legendre(16);
+        let n = self.control_points.len();
+        self.knots[self.degree..=n]
+            .windows(2)
+            .filter(|w| w[0] < w[1])
+            .map(|w| {
+                let size = w[1] - w[0];
+                nodes
+                    .iter()
+                    .zip(weights.iter())
+                    .map(|(x, wi)| wi * self.derivatives(w[0] + x * size).1.norm())
+                    .sum::<f64>()
+                    * size
+            })
+            .sum()
+    }
+
+    fn point_at(&self, s: f64) -> Point3f {
+        self.derivatives(self.domain().map(s)).0
+    }
+
+    fn velocity_at(&self, s: f64) -> Vector3f {
+        let d = self.domain();
+        self.derivatives(d.map(s)).1 * d.length()
+    }
+
+    fn acceleration_at(&self, s: f64) -> Vector3f {
+        let d = self.domain();
+        let l = d.length();
+        self.derivatives(d.map(s)).2 * l * l
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::f64::consts::TAU;
+
+    #[test]
+    fn arc_is_circular() {
+        let plane = Plane::default();
+        let radius = 1.5;
+        for angle in [FRAC_PI_2 * 0.5, 2.5, TAU] {
+            let arc = NurbsCurve::arc(&plane, radius, angle);
+            for i in 0..=32 {
+                let r = (arc.point_at(i as f64 / 32.0) - plane.origin).norm();
+                assert!(
+                    (r - radius).abs() < 1e-12,
+                    "radius {} of the arc {}",
+                    r,
+                    angle
+                );
+            }
+            let length = arc.length();
+            assert!(
+                (length - radius * angle).abs() < 1e-9,
+                "length {} of the arc {}",
+                length,
+                angle
+            );
+        }
+    }
+
+    #[test]
+    fn knot_insertion_keeps_the_curve() {
+        let arc = NurbsCurve::arc(&Plane::default(), 1.5, 2.5);
+        for t in [0.25, 0.5, 0.8] {
+            let inserted = arc.insert_knot(arc.domain().map(t));
+            assert_eq!(
+                inserted.control_points().len(),
+                arc.control_points().len() + 1
+            );
+            for i in 0..=16 {
+                let s = i as f64 / 16.0;
+                assert!((inserted.point_at(s) - arc.point_at(s)).norm() < 1e-12);
+                assert!((inserted.velocity_at(s) - arc.velocity_at(s)).norm() < 1e-9);
+                assert!((inserted.acceleration_at(s) - arc.acceleration_at(s)).norm() < 1e-9);
+            }
+        }
+    }
+}
@@ -6,9 +6,9 @@ use crate::types::{Point3f, Vector3f};
 
 pub trait ParametricCurve {
     fn domain(&self) -> Domain;
-    fn x_expr(&self) -> &Box<F>;
-    fn y_expr(&self) -> &Box<F>;
-    fn z_expr(&self) -> &Box<F>;
+    fn x_expr(&self) -> &F;
+    fn y_expr(&self) -> &F;
+    fn z_expr(&self) -> &F;
 }
 
 impl<T> Curve for T
@@ -35,8 +35,7 @@ where
     }
 
     fn point_at(&self, s: f64) -> Point3f {
-        let d = self.domain();
-        let t = d.start() + s * d.end();
+        let t = self.domain().map(s);
         let x = (self.x_expr())(t);
         let y = (self.y_expr())(t);
         let z = (self.z_expr())(t);
@@ -45,19 +44,21 @@ where
 
     fn velocity_at(&self, s: f64) -> Vector3f {
         let d = self.domain();
-        let t = d.start() + s * d.end();
+        let t = d.map(s);
+        let l = d.length(); // dt/ds
         let dx = df(self.x_expr(), 1, t, 1e-4);
         let dy = df(self.y_expr(), 1, t, 1e-4);
         let dz = df(self.z_expr(), 1, t, 1e-4);
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * l
     }
 
     fn acceleration_at(&self, s: f64) -> Vector3f {
         let d = self.domain();
-        let t = d.start() + s * d.end();
+        let t = d.map(s);
+        let l = d.length() * d.length(); // (dt/ds)^2
         let dx = df(self.x_expr(), 2, t, 1e-4);
         let dy = df(self.y_expr(), 2, t, 1e-4);
         let dz = df(self.z_expr(), 2, t, 1e-4);
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * l
     }
 }
@@ -0,0 +1,22 @@
+use crate::types::{Point3f, Vector3f};
+
+// orthonormal frame of a curve lying on a surface
+pub struct DarbouxFrame {
+    pub position: Point3f,
+    pub tangent: Vector3f,
+    // surface normal
+    pub normal: Vector3f,
+    // normal x tangent
+    pub tangent_normal: Vector3f,
+}
+
+impl DarbouxFrame {
+    pub fn new(p: &Point3f, t: &Vector3f, n: &Vector3f, u: &Vector3f) -> DarbouxFrame {
+        DarbouxFrame {
+            position: *p,
+            tangent: *t,
+            normal: *n,
+            tangent_normal: *u,
+        }
+    }
+}
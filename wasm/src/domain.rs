@@ -1,7 +1,18 @@
-#[derive(Clone)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DomainKind {
+    // [start, end]
+    Closed,
+    // (start, end)
+    Open,
+    // [start, end) repeated, start ~ end
+    Periodic,
+}
+
+#[derive(Clone, Debug, PartialEq)]
 pub struct Domain {
     start: f64,
     end: f64,
+    kind: DomainKind,
 }
 
 impl Default for Domain {
@@ -9,13 +20,38 @@ impl Default for Domain {
         Domain {
             start: 0.0,
             end: 0.0,
+            kind: DomainKind::Closed,
         }
     }
 }
 
 impl Domain {
     pub fn new(s: f64, e: f64) -> Domain {
-        Domain { start: s, end: e }
+        Domain {
+            start: s,
+            end: e,
+            kind: DomainKind::Closed,
+        }
+    }
+
+    pub fn open(s: f64, e: f64) -> Domain {
+        Domain {
+            start: s,
+            end: e,
+            kind: DomainKind::Open,
+        }
+    }
+
+    pub fn periodic(s: f64, e: f64) -> Domain {
+        Domain {
+            start: s,
+            end: e,
+            kind: DomainKind::Periodic,
+        }
+    }
+
+    pub fn unit() -> Domain {
+        Domain::new(0.0, 1.0)
     }
 
     pub fn start(&self) -> f64 {
@@ -26,7 +62,244 @@ impl Domain {
         self.end
     }
 
+    pub fn kind(&self) -> DomainKind {
+        self.kind
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.kind == DomainKind::Closed
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.kind == DomainKind::Open
+    }
+
+    pub fn is_periodic(&self) -> bool {
+        self.kind == DomainKind::Periodic
+    }
+
+    pub fn length(&self) -> f64 {
+        self.end - self.start
+    }
+
+    pub fn min(&self) -> f64 {
+        self.start.min(self.end)
+    }
+
+    pub fn max(&self) -> f64 {
+        self.start.max(self.end)
+    }
+
+    // map normalized parameter [0, 1] to [start, end]
     pub fn map(&self, t01: f64) -> f64 {
         (self.end - self.start) * t01 + self.start
     }
+
+    // inverse of map: [start, end] to [0, 1]
+    pub fn normalize(&self, t: f64) -> f64 {
+        let l = self.length();
+        if l == 0.0 {
+            0.0
+        } else {
+            (t - self.start) / l
+        }
+    }
+
+    // periodic domains contain every value since it wraps into [start, end)
+    pub fn contains(&self, t: f64) -> bool {
+        match self.kind {
+            DomainKind::Closed => self.min() <= t && t <= self.max(),
+            DomainKind::Open => self.min() < t && t < self.max(),
+            DomainKind::Periodic => t.is_finite(),
+        }
+    }
+
+    // clamp into the interval, or wrap into [start, end) if periodic
+    pub fn clamp(&self, t: f64) -> f64 {
+        match self.kind {
+            DomainKind::Periodic => self.wrap(t),
+            _ => t.max(self.min()).min(self.max()),
+        }
+    }
+
+    pub fn wrap(&self, t: f64) -> f64 {
+        let l = self.length();
+        if l == 0.0 {
+            self.start
+        } else {
+            self.start + ((t - self.start) * l.signum()).rem_euclid(l.abs()) * l.signum()
+        }
+    }
+
+    pub fn subdivide(&self, count: usize) -> Vec<Domain> {
+        let count = count.max(1);
+        (0..count)
+            .map(|i| {
+                let s = self.map(i as f64 / count as f64);
+                let e = self.map((i + 1) as f64 / count as f64);
+                Domain {
+                    start: s,
+                    end: e,
+                    kind: match self.kind {
+                        DomainKind::Periodic => DomainKind::Closed,
+                        k => k,
+                    },
+                }
+            })
+            .collect()
+    }
+
+    // the overlapping interval, oriented from min to max
+    pub fn intersection(&self, other: &Domain) -> Option<Domain> {
+        let s = self.min().max(other.min());
+        let e = self.max().min(other.max());
+        let open = self.is_open() || other.is_open();
+        if s < e || (s == e && !open) {
+            Some(Domain {
+                start: s,
+                end: e,
+                kind: if open {
+                    DomainKind::Open
+                } else {
+                    DomainKind::Closed
+                },
+            })
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::{
+        arc_curve::ArcCurve, clothoid_curve::ClothoidCurve, curve::Curve,
+        ellipse_arc_curve::EllipseArcCurve,
+    };
+
+    #[test]
+    fn map_and_normalize_are_inverse() {
+        for domain in [
+            Domain::new(-2.0, 3.0),
+            Domain::new(3.0, -2.0),
+            Domain::periodic(0.0, 6.0),
+        ] {
+            assert_eq!(domain.map(0.0), domain.start());
+            assert_eq!(domain.map(1.0), domain.end());
+            for t01 in [-0.5, 0.0, 0.25, 1.0, 1.5] {
+                assert!((domain.normalize(domain.map(t01)) - t01).abs() < 1e-15);
+            }
+        }
+        assert_eq!(Domain::new(1.0, 1.0).normalize(1.0), 0.0);
+    }
+
+    #[test]
+    fn contains_and_clamp() {
+        let closed = Domain::new(1.0, -1.0);
+        assert!(closed.contains(1.0) && closed.contains(-1.0) && !closed.contains(1.5));
+        assert_eq!(closed.clamp(2.0), 1.0);
+        assert_eq!(closed.clamp(-2.0), -1.0);
+        let open = Domain::open(-1.0, 1.0);
+        assert!(open.contains(0.0) && !open.contains(1.0) && !open.contains(-1.0));
+        let periodic = Domain::periodic(0.0, 4.0);
+        assert!(periodic.contains(-7.0) && !periodic.contains(f64::NAN));
+        assert_eq!(periodic.clamp(5.0), 1.0);
+    }
+
+    #[test]
+    fn wrap_into_start_end() {
+        let domain = Domain::periodic(-1.0, 3.0);
+        for (t, wrapped) in [
+            (-1.0, -1.0),
+            (3.0, -1.0),
+            (4.5, 0.5),
+            (-2.5, 1.5),
+            (-9.0, -1.0),
+        ] {
+            assert_eq!(domain.wrap(t), wrapped);
+        }
+        // a reversed domain wraps into (end, start]
+        let reversed = Domain::periodic(3.0, -1.0);
+        assert_eq!(reversed.wrap(4.0), 0.0);
+        assert_eq!(reversed.wrap(-1.0), 3.0);
+        assert_eq!(Domain::periodic(2.0, 2.0).wrap(5.0), 2.0);
+    }
+
+    #[test]
+    fn subdivide_into_adjacent_pieces() {
+        let domain = Domain::periodic(0.0, 3.0);
+        let pieces = domain.subdivide(3);
+        assert_eq!(pieces.len(), 3);
+        for (i, piece) in pieces.iter().enumerate() {
+            assert_eq!(piece.start(), i as f64);
+            assert_eq!(piece.end(), i as f64 + 1.0);
+            assert!(piece.is_closed());
+        }
+        assert_eq!(domain.subdivide(0), vec![Domain::new(0.0, 3.0)]);
+    }
+
+    #[test]
+    fn intersection() {
+        let a = Domain::new(0.0, 2.0);
+        assert_eq!(
+            a.intersection(&Domain::new(3.0, 1.0)),
+            Some(Domain::new(1.0, 2.0))
+        );
+        assert_eq!(
+            a.intersection(&Domain::open(1.0, 3.0)),
+            Some(Domain::open(1.0, 2.0))
+        );
+        // closed intervals touching at a point meet there, open ones do not
+        assert_eq!(
+            a.intersection(&Domain::new(2.0, 3.0)),
+            Some(Domain::new(2.0, 2.0))
+        );
+        assert_eq!(a.intersection(&Domain::open(2.0, 3.0)), None);
+        assert_eq!(a.intersection(&Domain::new(2.5, 3.0)), None);
+    }
+
+    // d/dt = d/ds / length: the native and normalized derivatives differ by the chain rule
+    #[test]
+    fn native_and_normalized_derivatives() {
+        let curves: Vec<Box<dyn Curve>> = vec![
+            Box::new(ArcCurve::default()),
+            Box::new(EllipseArcCurve::default()),
+            Box::new(ClothoidCurve::default()),
+        ];
+        let h = 1e-5;
+        for (index, curve) in curves.iter().enumerate() {
+            let domain = curve.domain();
+            let l = domain.length();
+            for i in 1..8 {
+                let s = i as f64 / 8.0;
+                let t = domain.map(s);
+                assert!((curve.point_at_parameter(t) - curve.point_at(s)).norm() < 1e-12);
+                let velocity = curve.velocity_at_parameter(t);
+                let acceleration = curve.acceleration_at_parameter(t);
+                assert!((velocity * l - curve.velocity_at(s)).norm() < 1e-9);
+                assert!((acceleration * l * l - curve.acceleration_at(s)).norm() < 1e-9);
+                let dt =
+                    (curve.point_at_parameter(t + h) - curve.point_at_parameter(t - h)) / (2.0 * h);
+                let ddt = (curve.velocity_at_parameter(t + h) - curve.velocity_at_parameter(t - h))
+                    / (2.0 * h);
+                assert!(
+                    (dt - velocity).norm() < 1e-6 * velocity.norm().max(1.0),
+                    "curve {} velocity {:?} difference {:?} at {}",
+                    index,
+                    velocity,
+                    dt,
+                    t
+                );
+                assert!(
+                    (ddt - acceleration).norm() < 1e-6 * acceleration.norm().max(1.0),
+                    "curve {} acceleration {:?} difference {:?} at {}",
+                    index,
+                    acceleration,
+                    ddt,
+                    t
+                );
+            }
+        }
+    }
 }
@@ -0,0 +1,656 @@
+use crate::types::Complexf;
+use fasteval::compiler::{Instruction, IC};
+use fasteval::{Compiler, Error, Evaler, InstructionI, Parser, Slab};
+use nalgebra::ComplexField;
+use std::collections::BTreeMap;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+// value with its first and second partial derivatives in (u, v)
+// https://en.wikipedia.org/wiki/Automatic_differentiation
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Jet {
+    pub value: f64,
+    pub du: f64,
+    pub dv: f64,
+    pub duu: f64,
+    pub duv: f64,
+    pub dvv: f64,
+}
+
+impl Jet {
+    pub fn constant(value: f64) -> Self {
+        Self {
+            value,
+            ..Default::default()
+        }
+    }
+
+    // the variable u itself
+    pub fn u(value: f64) -> Self {
+        Self {
+            value,
+            du: 1.0,
+            ..Default::default()
+        }
+    }
+
+    // the variable v itself
+    pub fn v(value: f64) -> Self {
+        Self {
+            value,
+            dv: 1.0,
+            ..Default::default()
+        }
+    }
+
+    pub fn is_constant(&self) -> bool {
+        self.du == 0.0 && self.dv == 0.0 && self.duu == 0.0 && self.duv == 0.0 && self.dvv == 0.0
+    }
+
+    pub fn scale(&self, s: f64) -> Self {
+        Self {
+            value: self.value * s,
+            du: self.du * s,
+            dv: self.dv * s,
+            duu: self.duu * s,
+            duv: self.duv * s,
+            dvv: self.dvv * s,
+        }
+    }
+
+    // φ(self) from φ, φ' and φ'' at the value
+    // (φ∘a)_i = φ' a_i, (φ∘a)_ij = φ'' a_i a_j + φ' a_ij
+    pub fn chain(&self, f: f64, df: f64, ddf: f64) -> Self {
+        Self {
+            value: f,
+            du: df * self.du,
+            dv: df * self.dv,
+            duu: ddf * self.du * self.du + df * self.duu,
+            duv: ddf * self.du * self.dv + df * self.duv,
+            dvv: ddf * self.dv * self.dv + df * self.dvv,
+        }
+    }
+
+    pub fn inv(&self) -> Self {
+        let x = self.value;
+        self.chain(1.0 / x, -1.0 / (x * x), 2.0 / (x * x * x))
+    }
+
+    pub fn ln(&self) -> Self {
+        let x = self.value;
+        self.chain(x.ln(), 1.0 / x, -1.0 / (x * x))
+    }
+
+    pub fn exp(&self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e, e)
+    }
+
+    pub fn powf(&self, power: &Jet) -> Self {
+        if power.is_constant() {
+            // x^c keeps negative bases and integer powers exact
+            let (x, c) = (self.value, power.value);
+            self.chain(
+                x.powf(c),
+                c * x.powf(c - 1.0),
+                c * (c - 1.0) * x.powf(c - 2.0),
+            )
+        } else if self.is_constant() {
+            let (b, p) = (self.value, power.value);
+            let l = b.ln();
+            let e = b.powf(p);
+            power.chain(e, l * e, l * l * e)
+        } else {
+            (*power * self.ln()).exp()
+        }
+    }
+
+    pub fn abs(&self) -> Self {
+        if self.value < 0.0 {
+            -*self
+        } else {
+            *self
+        }
+    }
+
+    pub fn sin(&self) -> Self {
+        let (s, c) = self.value.sin_cos();
+        self.chain(s, c, -s)
+    }
+
+    pub fn cos(&self) -> Self {
+        let (s, c) = self.value.sin_cos();
+        self.chain(c, -s, -c)
+    }
+
+    pub fn tan(&self) -> Self {
+        let t = self.value.tan();
+        let sec2 = 1.0 + t * t;
+        self.chain(t, sec2, 2.0 * t * sec2)
+    }
+
+    pub fn asin(&self) -> Self {
+        let x = self.value;
+        let r = 1.0 - x * x;
+        self.chain(x.asin(), 1.0 / r.sqrt(), x / (r * r.sqrt()))
+    }
+
+    pub fn acos(&self) -> Self {
+        let x = self.value;
+        let r = 1.0 - x * x;
+        self.chain(x.acos(), -1.0 / r.sqrt(), -x / (r * r.sqrt()))
+    }
+
+    pub fn atan(&self) -> Self {
+        let x = self.value;
+        let r = 1.0 + x * x;
+        self.chain(x.atan(), 1.0 / r, -2.0 * x / (r * r))
+    }
+
+    pub fn sinh(&self) -> Self {
+        let x = self.value;
+        self.chain(x.sinh(), x.cosh(), x.sinh())
+    }
+
+    pub fn cosh(&self) -> Self {
+        let x = self.value;
+        self.chain(x.cosh(), x.sinh(), x.cosh())
+    }
+
+    pub fn tanh(&self) -> Self {
+        let t = self.value.tanh();
+        let sech2 = 1.0 - t * t;
+        self.chain(t, sech2, -2.0 * t * sech2)
+    }
+
+    pub fn asinh(&self) -> Self {
+        let x = self.value;
+        let r = x * x + 1.0;
+        self.chain(x.asinh(), 1.0 / r.sqrt(), -x / (r * r.sqrt()))
+    }
+
+    pub fn acosh(&self) -> Self {
+        let x = self.value;
+        let r = x * x - 1.0;
+        self.chain(x.acosh(), 1.0 / r.sqrt(), -x / (r * r.sqrt()))
+    }
+
+    pub fn atanh(&self) -> Self {
+        let x = self.value;
+        let r = 1.0 - x * x;
+        self.chain(x.atanh(), 1.0 / r, 2.0 * x / (r * r))
+    }
+}
+
+impl Add for Jet {
+    type Output = Jet;
+    fn add(self, other: Jet) -> Jet {
+        Jet {
+            value: self.value + other.value,
+            du: self.du + other.du,
+            dv: self.dv + other.dv,
+            duu: self.duu + other.duu,
+            duv: self.duv + other.duv,
+            dvv: self.dvv + other.dvv,
+        }
+    }
+}
+
+impl Sub for Jet {
+    type Output = Jet;
+    fn sub(self, other: Jet) -> Jet {
+        self + -other
+    }
+}
+
+impl Neg for Jet {
+    type Output = Jet;
+    fn neg(self) -> Jet {
+        self.scale(-1.0)
+    }
+}
+
+// (ab)_ij = a_ij b + a_i b_j + a_j b_i + a b_ij
+impl Mul for Jet {
+    type Output = Jet;
+    fn mul(self, other: Jet) -> Jet {
+        let (a, b) = (self, other);
+        Jet {
+            value: a.value * b.value,
+            du: a.du * b.value + a.value * b.du,
+            dv: a.dv * b.value + a.value * b.dv,
+            duu: a.duu * b.value + 2.0 * a.du * b.du + a.value * b.duu,
+            duv: a.duv * b.value + a.du * b.dv + a.dv * b.du + a.value * b.duv,
+            dvv: a.dvv * b.value + 2.0 * a.dv * b.dv + a.value * b.dvv,
+        }
+    }
+}
+
+// a holomorphic value with its complex derivative in w
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct ComplexJet {
+    pub value: Complexf,
+    pub dw: Complexf,
+}
+
+impl ComplexJet {
+    pub fn constant(value: Complexf) -> Self {
+        Self {
+            value,
+            dw: Complexf::new(0.0, 0.0),
+        }
+    }
+
+    // the variable w itself
+    pub fn w(value: Complexf) -> Self {
+        Self {
+            value,
+            dw: Complexf::new(1.0, 0.0),
+        }
+    }
+
+    // φ(self) from φ and φ' at the value
+    pub fn chain(&self, f: Complexf, df: Complexf) -> Self {
+        Self {
+            value: f,
+            dw: df * self.dw,
+        }
+    }
+
+    pub fn inv(&self) -> Self {
+        let inv = self.value.inv();
+        self.chain(inv, -inv * inv)
+    }
+
+    pub fn exp(&self) -> Self {
+        let e = self.value.exp();
+        self.chain(e, e)
+    }
+
+    pub fn ln(&self) -> Self {
+        self.chain(self.value.ln(), self.value.inv())
+    }
+
+    pub fn sqrt(&self) -> Self {
+        let s = self.value.sqrt();
+        self.chain(s, (s * 2.0).inv())
+    }
+
+    // integer powers stay exact at zero, a^b = exp(b ln a) otherwise
+    pub fn powc(&self, power: &ComplexJet) -> Self {
+        let p = power.value;
+        let constant = power.dw == Complexf::new(0.0, 0.0);
+        if constant && p.im == 0.0 && p.re.fract() == 0.0 && p.re.abs() < 1e9 {
+            let n = p.re as i32;
+            if n == 0 {
+                return Self::constant(Complexf::new(1.0, 0.0));
+            }
+            return self.chain(self.value.powi(n), self.value.powi(n - 1) * p.re);
+        }
+        (*power * self.ln()).exp()
+    }
+
+    pub fn sin(&self) -> Self {
+        self.chain(self.value.sin(), self.value.cos())
+    }
+
+    pub fn cos(&self) -> Self {
+        self.chain(self.value.cos(), -self.value.sin())
+    }
+
+    pub fn tan(&self) -> Self {
+        let c = self.value.cos();
+        self.chain(self.value.tan(), (c * c).inv())
+    }
+
+    pub fn asin(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.asin(), (one - a * a).sqrt().inv())
+    }
+
+    pub fn acos(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.acos(), -(one - a * a).sqrt().inv())
+    }
+
+    pub fn atan(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.atan(), (one + a * a).inv())
+    }
+
+    pub fn sinh(&self) -> Self {
+        self.chain(self.value.sinh(), self.value.cosh())
+    }
+
+    pub fn cosh(&self) -> Self {
+        self.chain(self.value.cosh(), self.value.sinh())
+    }
+
+    pub fn tanh(&self) -> Self {
+        let c = self.value.cosh();
+        self.chain(self.value.tanh(), (c * c).inv())
+    }
+
+    pub fn asinh(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.asinh(), (a * a + one).sqrt().inv())
+    }
+
+    pub fn acosh(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.acosh(), ((a - one).sqrt() * (a + one).sqrt()).inv())
+    }
+
+    pub fn atanh(&self) -> Self {
+        let one = Complexf::new(1.0, 0.0);
+        let a = self.value;
+        self.chain(a.atanh(), (one - a * a).inv())
+    }
+}
+
+impl Add for ComplexJet {
+    type Output = ComplexJet;
+    fn add(self, other: ComplexJet) -> ComplexJet {
+        ComplexJet {
+            value: self.value + other.value,
+            dw: self.dw + other.dw,
+        }
+    }
+}
+
+impl Sub for ComplexJet {
+    type Output = ComplexJet;
+    fn sub(self, other: ComplexJet) -> ComplexJet {
+        self + -other
+    }
+}
+
+impl Neg for ComplexJet {
+    type Output = ComplexJet;
+    fn neg(self) -> ComplexJet {
+        ComplexJet {
+            value: -self.value,
+            dw: -self.dw,
+        }
+    }
+}
+
+impl Mul for ComplexJet {
+    type Output = ComplexJet;
+    fn mul(self, other: ComplexJet) -> ComplexJet {
+        ComplexJet {
+            value: self.value * other.value,
+            dw: self.dw * other.value + self.value * other.dw,
+        }
+    }
+}
+
+impl Div for ComplexJet {
+    type Output = ComplexJet;
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: ComplexJet) -> ComplexJet {
+        self * other.inv()
+    }
+}
+
+fn boolean(b: bool) -> Jet {
+    Jet::constant(if b { 1.0 } else { 0.0 })
+}
+
+// a formula compiled once by fasteval,
+// evaluated either as a plain value or as a jet for exact derivatives
+pub struct Expression {
+    source: String,
+    slab: Slab,
+    instruction: Instruction,
+}
+
+impl Clone for Expression {
+    fn clone(&self) -> Self {
+        // the source already compiled once
+        Expression::parse(&self.source).unwrap()
+    }
+}
+
+impl Expression {
+    pub fn parse(source: &str) -> Result<Self, Error> {
+        let mut slab = Slab::new();
+        let instruction = Parser::new()
+            .parse(source, &mut slab.ps)?
+            .from(&slab.ps)
+            .compile(&slab.ps, &mut slab.cs);
+        Ok(Self {
+            source: source.to_string(),
+            slab,
+            instruction,
+        })
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    pub fn eval(&self, variables: &BTreeMap<String, f64>) -> Result<f64, Error> {
+        let mut variables = variables.clone();
+        self.instruction.eval(&self.slab, &mut variables)
+    }
+
+    // the jet of the formula from the jets of its variables,
+    // piecewise functions (abs, min, max, %, comparisons, ...) differentiate the active branch
+    pub fn jet(&self, variables: &BTreeMap<String, Jet>) -> Result<Jet, Error> {
+        self.jet_of(&self.instruction, variables)
+    }
+
+    fn jet_at(&self, i: &InstructionI, variables: &BTreeMap<String, Jet>) -> Result<Jet, Error> {
+        self.jet_of(self.slab.cs.get_instr(*i), variables)
+    }
+
+    fn jet_ic(&self, ic: &IC, variables: &BTreeMap<String, Jet>) -> Result<Jet, Error> {
+        match ic {
+            IC::C(c) => Ok(Jet::constant(*c)),
+            IC::I(i) => self.jet_at(i, variables),
+        }
+    }
+
+    fn jet_of(
+        &self,
+        instruction: &Instruction,
+        variables: &BTreeMap<String, Jet>,
+    ) -> Result<Jet, Error> {
+        let at = |i: &InstructionI| self.jet_at(i, variables);
+        let ic = |i: &IC| self.jet_ic(i, variables);
+        use Instruction::*;
+        let jet = match instruction {
+            IConst(c) => Jet::constant(*c),
+            IVar(name) => *variables
+                .get(name)
+                .ok_or_else(|| Error::Undefined(name.clone()))?,
+            INeg(a) => -at(a)?,
+            INot(a) => boolean(at(a)?.value == 0.0),
+            IInv(a) => at(a)?.inv(),
+            IAdd(a, b) => at(a)? + ic(b)?,
+            IMul(a, b) => at(a)? * ic(b)?,
+            IMod { dividend, divisor } => {
+                // a % b = a - b trunc(a / b)
+                let (a, b) = (ic(dividend)?, ic(divisor)?);
+                a - b.scale((a.value / b.value).trunc())
+            }
+            IExp { base, power } => ic(base)?.powf(&ic(power)?),
+            ILT(a, b) => boolean(ic(a)?.value < ic(b)?.value),
+            ILTE(a, b) => boolean(ic(a)?.value <= ic(b)?.value),
+            IEQ(a, b) => boolean(ic(a)?.value == ic(b)?.value),
+            INE(a, b) => boolean(ic(a)?.value != ic(b)?.value),
+            IGTE(a, b) => boolean(ic(a)?.value >= ic(b)?.value),
+            IGT(a, b) => boolean(ic(a)?.value > ic(b)?.value),
+            IOR(a, b) => {
+                let a = at(a)?;
+                if a.value != 0.0 {
+                    a
+                } else {
+                    ic(b)?
+                }
+            }
+            IAND(a, b) => {
+                let a = at(a)?;
+                if a.value == 0.0 {
+                    a
+                } else {
+                    ic(b)?
+                }
+            }
+            IFunc { name, .. } => return Err(Error::Undefined(name.clone())),
+            IFuncInt(a) => Jet::constant(at(a)?.value.trunc()),
+            IFuncCeil(a) => Jet::constant(at(a)?.value.ceil()),
+            IFuncFloor(a) => Jet::constant(at(a)?.value.floor()),
+            IFuncAbs(a) => at(a)?.abs(),
+            IFuncSign(a) => Jet::constant(at(a)?.value.signum()),
+            IFuncLog { base, of } => ic(of)?.ln() * ic(base)?.ln().inv(),
+            IFuncRound { modulus, of } => {
+                let (m, x) = (ic(modulus)?.value, ic(of)?.value);
+                Jet::constant((x / m).round() * m)
+            }
+            IFuncMin(a, b) => {
+                let (a, b) = (at(a)?, ic(b)?);
+                if b.value < a.value {
+                    b
+                } else {
+                    a
+                }
+            }
+            IFuncMax(a, b) => {
+                let (a, b) = (at(a)?, ic(b)?);
+                if b.value > a.value {
+                    b
+                } else {
+                    a
+                }
+            }
+            IFuncSin(a) => at(a)?.sin(),
+            IFuncCos(a) => at(a)?.cos(),
+            IFuncTan(a) => at(a)?.tan(),
+            IFuncASin(a) => at(a)?.asin(),
+            IFuncACos(a) => at(a)?.acos(),
+            IFuncATan(a) => at(a)?.atan(),
+            IFuncSinH(a) => at(a)?.sinh(),
+            IFuncCosH(a) => at(a)?.cosh(),
+            IFuncTanH(a) => at(a)?.tanh(),
+            IFuncASinH(a) => at(a)?.asinh(),
+            IFuncACosH(a) => at(a)?.acosh(),
+            IFuncATanH(a) => at(a)?.atanh(),
+            IPrintFunc(_) => return Err(Error::Undefined(String::from("print"))),
+        };
+        Ok(jet)
+    }
+
+    // the formula as a holomorphic function of its complex variables, e.g. w and i,
+    // the functions without a complex derivative (abs, min, comparisons, ...) are undefined,
+    // exp(a), sqrt(a) and ln(a) may be used besides e()^a, a^0.5 and log(e(), a)
+    pub fn complex(&self, variables: &BTreeMap<String, ComplexJet>) -> Result<ComplexJet, Error> {
+        self.complex_of(&self.instruction, variables)
+    }
+
+    fn complex_at(
+        &self,
+        i: &InstructionI,
+        variables: &BTreeMap<String, ComplexJet>,
+    ) -> Result<ComplexJet, Error> {
+        self.complex_of(self.slab.cs.get_instr(*i), variables)
+    }
+
+    fn complex_ic(
+        &self,
+        ic: &IC,
+        variables: &BTreeMap<String, ComplexJet>,
+    ) -> Result<ComplexJet, Error> {
+        match ic {
+            IC::C(c) => Ok(ComplexJet::constant(Complexf::new(*c, 0.0))),
+            IC::I(i) => self.complex_at(i, variables),
+        }
+    }
+
+    fn complex_of(
+        &self,
+        instruction: &Instruction,
+        variables: &BTreeMap<String, ComplexJet>,
+    ) -> Result<ComplexJet, Error> {
+        let at = |i: &InstructionI| self.complex_at(i, variables);
+        let ic = |i: &IC| self.complex_ic(i, variables);
+        let undefined = |name: &str| Err(Error::Undefined(name.to_string()));
+        use Instruction::*;
+        let jet = match instruction {
+            IConst(c) => ComplexJet::constant(Complexf::new(*c, 0.0)),
+            IVar(name) => *variables
+                .get(name)
+                .ok_or_else(|| Error::Undefined(name.clone()))?,
+            INeg(a) => -at(a)?,
+            IInv(a) => at(a)?.inv(),
+            IAdd(a, b) => at(a)? + ic(b)?,
+            IMul(a, b) => at(a)? * ic(b)?,
+            IExp { base, power } => ic(base)?.powc(&ic(power)?),
+            IFuncLog { base, of } => ic(of)?.ln() / ic(base)?.ln(),
+            IFunc { name, args } => match (name.as_str(), args.as_slice()) {
+                ("exp", [a]) => ic(a)?.exp(),
+                ("sqrt", [a]) => ic(a)?.sqrt(),
+                ("ln", [a]) => ic(a)?.ln(),
+                _ => return undefined(name),
+            },
+            IFuncSin(a) => at(a)?.sin(),
+            IFuncCos(a) => at(a)?.cos(),
+            IFuncTan(a) => at(a)?.tan(),
+            IFuncASin(a) => at(a)?.asin(),
+            IFuncACos(a) => at(a)?.acos(),
+            IFuncATan(a) => at(a)?.atan(),
+            IFuncSinH(a) => at(a)?.sinh(),
+            IFuncCosH(a) => at(a)?.cosh(),
+            IFuncTanH(a) => at(a)?.tanh(),
+            IFuncASinH(a) => at(a)?.asinh(),
+            IFuncACosH(a) => at(a)?.acosh(),
+            IFuncATanH(a) => at(a)?.atanh(),
+            IMod { .. } => return undefined("%"),
+            INot(_) | ILT(..) | ILTE(..) | IEQ(..) | INE(..) | IGTE(..) | IGT(..) | IOR(..)
+            | IAND(..) => return undefined("comparison"),
+            IFuncInt(_) => return undefined("int"),
+            IFuncCeil(_) => return undefined("ceil"),
+            IFuncFloor(_) => return undefined("floor"),
+            IFuncAbs(_) => return undefined("abs"),
+            IFuncSign(_) => return undefined("sign"),
+            IFuncRound { .. } => return undefined("round"),
+            IFuncMin(..) => return undefined("min"),
+            IFuncMax(..) => return undefined("max"),
+            IPrintFunc(_) => return undefined("print"),
+        };
+        Ok(jet)
+    }
+}
+
+// named parameters from "a = 1, b = pi() / 2", entries separated by ',' or ';',
+// values may be formulas of the parameters defined before them
+pub fn parse_parameters(source: &str) -> Result<BTreeMap<String, f64>, Error> {
+    let mut parameters: BTreeMap<String, f64> = BTreeMap::new();
+    for entry in source.split([',', ';']) {
+        let entry = entry.trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (name, value) = match entry.find('=') {
+            Some(i) => (entry[..i].trim(), entry[i + 1..].trim()),
+            None => return Err(Error::Expected(String::from("name = value"))),
+        };
+        let valid = name
+            .chars()
+            .next()
+            .is_some_and(|c| c.is_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+        if !valid {
+            return Err(Error::InvalidValue);
+        }
+        let value = Expression::parse(value)?.eval(&parameters)?;
+        parameters.insert(name.to_string(), value);
+    }
+    Ok(parameters)
+}
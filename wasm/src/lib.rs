@@ -1,16 +1,23 @@
 mod utils;
 
 pub mod app;
+pub mod bspline;
 pub mod curve_tessellation;
 pub mod curves;
+pub mod darboux_frame;
 pub mod domain;
+pub mod expression;
 pub mod frenet_frame;
 pub mod log;
 pub mod optimizer;
 pub mod plane;
+pub mod quadrature;
 pub mod surface_tessellation;
 pub mod surfaces;
+pub mod triangle_mesh;
 pub mod types;
+pub mod umbilic_tessellation;
+pub mod vector_field_tessellation;
 
 // When the `wee_alloc` feature is enabled, use `wee_alloc` as the global
 // allocator.
@@ -1,2 +1,3 @@
 pub mod df;
+#[allow(clippy::module_inception)]
 pub mod optimizer;
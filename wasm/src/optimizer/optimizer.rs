@@ -64,9 +64,8 @@ impl Optimizer for GradientDescentOptimizer {
 
     fn iterate(&mut self, expr: &F2x1) -> Option<Matrix2x1f> {
         let next = self.next(expr, &self.x);
-        match next {
-            Some(x) => self.x = x,
-            None => {}
+        if let Some(x) = next {
+            self.x = x;
         }
         next
     }
@@ -93,9 +92,8 @@ impl Optimizer for NewtonOptimizer {
 
     fn iterate(&mut self, expr: &F2x1) -> Option<Matrix2x1f> {
         let next = self.next(expr, &self.x);
-        match next {
-            Some(x) => self.x = x,
-            None => {}
+        if let Some(x) = next {
+            self.x = x;
         }
         next
     }
@@ -1,5 +1,6 @@
 use crate::types::*;
 
+#[derive(Clone)]
 pub struct Plane {
     pub origin: Point3f,
     pub normal: Vector3f,
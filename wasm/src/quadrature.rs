@@ -0,0 +1,184 @@
+use core::f64::consts::PI;
+
+// numerical integration over the unit square of normalized (u, v)
+#[derive(Clone, Copy, Debug)]
+pub enum Quadrature {
+    // tensor Gauss-Legendre rule of the order on each of cells x cells uv cells
+    GaussLegendre {
+        order: usize,
+        cells: usize,
+    },
+    // the cell of the largest error, the difference between the rule on it and the sum over
+    // its four children, is split until the errors add up within the tolerance relative to
+    // the integral or the rule has been applied on the given number of cells
+    Adaptive {
+        order: usize,
+        tolerance: f64,
+        cells: usize,
+    },
+}
+
+impl Default for Quadrature {
+    fn default() -> Self {
+        Quadrature::Adaptive {
+            order: 8,
+            tolerance: 1e-7,
+            cells: 1024,
+        }
+    }
+}
+
+// nodes and weights of the Gauss-Legendre rule on [0, 1]
+// https://en.wikipedia.org/wiki/Gauss%E2%80%93Legendre_quadrature
+pub fn gauss_legendre(order: usize) -> (Vec<f64>, Vec<f64>) {
+    let n = order.max(1);
+    let mut nodes = vec![0.0; n];
+    let mut weights = vec![0.0; n];
+    for i in 0..n {
+        // Newton's method on P_n from the asymptotic guess of the i-th root
+        let mut x = (PI * (i as f64 + 0.75) / (n as f64 + 0.5)).cos();
+        let mut dp = 1.0;
+        for _ in 0..100 {
+            // P_n(x) and P_n'(x) by the three term recurrence
+            let (mut p0, mut p1) = (1.0, x);
+            for k in 2..=n {
+                let p2 = ((2 * k - 1) as f64 * x * p1 - (k - 1) as f64 * p0) / k as f64;
+                p0 = p1;
+                p1 = p2;
+            }
+            dp = n as f64 * (x * p1 - p0) / (x * x - 1.0);
+            let dx = p1 / dp;
+            x -= dx;
+            if dx.abs() < 1e-15 {
+                break;
+            }
+        }
+        // map from [-1, 1] to [0, 1]
+        nodes[i] = (1.0 - x) * 0.5;
+        weights[i] = 1.0 / ((1.0 - x * x) * dp * dp);
+    }
+    (nodes, weights)
+}
+
+struct Rule {
+    nodes: Vec<f64>,
+    weights: Vec<f64>,
+}
+
+impl Rule {
+    fn new(order: usize) -> Self {
+        let (nodes, weights) = gauss_legendre(order);
+        Self { nodes, weights }
+    }
+
+    // the tensor rule on [u0, u0 + size] x [v0, v0 + size]
+    fn cell(&self, f: &dyn Fn(f64, f64) -> f64, u0: f64, v0: f64, size: f64) -> f64 {
+        let mut sum = 0.0;
+        for (xu, wu) in self.nodes.iter().zip(self.weights.iter()) {
+            for (xv, wv) in self.nodes.iter().zip(self.weights.iter()) {
+                sum += wu * wv * f(u0 + xu * size, v0 + xv * size);
+            }
+        }
+        sum * size * size
+    }
+
+    // the rule on the four children of the cell whose own rule gave whole
+    fn split(
+        &self,
+        f: &dyn Fn(f64, f64) -> f64,
+        (u, v, size): (f64, f64, f64),
+        whole: f64,
+    ) -> Cell {
+        let half = size * 0.5;
+        let mut parts = [0.0; 4];
+        for (k, part) in parts.iter_mut().enumerate() {
+            let (cu, cv) = Cell::child(u, v, half, k);
+            *part = self.cell(f, cu, cv, half);
+        }
+        let error = (parts.iter().sum::<f64>() - whole).abs();
+        Cell {
+            u,
+            v,
+            size,
+            parts,
+            error,
+        }
+    }
+}
+
+// a cell of the adaptive rule with the rule on each of its children
+struct Cell {
+    u: f64,
+    v: f64,
+    size: f64,
+    parts: [f64; 4],
+    error: f64,
+}
+
+impl Cell {
+    // origin of the k-th child of the half size
+    fn child(u: f64, v: f64, half: f64, k: usize) -> (f64, f64) {
+        (u + half * (k % 2) as f64, v + half * (k / 2) as f64)
+    }
+
+    fn integral(&self) -> f64 {
+        self.parts.iter().sum()
+    }
+}
+
+impl Quadrature {
+    // ∫[0, 1]∫[0, 1] f(u, v) dudv
+    pub fn integrate(&self, f: &dyn Fn(f64, f64) -> f64) -> f64 {
+        match *self {
+            Quadrature::GaussLegendre { order, cells } => {
+                let rule = Rule::new(order);
+                let cells = cells.max(1);
+                let size = 1.0 / cells as f64;
+                (0..cells)
+                    .flat_map(|i| (0..cells).map(move |j| (i, j)))
+                    .map(|(i, j)| rule.cell(f, i as f64 * size, j as f64 * size, size))
+                    .sum()
+            }
+            Quadrature::Adaptive {
+                order,
+                tolerance,
+                cells: budget,
+            } => {
+                let rule = Rule::new(order);
+                // start from a few cells to catch features a single cell misses
+                let count = 4;
+                let size = 1.0 / count as f64;
+                let mut scale = 0.0;
+                let mut cells: Vec<Cell> = (0..count)
+                    .flat_map(|i| (0..count).map(move |j| (i, j)))
+                    .map(|(i, j)| {
+                        let (u, v) = (i as f64 * size, j as f64 * size);
+                        scale += rule.cell(&|u, v| f(u, v).abs(), u, v, size);
+                        rule.split(f, (u, v, size), rule.cell(f, u, v, size))
+                    })
+                    .collect();
+                // the rule on the cell, on |f| over it and on its four children
+                let mut spent = count * count * 6;
+                // splitting a cell applies the rule on its sixteen grandchildren
+                while spent + 16 <= budget {
+                    // relative to ∬|f| so that integrals cancelling to zero still terminate
+                    let error: f64 = cells.iter().map(|c| c.error).sum();
+                    if error <= tolerance * scale {
+                        break;
+                    }
+                    let worst = (0..cells.len())
+                        .max_by(|a, b| cells[*a].error.total_cmp(&cells[*b].error))
+                        .unwrap();
+                    let cell = cells.swap_remove(worst);
+                    let half = cell.size * 0.5;
+                    for (k, whole) in cell.parts.iter().enumerate() {
+                        let (u, v) = Cell::child(cell.u, cell.v, half, k);
+                        cells.push(rule.split(f, (u, v, half), *whole));
+                    }
+                    spent += 16;
+                }
+                cells.iter().map(|c| c.integral()).sum()
+            }
+        }
+    }
+}
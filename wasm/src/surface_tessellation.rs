@@ -1,3 +1,4 @@
+use crate::surfaces::surface_curvature::SurfaceCurvature;
 use nalgebra::{Point3, Vector3};
 use wasm_bindgen::prelude::*;
 
@@ -9,6 +10,10 @@ pub struct SurfaceTessellation {
     normals: Vec<Vector3<f32>>,
     gauss_curvature: Vec<f32>,
     mean_curvature: Vec<f32>,
+    k1: Vec<f32>,
+    k2: Vec<f32>,
+    principal_directions1: Vec<Vector3<f32>>,
+    principal_directions2: Vec<Vector3<f32>>,
     indices: Vec<usize>,
 }
 
@@ -16,22 +21,50 @@ impl SurfaceTessellation {
     pub fn new(
         rows: usize,
         columns: usize,
-        points: Vec<Point3<f32>>,
-        normals: Vec<Vector3<f32>>,
-        gauss_curvature: Vec<f32>,
-        mean_curvature: Vec<f32>,
+        curvatures: &[SurfaceCurvature<f64>],
         indices: Vec<usize>,
     ) -> Self {
         Self {
             rows,
             columns,
-            points,
-            normals,
-            gauss_curvature,
-            mean_curvature,
+            points: curvatures.iter().map(|c| c.point.cast()).collect(),
+            normals: curvatures.iter().map(|c| c.normal.cast()).collect(),
+            gauss_curvature: curvatures.iter().map(|c| c.gaussian as f32).collect(),
+            mean_curvature: curvatures.iter().map(|c| c.mean as f32).collect(),
+            k1: curvatures.iter().map(|c| c.k1 as f32).collect(),
+            k2: curvatures.iter().map(|c| c.k2 as f32).collect(),
+            principal_directions1: curvatures
+                .iter()
+                .map(|c| c.principal_directions.0.normalize().cast())
+                .collect(),
+            principal_directions2: curvatures
+                .iter()
+                .map(|c| c.principal_directions.1.normalize().cast())
+                .collect(),
             indices,
         }
     }
+
+    pub fn vertices(&self) -> &[Point3<f32>] {
+        &self.points
+    }
+
+    pub fn vertex_normals(&self) -> &[Vector3<f32>] {
+        &self.normals
+    }
+
+    pub fn gaussian_curvatures(&self) -> &[f32] {
+        &self.gauss_curvature
+    }
+
+    pub fn mean_curvatures(&self) -> &[f32] {
+        &self.mean_curvature
+    }
+
+    // three vertex indices per triangle
+    pub fn triangles(&self) -> &[usize] {
+        &self.indices
+    }
 }
 
 #[wasm_bindgen]
@@ -72,6 +105,22 @@ impl SurfaceTessellation {
         self.mean_curvature.as_ptr()
     }
 
+    pub fn k1(&self) -> *const f32 {
+        self.k1.as_ptr()
+    }
+
+    pub fn k2(&self) -> *const f32 {
+        self.k2.as_ptr()
+    }
+
+    pub fn principal_directions1(&self) -> *const Vector3<f32> {
+        self.principal_directions1.as_ptr()
+    }
+
+    pub fn principal_directions2(&self) -> *const Vector3<f32> {
+        self.principal_directions2.as_ptr()
+    }
+
     pub fn indices(&self) -> *const usize {
         self.indices.as_ptr()
     }
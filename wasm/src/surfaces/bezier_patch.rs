@@ -0,0 +1,126 @@
+use crate::bspline::bernstein;
+use crate::domain::Domain;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+
+// tensor product Bézier patch, S(u, v) = Σ B_i(u) B_j(v) P_ij,
+// control_points[i][j] runs along u with i and along v with j
+// https://en.wikipedia.org/wiki/B%C3%A9zier_surface
+#[derive(Clone)]
+pub struct BezierPatch {
+    control_points: Vec<Vec<Point3f>>,
+}
+
+impl BezierPatch {
+    // None unless the net is rectangular with at least two points in each direction
+    pub fn new(control_points: Vec<Vec<Point3f>>) -> Option<Self> {
+        let columns = control_points.first().map_or(0, |r| r.len());
+        if control_points.len() < 2
+            || columns < 2
+            || control_points.iter().any(|r| r.len() != columns)
+        {
+            return None;
+        }
+        Some(Self { control_points })
+    }
+
+    pub fn degrees(&self) -> (usize, usize) {
+        (
+            self.control_points.len() - 1,
+            self.control_points[0].len() - 1,
+        )
+    }
+
+    pub fn control_points(&self) -> &[Vec<Point3f>] {
+        &self.control_points
+    }
+
+    // the rows along u and the columns along v of the net as polylines
+    pub fn control_net(&self) -> Vec<Vec<Point3f>> {
+        control_net(&self.control_points)
+    }
+
+    // the partial derivative ∂^(k+l) S / ∂u^k ∂v^l for k, l <= 2
+    fn derivative(&self, u: f64, v: f64, k: usize, l: usize) -> Vector3f {
+        let (p, q) = self.degrees();
+        let bu = bernstein(p, u, 2);
+        let bv = bernstein(q, v, 2);
+        self.control_points
+            .iter()
+            .zip(bu[k].iter())
+            .flat_map(|(row, a)| {
+                row.iter()
+                    .zip(bv[l].iter())
+                    .map(move |(point, b)| point.coords * (a * b))
+            })
+            .sum()
+    }
+}
+
+// the rows and columns of a rectangular net
+pub fn control_net(net: &[Vec<Point3f>]) -> Vec<Vec<Point3f>> {
+    let rows = net.iter().map(|r| r.to_vec());
+    let columns = (0..net[0].len()).map(|j| net.iter().map(|r| r[j]).collect());
+    rows.chain(columns).collect()
+}
+
+impl Default for BezierPatch {
+    // a bicubic hill with a dip in one corner
+    fn default() -> Self {
+        let heights = [
+            [0.0, 0.5, 0.5, 0.0],
+            [0.5, 2.0, 1.5, 0.0],
+            [0.5, 1.5, -1.0, -0.5],
+            [0.0, 0.0, -0.5, 0.0],
+        ];
+        let net = (0..4)
+            .map(|i| {
+                (0..4)
+                    .map(|j| {
+                        Point3f::new(
+                            (i as f64 - 1.5) * 1.5,
+                            (j as f64 - 1.5) * 1.5,
+                            heights[i][j],
+                        )
+                    })
+                    .collect()
+            })
+            .collect();
+        BezierPatch::new(net).unwrap()
+    }
+}
+
+impl Surface for BezierPatch {
+    fn u_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.derivative(u, v, 0, 0))
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 0)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 1)
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 2, 0)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 1)
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 2)
+    }
+}
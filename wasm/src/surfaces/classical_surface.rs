@@ -0,0 +1,346 @@
+use crate::domain::Domain;
+use crate::expression::Jet;
+use crate::plane::Plane;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+use core::f64::consts::{FRAC_PI_2, PI, SQRT_2, TAU};
+
+// keeps the normals defined at the poles as Sphere does
+const POLE: f64 = 1e-8;
+
+// closed form shapes of the curriculum in the coordinates of the plane,
+// the second derivatives are exact by differentiating the formulas as jets
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClassicalShape {
+    // (a sin u cos v, b sin u sin v, c cos u)
+    Ellipsoid { a: f64, b: f64, c: f64 },
+    // (a cosh u cos v, b cosh u sin v, c sinh u)
+    HyperboloidOfOneSheet { a: f64, b: f64, c: f64 },
+    // (a sinh u cos v, b sinh u sin v, c cosh u), the sheet above the plane,
+    // the other one is its mirror image
+    HyperboloidOfTwoSheets { a: f64, b: f64, c: f64 },
+    // (a u cos v, b u sin v, h u), the upper nappe cut short of the apex where H blows up
+    Cone { a: f64, b: f64, h: f64 },
+    // the figure eight immersion of the Klein bottle around a circle of radius r
+    // https://en.wikipedia.org/wiki/Klein_bottle#The_figure_8_immersion
+    KleinBottle { r: f64 },
+    // Boy's immersion of the projective plane scaled by s
+    // https://mathworld.wolfram.com/BoySurface.html
+    Boy { s: f64 },
+    // the ring cyclide with c = sqrt(a^2 - b^2) < d < a, an inversion of a torus
+    // https://en.wikipedia.org/wiki/Dupin_cyclide
+    DupinCyclide { a: f64, b: f64, d: f64 },
+    // the graph of x^3 - 3 x y^2
+    MonkeySaddle,
+    // (u - u^3 / 3 + u v^2, v - v^3 / 3 + v u^2, u^2 - v^2)
+    Enneper,
+    // (c cosh(u / c) cos v, c cosh(u / c) sin v, u)
+    Catenoid { c: f64 },
+    // Steiner's Roman surface, (r^2 cos u cos v sin v, r^2 sin u cos v sin v, r^2 cos u sin u cos^2 v)
+    RomanSurface { r: f64 },
+}
+
+impl ClassicalShape {
+    fn domains(&self) -> (Domain, Domain) {
+        match self {
+            ClassicalShape::Ellipsoid { .. } => {
+                (Domain::new(POLE, PI - POLE), Domain::periodic(0.0, TAU))
+            }
+            ClassicalShape::HyperboloidOfOneSheet { .. } => {
+                (Domain::new(-1.2, 1.2), Domain::periodic(0.0, TAU))
+            }
+            ClassicalShape::HyperboloidOfTwoSheets { .. } => {
+                (Domain::new(POLE, 1.5), Domain::periodic(0.0, TAU))
+            }
+            ClassicalShape::Cone { .. } => (Domain::new(0.05, 1.0), Domain::periodic(0.0, TAU)),
+            // the seam at u = 0, 2 pi flips v, so it is not treated as periodic
+            ClassicalShape::KleinBottle { .. } => {
+                (Domain::new(0.0, TAU), Domain::periodic(0.0, TAU))
+            }
+            ClassicalShape::Boy { .. } => {
+                (Domain::new(-FRAC_PI_2, FRAC_PI_2), Domain::new(0.0, PI))
+            }
+            ClassicalShape::DupinCyclide { .. } => {
+                (Domain::periodic(0.0, TAU), Domain::periodic(0.0, TAU))
+            }
+            ClassicalShape::MonkeySaddle => (Domain::new(-1.2, 1.2), Domain::new(-1.2, 1.2)),
+            ClassicalShape::Enneper => (Domain::new(-1.5, 1.5), Domain::new(-1.5, 1.5)),
+            ClassicalShape::Catenoid { .. } => (Domain::new(-1.5, 1.5), Domain::periodic(0.0, TAU)),
+            ClassicalShape::RomanSurface { .. } => {
+                (Domain::new(0.0, PI), Domain::new(-FRAC_PI_2, FRAC_PI_2))
+            }
+        }
+    }
+
+    // x, y, z as jets of the native (u, v)
+    fn jets(&self, u: Jet, v: Jet) -> [Jet; 3] {
+        let c = Jet::constant;
+        match *self {
+            ClassicalShape::Ellipsoid { a, b, c: h } => {
+                let su = u.sin();
+                [
+                    (su * v.cos()).scale(a),
+                    (su * v.sin()).scale(b),
+                    u.cos().scale(h),
+                ]
+            }
+            ClassicalShape::HyperboloidOfOneSheet { a, b, c: h } => {
+                let cu = u.cosh();
+                [
+                    (cu * v.cos()).scale(a),
+                    (cu * v.sin()).scale(b),
+                    u.sinh().scale(h),
+                ]
+            }
+            ClassicalShape::HyperboloidOfTwoSheets { a, b, c: h } => {
+                let su = u.sinh();
+                [
+                    (su * v.cos()).scale(a),
+                    (su * v.sin()).scale(b),
+                    u.cosh().scale(h),
+                ]
+            }
+            ClassicalShape::Cone { a, b, h } => {
+                [(u * v.cos()).scale(a), (u * v.sin()).scale(b), u.scale(h)]
+            }
+            ClassicalShape::KleinBottle { r } => {
+                let (sh, ch) = (u.scale(0.5).sin(), u.scale(0.5).cos());
+                let (sv, s2v) = (v.sin(), v.scale(2.0).sin());
+                let w = c(r) + ch * sv - sh * s2v;
+                [w * u.cos(), w * u.sin(), sh * sv + ch * s2v]
+            }
+            ClassicalShape::Boy { s } => {
+                let cv2 = v.cos() * v.cos();
+                let s2v = v.scale(2.0).sin();
+                let d = (c(2.0) - (u.scale(3.0).sin() * s2v).scale(SQRT_2)).inv();
+                [
+                    ((u.scale(2.0).cos() * cv2).scale(SQRT_2) + u.cos() * s2v) * d.scale(s),
+                    ((u.scale(2.0).sin() * cv2).scale(SQRT_2) - u.sin() * s2v) * d.scale(s),
+                    cv2 * d.scale(3.0 * s),
+                ]
+            }
+            ClassicalShape::DupinCyclide { a, b, d } => {
+                let h = (a * a - b * b).sqrt();
+                let (cu, cv) = (u.cos(), v.cos());
+                let q = (c(a) - (cu * cv).scale(h)).inv();
+                [
+                    ((c(h) - (cu * cv).scale(a)).scale(d) + cu.scale(b * b)) * q,
+                    (u.sin() * (c(a) - cv.scale(d))).scale(b) * q,
+                    (v.sin() * (cu.scale(h) - c(d))).scale(b) * q,
+                ]
+            }
+            ClassicalShape::MonkeySaddle => [u, v, u * u * u - (u * v * v).scale(3.0)],
+            ClassicalShape::Enneper => [
+                u - (u * u * u).scale(1.0 / 3.0) + u * v * v,
+                v - (v * v * v).scale(1.0 / 3.0) + v * u * u,
+                u * u - v * v,
+            ],
+            ClassicalShape::Catenoid { c: h } => {
+                let r = u.scale(1.0 / h).cosh().scale(h);
+                [r * v.cos(), r * v.sin(), u]
+            }
+            ClassicalShape::RomanSurface { r } => {
+                let (cu, su) = (u.cos(), u.sin());
+                let (cv, sv) = (v.cos(), v.sin());
+                let r2 = r * r;
+                [
+                    (cu * cv * sv).scale(r2),
+                    (su * cv * sv).scale(r2),
+                    (cu * su * cv * cv).scale(r2),
+                ]
+            }
+        }
+    }
+}
+
+pub struct ClassicalSurface {
+    plane: Plane,
+    shape: ClassicalShape,
+    u_domain: Domain,
+    v_domain: Domain,
+}
+
+impl Default for ClassicalSurface {
+    fn default() -> Self {
+        Self::new(
+            Plane::default(),
+            ClassicalShape::Ellipsoid {
+                a: 2.0,
+                b: 1.5,
+                c: 1.0,
+            },
+        )
+    }
+}
+
+impl ClassicalSurface {
+    pub fn new(plane: Plane, shape: ClassicalShape) -> Self {
+        let (u_domain, v_domain) = shape.domains();
+        Self {
+            plane,
+            shape,
+            u_domain,
+            v_domain,
+        }
+    }
+
+    pub fn ellipsoid() -> Self {
+        Self::default()
+    }
+
+    pub fn hyperboloid_of_one_sheet() -> Self {
+        Self::new(
+            Plane::default(),
+            ClassicalShape::HyperboloidOfOneSheet {
+                a: 1.0,
+                b: 1.0,
+                c: 1.0,
+            },
+        )
+    }
+
+    pub fn hyperboloid_of_two_sheets() -> Self {
+        Self::new(
+            Plane::default(),
+            ClassicalShape::HyperboloidOfTwoSheets {
+                a: 1.0,
+                b: 1.0,
+                c: 1.0,
+            },
+        )
+    }
+
+    pub fn cone() -> Self {
+        Self::new(
+            Plane::default(),
+            ClassicalShape::Cone {
+                a: 1.5,
+                b: 1.5,
+                h: 2.0,
+            },
+        )
+    }
+
+    pub fn klein_bottle() -> Self {
+        Self::new(Plane::default(), ClassicalShape::KleinBottle { r: 2.0 })
+    }
+
+    pub fn boy() -> Self {
+        Self::new(Plane::default(), ClassicalShape::Boy { s: 1.5 })
+    }
+
+    pub fn dupin_cyclide() -> Self {
+        Self::new(
+            Plane::default(),
+            ClassicalShape::DupinCyclide {
+                a: 2.0,
+                b: 1.96,
+                d: 0.6,
+            },
+        )
+    }
+
+    pub fn monkey_saddle() -> Self {
+        Self::new(Plane::default(), ClassicalShape::MonkeySaddle)
+    }
+
+    pub fn enneper() -> Self {
+        Self::new(Plane::default(), ClassicalShape::Enneper)
+    }
+
+    pub fn catenoid() -> Self {
+        Self::new(Plane::default(), ClassicalShape::Catenoid { c: 1.0 })
+    }
+
+    pub fn roman_surface() -> Self {
+        Self::new(Plane::default(), ClassicalShape::RomanSurface { r: 2.0 })
+    }
+
+    pub fn shape(&self) -> ClassicalShape {
+        self.shape
+    }
+
+    // jets of x, y, z at normalized (u, v), scaled to derivatives in normalized parameters
+    fn jets(&self, u: f64, v: f64) -> [Jet; 3] {
+        let (lu, lv) = (self.u_domain.length(), self.v_domain.length());
+        let u = Jet {
+            du: lu,
+            ..Jet::constant(self.u_domain.map(u))
+        };
+        let v = Jet {
+            dv: lv,
+            ..Jet::constant(self.v_domain.map(v))
+        };
+        self.shape.jets(u, v)
+    }
+
+    fn vector(&self, u: f64, v: f64, component: fn(&Jet) -> f64) -> Vector3f {
+        let [x, y, z] = self.jets(u, v);
+        self.plane.x_axis * component(&x)
+            + self.plane.y_axis * component(&y)
+            + self.plane.normal * component(&z)
+    }
+}
+
+impl Surface for ClassicalSurface {
+    fn u_domain(&self) -> Domain {
+        self.u_domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.v_domain.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        self.plane.origin + self.vector(u, v, |j| j.value)
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.du)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.dv)
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.duu)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.duv)
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.dvv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::umbilic::UmbilicType;
+    use crate::types::Point2f;
+
+    // the triaxial ellipsoid has four lemons in the plane of its longest and shortest axes
+    #[test]
+    fn ellipsoid_umbilics() {
+        let found = ClassicalSurface::ellipsoid().umbilics(48);
+        assert_eq!(found.len(), 4);
+        for umbilic in found.iter() {
+            assert_eq!(umbilic.kind, UmbilicType::Lemon);
+            assert_eq!(umbilic.index, 0.5);
+        }
+    }
+
+    // the monkey saddle has a star at its flat point
+    #[test]
+    fn monkey_saddle_umbilic() {
+        let found = ClassicalSurface::monkey_saddle().umbilics(33);
+        assert_eq!(found.len(), 1);
+        assert!((found[0].uv - Point2f::new(0.5, 0.5)).norm() < 1e-8);
+        assert_eq!(found[0].kind, UmbilicType::Star);
+        assert_eq!(found[0].index, -0.5);
+    }
+}
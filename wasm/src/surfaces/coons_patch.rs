@@ -0,0 +1,258 @@
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoonsBlending {
+    // f(t) = t
+    Bilinear,
+    // f(t) = 3 t^2 - 2 t^3, the cubic Hermite blend with flat ends
+    Bicubic,
+}
+
+impl CoonsBlending {
+    // (f, f', f'') of the blend toward the far boundary
+    fn jet(&self, t: f64) -> [f64; 3] {
+        match self {
+            CoonsBlending::Bilinear => [t, 1.0, 0.0],
+            CoonsBlending::Bicubic => {
+                [t * t * (3.0 - 2.0 * t), 6.0 * t * (1.0 - t), 6.0 - 12.0 * t]
+            }
+        }
+    }
+}
+
+// fills a closed loop of four curves running head to tail,
+// bottom (0, 0) -> (1, 0), right (1, 0) -> (1, 1), top (1, 1) -> (0, 1) and left (0, 1) -> (0, 0),
+// as the sum of the ruled surfaces between opposite sides less the patch of the corners
+// https://en.wikipedia.org/wiki/Coons_patch
+pub struct CoonsPatch {
+    // in the order of the loop
+    boundaries: [Box<dyn Curve>; 4],
+    blending: CoonsBlending,
+}
+
+impl CoonsPatch {
+    // None unless every curve ends where the next begins
+    pub fn new(
+        bottom: Box<dyn Curve>,
+        right: Box<dyn Curve>,
+        top: Box<dyn Curve>,
+        left: Box<dyn Curve>,
+        blending: CoonsBlending,
+    ) -> Option<Self> {
+        let boundaries = [bottom, right, top, left];
+        let closed = (0..4).all(|i| {
+            let end = boundaries[i].point_at(1.0);
+            let start = boundaries[(i + 1) % 4].point_at(0.0);
+            (end - start).norm() <= 1e-6 * end.coords.norm().max(1.0)
+        });
+        if closed {
+            Some(Self {
+                boundaries,
+                blending,
+            })
+        } else {
+            None
+        }
+    }
+
+    pub fn boundaries(&self) -> &[Box<dyn Curve>; 4] {
+        &self.boundaries
+    }
+
+    // the loop sampled at count points per side as polylines
+    pub fn control_net(&self, count: usize) -> Vec<Vec<Point3f>> {
+        let count = count.max(2);
+        self.boundaries
+            .iter()
+            .map(|c| {
+                (0..count)
+                    .map(|i| c.point_at(i as f64 / (count - 1) as f64))
+                    .collect()
+            })
+            .collect()
+    }
+
+    // the derivative of the given order of the side running forward (reversed) in t
+    fn side(&self, index: usize, t: f64, order: usize, reversed: bool) -> Vector3f {
+        let c = &self.boundaries[index];
+        let (t, sign) = if reversed { (1.0 - t, -1.0) } else { (t, 1.0) };
+        match order {
+            0 => c.point_at(t).coords,
+            1 => c.velocity_at(t) * sign,
+            _ => c.acceleration_at(t),
+        }
+    }
+
+    // the partial derivative ∂^(k+l) S / ∂u^k ∂v^l of
+    // S = Σ F_a(v) c_a(u) + Σ F_a(u) d_a(v) - Σ F_a(u) F_b(v) P_ab
+    // with F_0 = 1 - f and F_1 = f
+    fn derivative(&self, u: f64, v: f64, k: usize, l: usize) -> Vector3f {
+        let blend = |t: f64, order: usize| {
+            let f = self.blending.jet(t);
+            let f1 = f[order];
+            let f0 = if order == 0 { 1.0 - f1 } else { -f1 };
+            [f0, f1]
+        };
+        let (fu, fv) = (blend(u, k), blend(v, l));
+        // c_0 is the bottom and c_1 the reversed top, d_0 the reversed left and d_1 the right
+        let c = [self.side(0, u, k, false), self.side(2, u, k, true)];
+        let d = [self.side(3, v, l, true), self.side(1, v, l, false)];
+        let corners = [
+            [self.side(0, 0.0, 0, false), self.side(3, 0.0, 0, false)],
+            [self.side(1, 0.0, 0, false), self.side(2, 0.0, 0, false)],
+        ];
+        let mut s = c[0] * fv[0] + c[1] * fv[1] + d[0] * fu[0] + d[1] * fu[1];
+        for (a, row) in corners.iter().enumerate() {
+            for (b, corner) in row.iter().enumerate() {
+                s -= corner * (fu[a] * fv[b]);
+            }
+        }
+        s
+    }
+}
+
+impl Surface for CoonsPatch {
+    fn u_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.derivative(u, v, 0, 0))
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 0)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 1)
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 2, 0)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 1)
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::nurbs_curve::NurbsCurve;
+    use crate::surfaces::uv;
+
+    // a rational quadratic from a to b bulging toward c
+    fn side(a: [f64; 3], c: [f64; 3], b: [f64; 3]) -> Box<dyn Curve> {
+        let point = |p: [f64; 3]| Point3f::new(p[0], p[1], p[2]);
+        Box::new(
+            NurbsCurve::new(
+                2,
+                vec![0.0, 0.0, 0.0, 1.0, 1.0, 1.0],
+                vec![point(a), point(c), point(b)],
+                vec![1.0, 0.7, 1.0],
+            )
+            .unwrap(),
+        )
+    }
+
+    fn patch(blending: CoonsBlending) -> CoonsPatch {
+        CoonsPatch::new(
+            side([0.0, 0.0, 0.0], [0.5, -0.3, 0.4], [1.0, 0.0, 0.0]),
+            side([1.0, 0.0, 0.0], [1.2, 0.5, -0.2], [1.0, 1.0, 0.0]),
+            side([1.0, 1.0, 0.0], [0.5, 1.3, 0.5], [0.0, 1.0, 0.0]),
+            side([0.0, 1.0, 0.0], [-0.2, 0.5, 0.3], [0.0, 0.0, 0.0]),
+            blending,
+        )
+        .unwrap()
+    }
+
+    // the patch runs along its sides on the edges of the unit square
+    #[test]
+    fn edges_reproduce_the_boundaries() {
+        for blending in [CoonsBlending::Bilinear, CoonsBlending::Bicubic] {
+            let patch = patch(blending);
+            let [bottom, right, top, left] = patch.boundaries();
+            for i in 0..=16 {
+                let t = i as f64 / 16.0;
+                // (point on the edge, side point, derivative along the edge, side velocity)
+                let edges = [
+                    (
+                        patch.point_at(t, 0.0),
+                        bottom.point_at(t),
+                        patch.du_at(t, 0.0),
+                        bottom.velocity_at(t),
+                    ),
+                    (
+                        patch.point_at(1.0, t),
+                        right.point_at(t),
+                        patch.dv_at(1.0, t),
+                        right.velocity_at(t),
+                    ),
+                    (
+                        patch.point_at(1.0 - t, 1.0),
+                        top.point_at(t),
+                        -patch.du_at(1.0 - t, 1.0),
+                        top.velocity_at(t),
+                    ),
+                    (
+                        patch.point_at(0.0, 1.0 - t),
+                        left.point_at(t),
+                        -patch.dv_at(0.0, 1.0 - t),
+                        left.velocity_at(t),
+                    ),
+                ];
+                for (p, q, dp, dq) in edges.iter() {
+                    assert!((p - q).norm() < 1e-12, "{:?} {:?} at {}", blending, p, t);
+                    assert!((dp - dq).norm() < 1e-12, "{:?} {:?} at {}", blending, dp, t);
+                }
+            }
+        }
+    }
+
+    // the derivatives of the blend agree with central differences
+    #[test]
+    fn derivatives_agree_with_differences() {
+        let h = 1e-5;
+        for blending in [CoonsBlending::Bilinear, CoonsBlending::Bicubic] {
+            let patch = patch(blending);
+            for (u, v) in uv::grid(5) {
+                let du = (patch.point_at(u + h, v) - patch.point_at(u - h, v)) / (2.0 * h);
+                let dv = (patch.point_at(u, v + h) - patch.point_at(u, v - h)) / (2.0 * h);
+                let dudu = (patch.du_at(u + h, v) - patch.du_at(u - h, v)) / (2.0 * h);
+                let dudv = (patch.du_at(u, v + h) - patch.du_at(u, v - h)) / (2.0 * h);
+                let dvdv = (patch.dv_at(u, v + h) - patch.dv_at(u, v - h)) / (2.0 * h);
+                for (d, exact) in [
+                    (du, patch.du_at(u, v)),
+                    (dv, patch.dv_at(u, v)),
+                    (dudu, patch.dudu_at(u, v)),
+                    (dudv, patch.dudv_at(u, v)),
+                    (dvdv, patch.dvdv_at(u, v)),
+                ] {
+                    assert!(
+                        (d - exact).norm() < 1e-6 * exact.norm().max(1.0),
+                        "{:?} difference {:?} derivative {:?} at ({}, {})",
+                        blending,
+                        d,
+                        exact,
+                        u,
+                        v
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use crate::curve_tessellation::CurveTessellation;
+use crate::surfaces::geodesic;
+use crate::surfaces::surface::Surface;
+use crate::surfaces::uv::{clip, wrap};
+use crate::types::*;
+
+// relative tolerances of k1 - k2 (umbilic) and of -K (parabolic) to the curvature scale
+const UMBILIC_TOLERANCE: f64 = 1e-3;
+const PARABOLIC_TOLERANCE: f64 = 1e-3;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineFamily {
+    // lines of curvature along the first (k1) and second (k2) principal directions
+    Principal1,
+    Principal2,
+    // the two asymptotic directions, defined where K < 0
+    Asymptotic1,
+    Asymptotic2,
+}
+
+impl LineFamily {
+    pub fn is_principal(&self) -> bool {
+        matches!(self, LineFamily::Principal1 | LineFamily::Principal2)
+    }
+
+    // the other family of the net
+    pub fn transversal(&self) -> LineFamily {
+        match self {
+            LineFamily::Principal1 => LineFamily::Principal2,
+            LineFamily::Principal2 => LineFamily::Principal1,
+            LineFamily::Asymptotic1 => LineFamily::Asymptotic2,
+            LineFamily::Asymptotic2 => LineFamily::Asymptotic1,
+        }
+    }
+}
+
+// why the tracing of a line stopped
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LineEnd {
+    Boundary,
+    // principal directions are undefined at k1 = k2
+    Umbilic,
+    // asymptotic directions merge at K = 0 and vanish where K > 0
+    Parabolic,
+    // came back to the seed
+    Closed,
+    Length,
+}
+
+pub struct CurvatureLine {
+    pub family: LineFamily,
+    pub uv: Vec<Point2f>,
+    pub length: f64,
+    // (backward, forward) ends
+    pub ends: (LineEnd, LineEnd),
+}
+
+impl CurvatureLine {
+    pub fn tessellate<S: Surface + ?Sized>(&self, surface: &S) -> CurveTessellation {
+        geodesic::tessellate(surface, &self.uv)
+    }
+}
+
+// unit (in the metric) directions of the family at (u, v),
+// asymptotic families also return the other asymptotic direction to follow the field across sign flips
+fn candidates<S: Surface + ?Sized>(
+    surface: &S,
+    family: LineFamily,
+    p: &Point2f,
+) -> Result<Vec<Vector2f>, LineEnd> {
+    let c = surface.curvature_at(p.x, p.y);
+    let (d1, d2) = c.principal_directions_uv;
+    let scale = c.k1.abs().max(c.k2.abs());
+    if family.is_principal() {
+        if c.k1 - c.k2 <= UMBILIC_TOLERANCE * scale + 1e-12 {
+            return Err(LineEnd::Umbilic);
+        }
+        Ok(vec![if family == LineFamily::Principal1 {
+            d1
+        } else {
+            d2
+        }])
+    } else {
+        if -c.gaussian <= PARABOLIC_TOLERANCE * scale * scale + 1e-12 {
+            return Err(LineEnd::Parabolic);
+        }
+        // k1 cos^2θ + k2 sin^2θ = 0
+        let theta = (-c.k1 / c.k2).sqrt().atan();
+        let a1 = d1 * theta.cos() + d2 * theta.sin();
+        let a2 = d1 * theta.cos() - d2 * theta.sin();
+        Ok(if family == LineFamily::Asymptotic1 {
+            vec![a1, a2]
+        } else {
+            vec![a2, a1]
+        })
+    }
+}
+
+// the line field is unoriented, pick the candidate closest to the previous direction
+fn direction_at<S: Surface + ?Sized>(
+    surface: &S,
+    family: LineFamily,
+    p: &Point2f,
+    previous: Option<&Vector2f>,
+) -> Result<Vector2f, LineEnd> {
+    let candidates = candidates(surface, family, p)?;
+    let previous = match previous {
+        Some(previous) => previous,
+        None => return Ok(candidates[0]),
+    };
+    let first = surface.first_fundamental_form_at(p.x, p.y);
+    let d = candidates
+        .iter()
+        .max_by(|a, b| {
+            let a = first.inner(previous, a).abs();
+            let b = first.inner(previous, b).abs();
+            a.partial_cmp(&b).unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .unwrap();
+    Ok(if first.inner(previous, d) < 0.0 {
+        -d
+    } else {
+        *d
+    })
+}
+
+// RK4 on the unit speed field in one direction from the seed,
+// step and length are arc lengths on the surface
+fn trace_direction<S: Surface + ?Sized>(
+    surface: &S,
+    family: LineFamily,
+    seed: &Point2f,
+    direction: &Vector2f,
+    step: f64,
+    length: f64,
+) -> (Vec<Point2f>, f64, LineEnd) {
+    let start = surface.point_at(seed.x, seed.y);
+    let mut uv = vec![*seed];
+    let mut p = *seed;
+    let mut d = *direction;
+    let mut s = 0.0;
+
+    while s < length {
+        let h = step.min(length - s);
+        let next = (|| {
+            let k1 = direction_at(surface, family, &p, Some(&d))?;
+            let k2 = direction_at(surface, family, &(p + k1 * h * 0.5), Some(&k1))?;
+            let k3 = direction_at(surface, family, &(p + k2 * h * 0.5), Some(&k2))?;
+            let k4 = direction_at(surface, family, &(p + k3 * h), Some(&k3))?;
+            Ok((p + (k1 + k2 * 2.0 + k3 * 2.0 + k4) * h / 6.0, k4))
+        })();
+        let (next, k4) = match next {
+            Ok(next) => next,
+            Err(end) => return (uv, s, end),
+        };
+
+        match wrap(surface, &next) {
+            Some(q) => {
+                p = q;
+                d = k4;
+                s += h;
+                uv.push(p);
+            }
+            None => {
+                let q = clip(surface, &p, &next);
+                s += (surface.point_at(q.x, q.y) - surface.point_at(p.x, p.y)).norm();
+                uv.push(q);
+                return (uv, s, LineEnd::Boundary);
+            }
+        }
+
+        if s > step * 4.0 && (surface.point_at(p.x, p.y) - start).norm() < step * 0.75 {
+            uv.push(*seed);
+            return (uv, s, LineEnd::Closed);
+        }
+    }
+    (uv, s, LineEnd::Length)
+}
+
+// trace the line of the family through the seed in both directions,
+// each direction up to the given arc length
+pub fn trace<S: Surface + ?Sized>(
+    surface: &S,
+    family: LineFamily,
+    seed: &Point2f,
+    step: f64,
+    length: f64,
+) -> Option<CurvatureLine> {
+    let d = direction_at(surface, family, seed, None).ok()?;
+    let (forward, lf, ef) = trace_direction(surface, family, seed, &d, step, length);
+    if ef == LineEnd::Closed {
+        return Some(CurvatureLine {
+            family,
+            uv: forward,
+            length: lf,
+            ends: (ef, ef),
+        });
+    }
+    let (backward, lb, eb) = trace_direction(surface, family, seed, &-d, step, length);
+    let mut uv: Vec<Point2f> = backward.into_iter().rev().collect();
+    uv.extend(forward.into_iter().skip(1));
+    Some(CurvatureLine {
+        family,
+        uv,
+        length: lf + lb,
+        ends: (eb, ef),
+    })
+}
+
+// points along the polyline at multiples of the spacing in arc length, the first point included
+fn resample<S: Surface + ?Sized>(
+    surface: &S,
+    uv: &[Point2f],
+    spacing: f64,
+    count: usize,
+) -> Vec<Point2f> {
+    let mut samples = vec![];
+    let mut s = 0.0;
+    let mut previous: Option<Point3f> = None;
+    for p in uv {
+        let x = surface.point_at(p.x, p.y);
+        if let Some(previous) = previous {
+            s += (x - previous).norm();
+        }
+        previous = Some(x);
+        if samples.len() < count && s >= spacing * samples.len() as f64 {
+            samples.push(*p);
+        }
+    }
+    samples
+}
+
+// lines of the family through points spaced evenly in arc length along the transversal line
+// through the seed, count lines on each side of the seed line
+pub fn net<S: Surface + ?Sized>(
+    surface: &S,
+    family: LineFamily,
+    seed: &Point2f,
+    spacing: f64,
+    count: usize,
+    length: f64,
+) -> Vec<CurvatureLine> {
+    let step = spacing / 16.0;
+    let transversal = family.transversal();
+    let mut seeds = vec![*seed];
+    if let Ok(d) = direction_at(surface, transversal, seed, None) {
+        for d in [d, -d].iter() {
+            let extent = spacing * count as f64;
+            let (uv, _, _) = trace_direction(surface, transversal, seed, d, step, extent);
+            seeds.extend(
+                resample(surface, &uv, spacing, count + 1)
+                    .into_iter()
+                    .skip(1),
+            );
+        }
+    }
+    seeds
+        .iter()
+        .filter_map(|p| trace(surface, family, p, step, length))
+        .collect()
+}
@@ -1,3 +1,4 @@
+use crate::domain::Domain;
 use crate::plane::Plane;
 use crate::surfaces::surface::Surface;
 use crate::types::Point3f;
@@ -35,6 +36,12 @@ impl Cylinder {
         }
     }
 
+    // dtheta/du
+    fn scale(&self) -> f64 {
+        let delta = 1e-8;
+        TAU * (1.0 - delta)
+    }
+
     fn sin_cos(&self, u: f64) -> (f64, f64) {
         let delta = 1e-8;
         let theta = u * TAU * (1.0 - delta); // 0.0 <= v < 2pi
@@ -45,6 +52,14 @@ impl Cylinder {
 }
 
 impl Surface for Cylinder {
+    fn u_domain(&self) -> Domain {
+        Domain::periodic(0.0, TAU)
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::new(0.0, self.height)
+    }
+
     fn area(&self) -> f64 {
         2.0 * PI * self.radius * self.height
     }
@@ -61,7 +76,7 @@ impl Surface for Cylinder {
         let (st, ct) = self.sin_cos(u);
         let dx = self.radius * ct * self.plane.x_axis; // cos(theta)
         let dy = -self.radius * st * self.plane.y_axis; // - sin(theta)
-        dx + dy
+        (dx + dy) * self.scale()
     }
 
     fn dv_at(&self, _u: f64, _v: f64) -> Vector3f {
@@ -72,7 +87,7 @@ impl Surface for Cylinder {
         let (st, ct) = self.sin_cos(u);
         let dx = -self.radius * st * self.plane.x_axis; // - sin(theta)
         let dy = -self.radius * ct * self.plane.y_axis; // - cos(theta)
-        dx + dy
+        (dx + dy) * self.scale() * self.scale()
     }
 
     fn dvdv_at(&self, _u: f64, _v: f64) -> Vector3f {
@@ -0,0 +1,168 @@
+use crate::domain::Domain;
+use crate::expression::{Expression, Jet};
+use crate::surfaces::surface::Surface;
+use crate::types::Point2f;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+use core::f64::consts::TAU;
+use std::collections::BTreeMap;
+
+// x(u, v), y(u, v), z(u, v) formulas over the native domains,
+// derivatives are exact by differentiating the formulas
+#[derive(Clone)]
+pub struct ExpressionSurface {
+    x_expr: Expression,
+    y_expr: Expression,
+    z_expr: Expression,
+    u_domain: Domain,
+    v_domain: Domain,
+    parameters: BTreeMap<String, f64>,
+}
+
+impl ExpressionSurface {
+    // check that every formula parses and only refers to u, v and the parameters,
+    // a domain is made periodic when the surface closes up across it
+    pub fn try_expression(
+        x: &str,
+        y: &str,
+        z: &str,
+        u: Domain,
+        v: Domain,
+        parameters: BTreeMap<String, f64>,
+    ) -> Result<Self, fasteval::Error> {
+        let mut surface = Self {
+            x_expr: Expression::parse(x)?,
+            y_expr: Expression::parse(y)?,
+            z_expr: Expression::parse(z)?,
+            u_domain: u,
+            v_domain: v,
+            parameters,
+        };
+        let variables = surface.variables(surface.u_domain.start(), surface.v_domain.start());
+        surface.x_expr.jet(&variables)?;
+        surface.y_expr.jet(&variables)?;
+        surface.z_expr.jet(&variables)?;
+
+        let (u, v) = (surface.u_domain.clone(), surface.v_domain.clone());
+        if surface.closes(|s| (Point2f::new(0.0, s), Point2f::new(1.0, s))) {
+            surface.u_domain = Domain::periodic(u.start(), u.end());
+        }
+        if surface.closes(|s| (Point2f::new(s, 0.0), Point2f::new(s, 1.0))) {
+            surface.v_domain = Domain::periodic(v.start(), v.end());
+        }
+        Ok(surface)
+    }
+
+    pub fn parameters(&self) -> &BTreeMap<String, f64> {
+        &self.parameters
+    }
+
+    pub fn set_parameter(&mut self, name: &str, value: f64) {
+        self.parameters.insert(name.to_string(), value);
+    }
+
+    // u and v shadow parameters of the same names
+    fn variables(&self, u: f64, v: f64) -> BTreeMap<String, Jet> {
+        let mut variables: BTreeMap<String, Jet> = self
+            .parameters
+            .iter()
+            .map(|(name, value)| (name.clone(), Jet::constant(*value)))
+            .collect();
+        variables.insert(String::from("u"), Jet::u(u));
+        variables.insert(String::from("v"), Jet::v(v));
+        variables
+    }
+
+    // jets of x, y, z at normalized (u, v), scaled to derivatives in normalized parameters
+    fn jets(&self, u: f64, v: f64) -> [Jet; 3] {
+        let (lu, lv) = (self.u_domain.length(), self.v_domain.length());
+        let variables = self.variables(self.u_domain.map(u), self.v_domain.map(v));
+        let jet = |e: &Expression| {
+            let j = e
+                .jet(&variables)
+                .unwrap_or_else(|_| Jet::constant(f64::NAN));
+            Jet {
+                value: j.value,
+                du: j.du * lu,
+                dv: j.dv * lv,
+                duu: j.duu * lu * lu,
+                duv: j.duv * lu * lv,
+                dvv: j.dvv * lv * lv,
+            }
+        };
+        [jet(&self.x_expr), jet(&self.y_expr), jet(&self.z_expr)]
+    }
+
+    fn vector(&self, u: f64, v: f64, component: fn(&Jet) -> f64) -> Vector3f {
+        let [x, y, z] = self.jets(u, v);
+        Vector3f::new(component(&x), component(&y), component(&z))
+    }
+
+    // the opposite sides, sampled as pairs of normalized points, coincide
+    fn closes(&self, sides: fn(f64) -> (Point2f, Point2f)) -> bool {
+        let count = 16;
+        let samples: Vec<(Point3f, Point3f)> = (0..=count)
+            .map(|i| {
+                let (a, b) = sides(i as f64 / count as f64);
+                (self.point_at(a.x, a.y), self.point_at(b.x, b.y))
+            })
+            .collect();
+        let size = samples
+            .iter()
+            .map(|(a, _)| (a - samples[0].0).norm())
+            .fold(1.0, f64::max);
+        samples.iter().all(|(a, b)| (a - b).norm() <= 1e-9 * size)
+    }
+}
+
+impl Default for ExpressionSurface {
+    fn default() -> Self {
+        let mut parameters: BTreeMap<String, f64> = BTreeMap::new();
+        parameters.insert(String::from("a"), 1.0);
+        parameters.insert(String::from("b"), 3.0);
+        ExpressionSurface::try_expression(
+            "(a * cos(u) + b) * cos(v)",
+            "(a * cos(u) + b) * sin(v)",
+            "a * sin(u)",
+            Domain::new(0.0, TAU),
+            Domain::new(0.0, TAU),
+            parameters,
+        )
+        .unwrap()
+    }
+}
+
+impl Surface for ExpressionSurface {
+    fn u_domain(&self) -> Domain {
+        self.u_domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.v_domain.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        let [x, y, z] = self.jets(u, v);
+        Point3f::new(x.value, y.value, z.value)
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.du)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.dv)
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.duu)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.duv)
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.vector(u, v, |j| j.dvv)
+    }
+}
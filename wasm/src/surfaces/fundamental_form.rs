@@ -0,0 +1,88 @@
+use crate::types::{Matrix2x2f, Vector2f};
+
+// I = E du^2 + 2F dudv + G dv^2
+// https://mathworld.wolfram.com/FirstFundamentalForm.html
+#[derive(Clone, Copy, Debug)]
+pub struct FirstFundamentalForm {
+    pub e: f64,
+    pub f: f64,
+    pub g: f64,
+}
+
+impl FirstFundamentalForm {
+    pub fn new(e: f64, f: f64, g: f64) -> Self {
+        Self { e, f, g }
+    }
+
+    pub fn matrix(&self) -> Matrix2x2f {
+        Matrix2x2f::new(self.e, self.f, self.f, self.g)
+    }
+
+    pub fn inverse(&self) -> Option<Matrix2x2f> {
+        self.matrix().try_inverse()
+    }
+
+    // EG - FF
+    pub fn determinant(&self) -> f64 {
+        self.e * self.g - self.f * self.f
+    }
+
+    // √(EG - FF)
+    pub fn area_element(&self) -> f64 {
+        self.determinant().sqrt()
+    }
+
+    // I(a, b) for uv tangent vectors a, b
+    pub fn inner(&self, a: &Vector2f, b: &Vector2f) -> f64 {
+        self.e * a.x * b.x + self.f * (a.x * b.y + a.y * b.x) + self.g * a.y * b.y
+    }
+
+    pub fn length(&self, a: &Vector2f) -> f64 {
+        self.inner(a, a).sqrt()
+    }
+
+    // angle between uv tangent vectors measured on the surface
+    pub fn angle(&self, a: &Vector2f, b: &Vector2f) -> f64 {
+        let c = self.inner(a, b) / (self.length(a) * self.length(b));
+        c.clamp(-1.0, 1.0).acos()
+    }
+}
+
+// II = L du^2 + 2M dudv + N dv^2
+// https://mathworld.wolfram.com/SecondFundamentalForm.html
+#[derive(Clone, Copy, Debug)]
+pub struct SecondFundamentalForm {
+    pub l: f64,
+    pub m: f64,
+    pub n: f64,
+}
+
+impl SecondFundamentalForm {
+    pub fn new(l: f64, m: f64, n: f64) -> Self {
+        Self { l, m, n }
+    }
+
+    pub fn matrix(&self) -> Matrix2x2f {
+        Matrix2x2f::new(self.l, self.m, self.m, self.n)
+    }
+
+    // LN - MM
+    pub fn determinant(&self) -> f64 {
+        self.l * self.n - self.m * self.m
+    }
+
+    // II(a, b) for uv tangent vectors a, b
+    pub fn apply(&self, a: &Vector2f, b: &Vector2f) -> f64 {
+        self.l * a.x * b.x + self.m * (a.x * b.y + a.y * b.x) + self.n * a.y * b.y
+    }
+
+    // k_n = II(d, d) / I(d, d)
+    pub fn normal_curvature(&self, first: &FirstFundamentalForm, d: &Vector2f) -> f64 {
+        self.apply(d, d) / first.inner(d, d)
+    }
+
+    // Weingarten map S = I^-1 II
+    pub fn shape_operator(&self, first: &FirstFundamentalForm) -> Matrix2x2f {
+        first.inverse().unwrap_or_else(Matrix2x2f::zeros) * self.matrix()
+    }
+}
@@ -0,0 +1,248 @@
+use crate::domain::Domain;
+use crate::quadrature::{gauss_legendre, Quadrature};
+use crate::surfaces::surface::Surface;
+use crate::types::*;
+use core::f64::consts::TAU;
+use wasm_bindgen::prelude::*;
+
+// ∬K dA + ∮k_g ds + Σθ = 2πχ
+// https://en.wikipedia.org/wiki/Gauss%E2%80%93Bonnet_theorem
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug)]
+pub struct GaussBonnet {
+    // ∬K dA
+    pub total_curvature: f64,
+    // ∮k_g ds along the boundary
+    pub boundary_curvature: f64,
+    // Σθ, the turning angles at the corners of the boundary
+    pub exterior_angles: f64,
+    // χ = V - E + F of the patch with its periodic sides glued and degenerate sides collapsed
+    pub euler_characteristic: i32,
+}
+
+#[wasm_bindgen]
+impl GaussBonnet {
+    pub fn total(&self) -> f64 {
+        self.total_curvature + self.boundary_curvature + self.exterior_angles
+    }
+
+    // χ read off the integrals
+    pub fn inferred_euler_characteristic(&self) -> i32 {
+        (self.total() / TAU).round() as i32
+    }
+
+    // total - 2πχ
+    pub fn error(&self) -> f64 {
+        self.total() - TAU * self.euler_characteristic as f64
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Side {
+    Boundary,
+    // glued to the opposite side of a periodic direction
+    Glued,
+    // shrinks to a point, e.g. at the poles of a sphere
+    Degenerate,
+}
+
+// the straight uv segment from a to b
+struct Segment {
+    a: Point2f,
+    b: Point2f,
+}
+
+impl Segment {
+    fn at(&self, t: f64) -> Point2f {
+        self.a + (self.b - self.a) * t
+    }
+
+    // r' = Xu u' + Xv v'
+    fn velocity<S: Surface + ?Sized>(&self, surface: &S, t: f64) -> Vector3f {
+        let p = self.at(t);
+        let d = self.b - self.a;
+        surface.du_at(p.x, p.y) * d.x + surface.dv_at(p.x, p.y) * d.y
+    }
+
+    // (∫|r'| dt, ∫k_g |r'| dt) with k_g = (r' x r'')・N / |r'|^3
+    fn integrals<S: Surface + ?Sized>(&self, surface: &S) -> (f64, f64) {
+        let (nodes, weights) = gauss_legendre(8);
+        let segments = 32;
+        let d = self.b - self.a;
+        let mut length = 0.0;
+        let mut curvature = 0.0;
+        for i in 0..segments {
+            for (x, w) in nodes.iter().zip(weights.iter()) {
+                let t = (i as f64 + x) / segments as f64;
+                let p = self.at(t);
+                let (u, v) = (p.x, p.y);
+                let r1 = self.velocity(surface, t);
+                let r2 = surface.dudu_at(u, v) * d.x * d.x
+                    + surface.dudv_at(u, v) * 2.0 * d.x * d.y
+                    + surface.dvdv_at(u, v) * d.y * d.y;
+                let speed = r1.norm();
+                let w = w / segments as f64;
+                length += speed * w;
+                if speed > 0.0 {
+                    curvature += r1.cross(&r2).dot(&surface.normal_at(u, v)) / (speed * speed) * w;
+                }
+            }
+        }
+        (length, curvature)
+    }
+}
+
+fn find(parents: &mut [usize], i: usize) -> usize {
+    if parents[i] != i {
+        parents[i] = find(parents, parents[i]);
+    }
+    parents[i]
+}
+
+fn union(parents: &mut [usize], a: usize, b: usize) {
+    let a = find(parents, a);
+    let b = find(parents, b);
+    parents[a] = b;
+}
+
+// Gauss-Bonnet over the patch [u.start, u.end] x [v.start, v.end] of normalized (u, v),
+// the boundary runs counterclockwise in uv so that the patch lies to the left of it
+pub fn gauss_bonnet<S: Surface + ?Sized>(surface: &S, u: &Domain, v: &Domain) -> GaussBonnet {
+    let (u0, u1, v0, v1) = (u.min(), u.max(), v.min(), v.max());
+    let total_curvature = surface.integrate_over(
+        &|s, t| surface.curvature_at(s, t).gaussian,
+        &Domain::new(u0, u1),
+        &Domain::new(v0, v1),
+        &Quadrature::default(),
+    );
+
+    // corners 0: (u0, v0), 1: (u1, v0), 2: (u1, v1), 3: (u0, v1)
+    let corners = [
+        Point2f::new(u0, v0),
+        Point2f::new(u1, v0),
+        Point2f::new(u1, v1),
+        Point2f::new(u0, v1),
+    ];
+    // side i runs from corner i to corner i + 1
+    let segments: Vec<Segment> = (0..4)
+        .map(|i| Segment {
+            a: corners[i],
+            b: corners[(i + 1) % 4],
+        })
+        .collect();
+    let integrals: Vec<(f64, f64)> = segments.iter().map(|s| s.integrals(surface)).collect();
+
+    let full = |d: &Domain, periodic: bool| periodic && d.min() <= 0.0 && d.max() >= 1.0;
+    let glue_u = full(u, surface.u_domain().is_periodic());
+    let glue_v = full(v, surface.v_domain().is_periodic());
+    let size = surface
+        .integrate_over(
+            &|_, _| 1.0,
+            &Domain::new(u0, u1),
+            &Domain::new(v0, v1),
+            &Quadrature::default(),
+        )
+        .sqrt();
+    let sides: Vec<Side> = (0..4)
+        .map(|i| {
+            // sides 0 and 2 lie along u, sides 1 and 3 along v
+            let glued = if i % 2 == 0 { glue_v } else { glue_u };
+            if glued {
+                Side::Glued
+            } else if integrals[i].0 <= 1e-6 * size {
+                Side::Degenerate
+            } else {
+                Side::Boundary
+            }
+        })
+        .collect();
+
+    let boundary_curvature = (0..4)
+        .filter(|i| sides[*i] == Side::Boundary)
+        .map(|i| integrals[i].1)
+        .sum();
+
+    // turning from the end of a boundary side to the start of the next one,
+    // through degenerate sides but not across a glued seam
+    let mut exterior_angles = 0.0;
+    for i in (0..4).filter(|i| sides[*i] == Side::Boundary) {
+        let next = (1..4)
+            .map(|k| (i + k) % 4)
+            .find(|j| sides[*j] != Side::Degenerate);
+        let j = match next {
+            Some(j) if sides[j] == Side::Boundary => j,
+            _ => continue,
+        };
+        let t0 = segments[i].velocity(surface, 1.0).normalize();
+        let t1 = segments[j].velocity(surface, 0.0).normalize();
+        let p = segments[j].a;
+        let n = surface.normal_at(p.x, p.y);
+        exterior_angles += t0.cross(&t1).dot(&n).atan2(t0.dot(&t1));
+    }
+
+    // χ = V - E + F of the square with its identifications
+    let mut parents: Vec<usize> = (0..4).collect();
+    if glue_u {
+        // side 1 (u1) onto side 3 (u0) reversed
+        union(&mut parents, 1, 0);
+        union(&mut parents, 2, 3);
+    }
+    if glue_v {
+        // side 0 (v0) onto side 2 (v1) reversed
+        union(&mut parents, 0, 3);
+        union(&mut parents, 1, 2);
+    }
+    for i in (0..4).filter(|i| sides[*i] == Side::Degenerate) {
+        union(&mut parents, i, (i + 1) % 4);
+    }
+    let vertices = (0..4).filter(|i| find(&mut parents, *i) == *i).count() as i32;
+    let edges = sides.iter().filter(|s| **s == Side::Boundary).count() as i32
+        + glue_u as i32
+        + glue_v as i32;
+
+    GaussBonnet {
+        total_curvature,
+        boundary_curvature,
+        exterior_angles,
+        euler_characteristic: vertices - edges + 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{
+        hyperbolic_paraboloid::HyperbolicParaboloid, sphere::Sphere, torus::Torus,
+    };
+
+    fn assert_gauss_bonnet<S: Surface>(surface: &S, u: Domain, v: Domain, euler: i32) {
+        let gb = gauss_bonnet(surface, &u, &v);
+        assert_eq!(gb.euler_characteristic, euler);
+        assert!(gb.error().abs() < 1e-6, "{:?}", gb);
+    }
+
+    #[test]
+    fn closed_surfaces() {
+        assert_gauss_bonnet(&Sphere::default(), Domain::unit(), Domain::unit(), 2);
+        assert_gauss_bonnet(&Torus::default(), Domain::unit(), Domain::unit(), 0);
+    }
+
+    #[test]
+    fn disks() {
+        assert_gauss_bonnet(
+            &HyperbolicParaboloid::default(),
+            Domain::unit(),
+            Domain::unit(),
+            1,
+        );
+        // with geodesic curvature along the boundary and corners
+        let (u, v) = (Domain::new(0.2, 0.6), Domain::new(0.1, 0.5));
+        assert_gauss_bonnet(&Sphere::default(), u, v, 1);
+    }
+
+    #[test]
+    fn annulus() {
+        // a band around the sphere, periodic in v
+        assert_gauss_bonnet(&Sphere::default(), Domain::new(0.3, 0.7), Domain::unit(), 0);
+    }
+}
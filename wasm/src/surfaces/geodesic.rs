@@ -0,0 +1,509 @@
+use crate::curve_tessellation::CurveTessellation;
+use crate::frenet_frame::FrenetFrame;
+use crate::quadrature::gauss_legendre;
+use crate::surfaces::surface::Surface;
+use crate::surfaces::uv;
+use crate::types::*;
+use core::f64::consts::{PI, TAU};
+
+// state of the geodesic ODE: (u, v, u', v') with respect to arc length
+type State = [f64; 4];
+
+pub struct Geodesic {
+    pub tessellation: CurveTessellation,
+    pub uv: Vec<Point2f>,
+    // integrated arc length, shorter than requested if the path ran into the boundary
+    pub length: f64,
+}
+
+// Γ^u_ij and Γ^v_ij as symmetric matrices
+// Γ^k_ij = g^kl (X_ij・X_l)
+pub fn christoffel_symbols<S: Surface + ?Sized>(
+    surface: &S,
+    u: f64,
+    v: f64,
+) -> (Matrix2x2f, Matrix2x2f) {
+    let xu = surface.du_at(u, v);
+    let xv = surface.dv_at(u, v);
+    let xuu = surface.dudu_at(u, v);
+    let xuv = surface.dudv_at(u, v);
+    let xvv = surface.dvdv_at(u, v);
+    let ig = surface
+        .first_fundamental_form_at(u, v)
+        .inverse()
+        .unwrap_or_else(Matrix2x2f::zeros);
+
+    // lower index Γ_ijl = X_ij・X_l
+    let lower = |l: &Vector3f| Matrix2x2f::new(xuu.dot(l), xuv.dot(l), xuv.dot(l), xvv.dot(l));
+    let lu = lower(&xu);
+    let lv = lower(&xv);
+    let gu = lu * ig[(0, 0)] + lv * ig[(0, 1)];
+    let gv = lu * ig[(1, 0)] + lv * ig[(1, 1)];
+    (gu, gv)
+}
+
+// u'' = - Γ^u_ij u^i' u^j', v'' = - Γ^v_ij u^i' u^j'
+fn derivative<S: Surface + ?Sized>(surface: &S, y: &State) -> State {
+    let (gu, gv) = surface.christoffel_symbols_at(y[0], y[1]).matrices();
+    let d = Matrix2x1f::new(y[2], y[3]);
+    let ddu = -(d.transpose() * gu * d)[(0, 0)];
+    let ddv = -(d.transpose() * gv * d)[(0, 0)];
+    [y[2], y[3], ddu, ddv]
+}
+
+fn add(y: &State, k: &State, h: f64) -> State {
+    [
+        y[0] + k[0] * h,
+        y[1] + k[1] * h,
+        y[2] + k[2] * h,
+        y[3] + k[3] * h,
+    ]
+}
+
+fn rk4<S: Surface + ?Sized>(surface: &S, y: &State, h: f64) -> State {
+    let k1 = derivative(surface, y);
+    let k2 = derivative(surface, &add(y, &k1, h * 0.5));
+    let k3 = derivative(surface, &add(y, &k2, h * 0.5));
+    let k4 = derivative(surface, &add(y, &k3, h));
+    let mut next = *y;
+    for i in 0..4 {
+        next[i] += (k1[i] + 2.0 * k2[i] + 2.0 * k3[i] + k4[i]) * h / 6.0;
+    }
+    next
+}
+
+// wrap the periodic coordinates of the point, None if it left the domain
+fn wrap<S: Surface + ?Sized>(surface: &S, y: &State) -> Option<State> {
+    uv::wrap(surface, &Point2f::new(y[0], y[1])).map(|p| [p.x, p.y, y[2], y[3]])
+}
+
+// uv velocity of the unit speed curve heading toward the direction projected on the tangent plane
+pub fn uv_direction<S: Surface + ?Sized>(
+    surface: &S,
+    u: f64,
+    v: f64,
+    direction: &Vector3f,
+) -> Option<(f64, f64)> {
+    let xu = surface.du_at(u, v);
+    let xv = surface.dv_at(u, v);
+    let first = surface.first_fundamental_form_at(u, v);
+    let d = first.inverse()? * Vector2f::new(xu.dot(direction), xv.dot(direction));
+    let speed = first.length(&d);
+    if speed <= f64::EPSILON {
+        None
+    } else {
+        Some((d.x / speed, d.y / speed))
+    }
+}
+
+// integrate the geodesic equation with adaptive RK4 (step doubling)
+pub fn shoot<S: Surface + ?Sized>(
+    surface: &S,
+    u: f64,
+    v: f64,
+    du: f64,
+    dv: f64,
+    length: f64,
+) -> (Vec<Point2f>, f64) {
+    let (uv, s, _) = shoot_within(surface, (u, v, du, dv), length, &mut 100000);
+    (uv, s)
+}
+
+// shoot taking at most the given number of steps, the steps taken are deducted from it,
+// the flag is false if they ran out before the length or the boundary was reached
+fn shoot_within<S: Surface + ?Sized>(
+    surface: &S,
+    (u, v, du, dv): (f64, f64, f64, f64),
+    length: f64,
+    steps: &mut usize,
+) -> (Vec<Point2f>, f64, bool) {
+    let tolerance = 1e-9;
+    let max_step = length / 64.0;
+
+    let mut y: State = [u, v, du, dv];
+    let mut uv = vec![Point2f::new(u, v)];
+    let mut s = 0.0;
+    let mut h = max_step * 0.25;
+    let mut boundary = false;
+
+    while s < length && *steps > 0 {
+        *steps -= 1;
+        h = h.min(length - s).min(max_step);
+
+        let full = rk4(surface, &y, h);
+        let half = rk4(surface, &rk4(surface, &y, h * 0.5), h * 0.5);
+        let error = (0..4)
+            .map(|i| (full[i] - half[i]).abs())
+            .fold(0.0, f64::max)
+            / 15.0;
+
+        if error > tolerance && h > 1e-9 {
+            h *= (0.9 * (tolerance / error).powf(0.2)).max(0.2);
+            continue;
+        }
+
+        match wrap(surface, &half) {
+            Some(next) => {
+                y = next;
+                s += h;
+                uv.push(Point2f::new(y[0], y[1]));
+            }
+            None => {
+                // step onto the boundary and stop
+                let t = uv::boundary_fraction(
+                    surface,
+                    &Point2f::new(y[0], y[1]),
+                    &Point2f::new(half[0], half[1]),
+                );
+                let last = rk4(surface, &y, h * t);
+                let u = last[0].clamp(0.0, 1.0);
+                let v = last[1].clamp(0.0, 1.0);
+                if let Some(last) = wrap(surface, &[u, v, last[2], last[3]]) {
+                    uv.push(Point2f::new(last[0], last[1]));
+                }
+                s += h * t;
+                boundary = true;
+                break;
+            }
+        }
+
+        if error > 0.0 {
+            h *= (0.9 * (tolerance / error).powf(0.2)).min(2.0);
+        } else {
+            h *= 2.0;
+        }
+    }
+
+    let complete = boundary || s >= length;
+    (uv, s, complete)
+}
+
+// frames along the path: the principal normal of a geodesic is the surface normal
+pub fn tessellate<S: Surface + ?Sized>(surface: &S, uv: &[Point2f]) -> CurveTessellation {
+    let count = uv.len();
+    let points: Vec<Point3f> = uv.iter().map(|p| surface.point_at(p.x, p.y)).collect();
+    let mut frames = vec![];
+    let mut curvatures = vec![];
+    for i in 0..count {
+        let p = uv[i];
+        let position = points[i];
+        let normal = surface.normal_at(p.x, p.y);
+        let next = points[(i + 1).min(count - 1)];
+        let prev = points[i.saturating_sub(1)];
+        let chord = next - prev;
+        let tangent = (chord - normal * chord.dot(&normal)).normalize();
+        let binormal = tangent.cross(&normal);
+        frames.push(FrenetFrame::new(&position, &tangent, &normal, &binormal));
+
+        // |k| = |k_n| for a geodesic
+        let xu = surface.du_at(p.x, p.y);
+        let xv = surface.dv_at(p.x, p.y);
+        let first = surface.first_fundamental_form_at(p.x, p.y);
+        let second = surface.second_fundamental_form_at(p.x, p.y);
+        let d = first
+            .inverse()
+            .map(|ig| ig * Vector2f::new(xu.dot(&tangent), xv.dot(&tangent)))
+            .unwrap_or_else(Vector2f::zeros);
+        let kn = second.normal_curvature(&first, &d);
+        curvatures.push(kn.abs() as f32);
+    }
+    CurveTessellation::new(frames, curvatures)
+}
+
+// unit tangent in the tangent plane at the angle from the u direction
+fn direction_at<S: Surface + ?Sized>(surface: &S, u: f64, v: f64, angle: f64) -> Vector3f {
+    let x = surface.du_at(u, v).normalize();
+    let y = surface.normal_at(u, v).cross(&x);
+    x * angle.cos() + y * angle.sin()
+}
+
+// difference between uv points, periodic coordinates take the nearest copy
+fn uv_difference<S: Surface + ?Sized>(surface: &S, a: &Point2f, b: &Point2f) -> Vector2f {
+    let mut d = a - b;
+    if surface.u_domain().is_periodic() {
+        d.x = (d.x + 0.5).rem_euclid(1.0) - 0.5;
+    }
+    if surface.v_domain().is_periodic() {
+        d.y = (d.y + 0.5).rem_euclid(1.0) - 0.5;
+    }
+    d
+}
+
+// integration steps one boundary value search may spend on all of its shots, and one shot of it
+const STEPS: usize = 16384;
+const SHOT_STEPS: usize = 2048;
+
+// end point of the geodesic and the arc length it ran, shorter than asked if it stopped at the boundary,
+// None once the steps are spent
+fn endpoint<S: Surface + ?Sized>(
+    surface: &S,
+    from: &Point2f,
+    angle: f64,
+    length: f64,
+    steps: &mut usize,
+) -> Option<(Point2f, f64)> {
+    let direction = direction_at(surface, from.x, from.y, angle);
+    let (du, dv) = uv_direction(surface, from.x, from.y, &direction)?;
+    let mut shot = (*steps).min(SHOT_STEPS);
+    *steps -= shot;
+    let (uv, l, complete) = shoot_within(surface, (from.x, from.y, du, dv), length, &mut shot);
+    *steps += shot;
+    if !complete {
+        return None;
+    }
+    uv.last().map(|p| (*p, l))
+}
+
+// the miss of the shot from the target and the arc length it ran
+fn residual<S: Surface + ?Sized>(
+    surface: &S,
+    (from, to): (&Point2f, &Point2f),
+    angle: f64,
+    length: f64,
+    steps: &mut usize,
+) -> Option<(Vector2f, f64)> {
+    let (e, l) = endpoint(surface, from, angle, length, steps)?;
+    Some((uv_difference(surface, &e, to), l))
+}
+
+// correct (angle, length) of the shot by Newton iteration until it hits the target,
+// a shot into the boundary is shortened to the length it ran instead of given up
+fn newton<S: Surface + ?Sized>(
+    surface: &S,
+    ends: (&Point2f, &Point2f),
+    angle: f64,
+    length: f64,
+    steps: &mut usize,
+) -> Option<(f64, f64)> {
+    // in uv, above the accuracy of the shots
+    let tolerance = 1e-7;
+    let reached = |l: f64, length: f64| l >= length - 1e-9 * length.max(1.0);
+    let mut angle = angle;
+    let (mut r, mut length) = residual(surface, ends, angle, length, steps)?;
+    for _ in 0..32 {
+        if r.norm() < tolerance {
+            return Some((angle, length));
+        }
+
+        // jacobian of the end point with respect to (angle, length) by finite differences,
+        // backward in length and toward the side of the angle that stays off the boundary
+        let hl = 1e-6 * length.max(1.0);
+        let (rl, _) = residual(surface, ends, angle, length - hl, steps)?;
+        let mut ha = 1e-6;
+        let (mut ra, l) = residual(surface, ends, angle + ha, length, steps)?;
+        if !reached(l, length) {
+            ha = -ha;
+            ra = residual(surface, ends, angle + ha, length, steps)?.0;
+        }
+        let j = Matrix2x2f::new(
+            (ra.x - r.x) / ha,
+            (r.x - rl.x) / hl,
+            (ra.y - r.y) / ha,
+            (r.y - rl.y) / hl,
+        );
+        let step = j.try_inverse()? * -r;
+
+        // damp the step while it does not reduce the residual, a start that stalls is given up
+        let mut alpha = 1.0;
+        loop {
+            let a = angle + step.x * alpha;
+            let l = length + step.y * alpha;
+            if l > 0.0 {
+                let (e, l) = residual(surface, ends, a, l, steps)?;
+                if e.norm() < r.norm() {
+                    angle = a;
+                    length = l;
+                    r = e;
+                    break;
+                }
+            }
+            alpha *= 0.5;
+            if alpha < 1.0 / 32.0 {
+                return None;
+            }
+        }
+    }
+    None
+}
+
+// arc length of the straight uv segment on the surface, the first guess of the geodesic length,
+// the tangent at one end overshoots where the surface is steep
+fn segment_length<S: Surface + ?Sized>(surface: &S, from: &Point2f, to: &Point2f) -> f64 {
+    let (nodes, weights) = gauss_legendre(16);
+    let d = to - from;
+    nodes
+        .iter()
+        .zip(weights.iter())
+        .filter_map(|(t, w)| {
+            let p = uv::wrap(surface, &(from + d * *t))?;
+            Some((surface.du_at(p.x, p.y) * d.x + surface.dv_at(p.x, p.y) * d.y).norm() * w)
+        })
+        .sum()
+}
+
+// boundary value geodesics between two uv points by shooting with Newton correction,
+// initial guesses are straight lines in uv toward every periodic copy of the target
+// with a few angular perturbations, distinct solutions are returned sorted by length
+pub fn geodesics_between<S: Surface + ?Sized>(
+    surface: &S,
+    from: &Point2f,
+    to: &Point2f,
+    guesses: usize,
+) -> Vec<Geodesic> {
+    let copies = |periodic: bool| {
+        if periodic {
+            vec![-1.0, 0.0, 1.0]
+        } else {
+            vec![0.0]
+        }
+    };
+    let mut solutions: Vec<(f64, f64)> = vec![];
+    let mut steps = STEPS;
+    let xu = surface.du_at(from.x, from.y).normalize();
+    let n = surface.normal_at(from.x, from.y);
+    // (angle, length) toward every copy of the target, the shortest segments get the steps first
+    let mut starts: Vec<(f64, f64)> = vec![];
+    for ou in copies(surface.u_domain().is_periodic()) {
+        for ov in copies(surface.v_domain().is_periodic()) {
+            let target = Point2f::new(to.x + ou, to.y + ov);
+            let delta = target - from;
+            let chord =
+                surface.du_at(from.x, from.y) * delta.x + surface.dv_at(from.x, from.y) * delta.y;
+            let length = segment_length(surface, from, &target);
+            if length > f64::EPSILON && chord.norm() > f64::EPSILON {
+                starts.push((chord.dot(&n.cross(&xu)).atan2(chord.dot(&xu)), length));
+            }
+        }
+    }
+    starts.sort_by(|a, b| a.1.total_cmp(&b.1));
+    for (angle, length) in starts {
+        for i in 0..guesses.max(1) {
+            // 0, +d, -d, +2d, -2d, ...
+            let k = i.div_ceil(2) as f64 * if i % 2 == 0 { -1.0 } else { 1.0 };
+            let a = angle + k * 0.25;
+            if let Some((a, l)) = newton(surface, (from, to), a, length, &mut steps) {
+                let duplicated = solutions.iter().any(|(sa, sl)| {
+                    let da = (sa - a + PI).rem_euclid(TAU) - PI;
+                    da.abs() < 1e-5 && (sl - l).abs() < 1e-6 * l.max(1.0)
+                });
+                if !duplicated {
+                    solutions.push((a, l));
+                }
+            }
+        }
+    }
+    solutions.sort_by(|a, b| a.1.total_cmp(&b.1));
+    solutions
+        .into_iter()
+        .filter_map(|(a, l)| {
+            let direction = direction_at(surface, from.x, from.y, a);
+            let (du, dv) = uv_direction(surface, from.x, from.y, &direction)?;
+            let (uv, length) = shoot(surface, from.x, from.y, du, dv, l);
+            let tessellation = tessellate(surface, &uv);
+            Some(Geodesic {
+                tessellation,
+                uv,
+                length,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{sphere::Sphere, torus::Torus};
+
+    // a geodesic of the sphere is an arc of the great circle in the plane through the center
+    #[test]
+    fn great_circle_on_sphere() {
+        let sphere = Sphere::default();
+        let (u, v, length) = (0.3, 0.2, 10.0);
+        let tangent = (sphere.dv_at(u, v) + sphere.du_at(u, v) * 0.2).normalize();
+        let geodesic = sphere.geodesic_from(u, v, &tangent, length).unwrap();
+        assert!((geodesic.length - length).abs() < 1e-9);
+
+        let points: Vec<Point3f> = geodesic
+            .uv
+            .iter()
+            .map(|p| sphere.point_at(p.x, p.y))
+            .collect();
+        let normal = points[0].coords.cross(&tangent).normalize();
+        for p in points.iter() {
+            assert!(p.coords.dot(&normal).abs() < 1e-6, "{:?} off the plane", p);
+        }
+        // the arcs of radius 2 between the points add up to the length
+        let arcs: f64 = points
+            .windows(2)
+            .map(|w| ((w[1] - w[0]).norm() / 4.0).asin() * 4.0)
+            .sum();
+        assert!((arcs - length).abs() < 1e-6, "arc length {}", arcs);
+    }
+
+    // the shortest geodesic between points on a meridian of the sphere is its arc
+    #[test]
+    fn shortest_on_sphere() {
+        let sphere = Sphere::default();
+        let geodesic = sphere
+            .shortest_geodesic(&Point2f::new(0.3, 0.5), &Point2f::new(0.6, 0.5))
+            .unwrap();
+        // radius 2 times the angle 0.3π between the colatitudes,
+        // which Sphere scales by 1 - 2δ to leave out the poles
+        let expected = 2.0 * 0.3 * PI * (1.0 - 2e-8);
+        assert!(
+            (geodesic.length - expected).abs() < 1e-8,
+            "length {} expected {}",
+            geodesic.length,
+            expected
+        );
+    }
+
+    // the shortest way between the points at u = 0.1 and 0.9 of a meridian of the torus
+    // crosses the seam of the periodic u, a fifth of the tube instead of four fifths
+    #[test]
+    fn shortest_on_torus_across_seam() {
+        let torus = Torus::default();
+        let (from, to) = (Point2f::new(0.1, 0.5), Point2f::new(0.9, 0.5));
+        let geodesic = torus.shortest_geodesic(&from, &to).unwrap();
+        // tube radius 1
+        let expected = 0.2 * TAU;
+        assert!(
+            (geodesic.length - expected).abs() < 1e-8,
+            "length {} expected {}",
+            geodesic.length,
+            expected
+        );
+        let end = geodesic.uv.last().unwrap();
+        assert!(uv_difference(&torus, end, &to).norm() < 1e-6);
+        // it leaves toward decreasing u and stays away from the middle of the meridian
+        assert!(geodesic.uv[1].x < from.x);
+        assert!(geodesic.uv.iter().all(|p| (p.x - 0.5).abs() > 0.35));
+    }
+
+    // Clairaut's relation: r cos ψ, the moment of the unit tangent around the axis,
+    // is conserved by the geodesic equation
+    #[test]
+    fn clairaut_on_torus() {
+        let torus = Torus::default();
+        let axis = Vector3f::new(0.0, 0.0, 1.0);
+        let (u, v) = (0.1, 0.3);
+        let direction = torus.du_at(u, v) + torus.dv_at(u, v) * 0.4;
+        let (du, dv) = uv_direction(&torus, u, v, &direction).unwrap();
+        let mut y: State = [u, v, du, dv];
+        let constant = |y: &State| {
+            let t = torus.du_at(y[0], y[1]) * y[2] + torus.dv_at(y[0], y[1]) * y[3];
+            (
+                axis.cross(&torus.point_at(y[0], y[1]).coords).dot(&t),
+                t.norm(),
+            )
+        };
+        let (c0, _) = constant(&y);
+        assert!(c0.abs() > 1.0);
+        for _ in 0..2000 {
+            y = wrap(&torus, &rk4(&torus, &y, 0.01)).unwrap();
+            let (c, speed) = constant(&y);
+            assert!((c - c0).abs() < 1e-9, "r cos ψ {} from {}", c, c0);
+            assert!((speed - 1.0).abs() < 1e-9, "speed {}", speed);
+        }
+    }
+}
@@ -0,0 +1,233 @@
+use crate::bspline::bernstein;
+use crate::domain::Domain;
+use crate::surfaces::bezier_patch::{control_net, BezierPatch};
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+
+// bicubic Gregory patch, a Bézier net whose four interior points each come in two,
+// one for the cross boundary derivative along u (the edges v = 0 and v = 1)
+// and one along v (the edges u = 0 and u = 1), blended rationally,
+// P_11(u, v) = (u F_u + v F_v) / (u + v) near the corner (0, 0) and alike at the others,
+// so the twists need not be compatible at the corners
+// https://en.wikipedia.org/wiki/Gregory_patch
+#[derive(Clone)]
+pub struct GregoryPatch {
+    // the 4 x 4 net with the interior points F_u
+    net: Vec<Vec<Point3f>>,
+    // F_v of the interior points net[1 + i][1 + j]
+    twins: [[Point3f; 2]; 2],
+}
+
+impl GregoryPatch {
+    // the 4 x 4 net holds F_u in its interior, twins[i][j] is F_v of net[1 + i][1 + j]
+    pub fn new(net: [[Point3f; 4]; 4], twins: [[Point3f; 2]; 2]) -> Self {
+        Self {
+            net: net.iter().map(|r| r.to_vec()).collect(),
+            twins,
+        }
+    }
+
+    // the bicubic patch as a Gregory patch with both interior points equal
+    pub fn from_bezier(patch: &BezierPatch) -> Option<Self> {
+        if patch.degrees() != (3, 3) {
+            return None;
+        }
+        let net = patch.control_points().to_vec();
+        let twins = [[net[1][1], net[1][2]], [net[2][1], net[2][2]]];
+        Some(Self { net, twins })
+    }
+
+    pub fn control_points(&self) -> &[Vec<Point3f>] {
+        &self.net
+    }
+
+    pub fn twins(&self) -> &[[Point3f; 2]; 2] {
+        &self.twins
+    }
+
+    // the rows along u through F_u and the columns along v through F_v as polylines
+    pub fn control_net(&self) -> Vec<Vec<Point3f>> {
+        let mut columns = self.net.clone();
+        for (i, twins) in self.twins.iter().enumerate() {
+            for (j, twin) in twins.iter().enumerate() {
+                columns[1 + i][1 + j] = *twin;
+            }
+        }
+        let rows = control_net(&self.net).into_iter().take(4);
+        let columns = control_net(&columns).into_iter().skip(4);
+        rows.chain(columns).collect()
+    }
+
+    // the interior point (1 + i, 1 + j) and its partial derivatives up to second order,
+    // P = F_v + g (F_u - F_v) with g = α / (α + β), α = u or 1 - u, β = v or 1 - v
+    fn interior(&self, i: usize, j: usize, u: f64, v: f64) -> [[Vector3f; 3]; 3] {
+        let fu = self.net[1 + i][1 + j].coords;
+        let fv = self.twins[i][j].coords;
+        let (a, sa) = if i == 0 { (u, 1.0) } else { (1.0 - u, -1.0) };
+        let (b, sb) = if j == 0 { (v, 1.0) } else { (1.0 - v, -1.0) };
+        let s = a + b;
+        let mut g = [[0.0; 3]; 3];
+        if s > 0.0 {
+            g[0][0] = a / s;
+            g[1][0] = sa * b / (s * s);
+            g[0][1] = -sb * a / (s * s);
+            g[2][0] = -2.0 * b / (s * s * s);
+            g[0][2] = 2.0 * a / (s * s * s);
+            g[1][1] = sa * sb * (a - b) / (s * s * s);
+        } else {
+            // the corner itself, where the point has no weight
+            g[0][0] = 0.5;
+        }
+        let mut p = [[Vector3f::zeros(); 3]; 3];
+        for (k, row) in p.iter_mut().enumerate() {
+            for (l, d) in row.iter_mut().enumerate() {
+                *d = (fu - fv) * g[k][l];
+            }
+        }
+        p[0][0] += fv;
+        p
+    }
+
+    // the partial derivative ∂^(k+l) S / ∂u^k ∂v^l for k + l <= 2 by the product rule
+    fn derivative(&self, u: f64, v: f64, k: usize, l: usize) -> Vector3f {
+        let bu = bernstein(3, u, 2);
+        let bv = bernstein(3, v, 2);
+        let mut d = Vector3f::zeros();
+        for (i, row) in self.net.iter().enumerate() {
+            for (j, point) in row.iter().enumerate() {
+                if (1..=2).contains(&i) && (1..=2).contains(&j) {
+                    let p = self.interior(i - 1, j - 1, u, v);
+                    // Leibniz over the orders of u and v
+                    for ku in 0..=k {
+                        for lv in 0..=l {
+                            let c = binomial(k, ku) * binomial(l, lv);
+                            d += p[k - ku][l - lv] * (c * bu[ku][i] * bv[lv][j]);
+                        }
+                    }
+                } else {
+                    d += point.coords * (bu[k][i] * bv[l][j]);
+                }
+            }
+        }
+        d
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    match (n, k) {
+        (2, 1) => 2.0,
+        _ => 1.0,
+    }
+}
+
+impl Default for GregoryPatch {
+    // the default Bézier hill with its interior twins pulled apart
+    fn default() -> Self {
+        let mut patch = GregoryPatch::from_bezier(&BezierPatch::default()).unwrap();
+        let lift = [[0.8, -0.6], [-0.4, 1.0]];
+        for (i, twins) in patch.twins.iter_mut().enumerate() {
+            for (j, twin) in twins.iter_mut().enumerate() {
+                twin.z += lift[i][j];
+            }
+        }
+        patch
+    }
+}
+
+impl Surface for GregoryPatch {
+    fn u_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.derivative(u, v, 0, 0))
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 0)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 1)
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 2, 0)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 1, 1)
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivative(u, v, 0, 2)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::uv;
+
+    // with both interior points equal the rational blend drops out
+    #[test]
+    fn from_bezier_is_the_bezier_patch() {
+        let bezier = BezierPatch::default();
+        let gregory = GregoryPatch::from_bezier(&bezier).unwrap();
+        for (u, v) in
+            uv::grid(6)
+                .into_iter()
+                .chain([(0.0, 0.0), (1.0, 0.0), (0.0, 1.0), (1.0, 1.0)])
+        {
+            assert!((gregory.point_at(u, v) - bezier.point_at(u, v)).norm() < 1e-12);
+            for (g, b) in [
+                (gregory.du_at(u, v), bezier.du_at(u, v)),
+                (gregory.dv_at(u, v), bezier.dv_at(u, v)),
+                (gregory.dudu_at(u, v), bezier.dudu_at(u, v)),
+                (gregory.dudv_at(u, v), bezier.dudv_at(u, v)),
+                (gregory.dvdv_at(u, v), bezier.dvdv_at(u, v)),
+            ] {
+                assert!((g - b).norm() < 1e-9, "{:?} {:?} at ({}, {})", g, b, u, v);
+            }
+        }
+    }
+
+    // the derivatives of the rational blend agree with central differences away from the corners
+    #[test]
+    fn derivatives_agree_with_differences() {
+        let patch = GregoryPatch::default();
+        let h = 1e-5;
+        let difference = |f: &dyn Fn(f64, f64) -> Vector3f, u: f64, v: f64| {
+            (
+                (f(u + h, v) - f(u - h, v)) / (2.0 * h),
+                (f(u, v + h) - f(u, v - h)) / (2.0 * h),
+            )
+        };
+        for (u, v) in uv::grid(5) {
+            let (du, dv) = difference(&|u, v| patch.point_at(u, v).coords, u, v);
+            let (dudu, dudv) = difference(&|u, v| patch.du_at(u, v), u, v);
+            let (_, dvdv) = difference(&|u, v| patch.dv_at(u, v), u, v);
+            for (d, exact) in [
+                (du, patch.du_at(u, v)),
+                (dv, patch.dv_at(u, v)),
+                (dudu, patch.dudu_at(u, v)),
+                (dudv, patch.dudv_at(u, v)),
+                (dvdv, patch.dvdv_at(u, v)),
+            ] {
+                assert!(
+                    (d - exact).norm() < 1e-6 * exact.norm().max(1.0),
+                    "difference {:?} derivative {:?} at ({}, {})",
+                    d,
+                    exact,
+                    u,
+                    v
+                );
+            }
+        }
+    }
+}
@@ -24,9 +24,22 @@ impl Helicoid {
     fn map(&self, u: f64, v: f64) -> (f64, f64) {
         (self.domain.map(u), self.domain.map(v))
     }
+
+    // du/du01 = dv/dv01
+    fn scale(&self) -> f64 {
+        self.domain.length()
+    }
 }
 
 impl Surface for Helicoid {
+    fn u_domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
     fn point_at(&self, u: f64, v: f64) -> Point3f {
         let (u, v) = self.map(u, v);
         self.plane.origin
@@ -37,14 +50,15 @@ impl Surface for Helicoid {
 
     fn du_at(&self, u: f64, v: f64) -> Vector3f {
         let (_u, v) = self.map(u, v);
-        self.plane.x_axis * v.cos() + self.plane.y_axis * v.sin()
+        (self.plane.x_axis * v.cos() + self.plane.y_axis * v.sin()) * self.scale()
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
         let (u, v) = self.map(u, v);
-        -self.plane.x_axis * u * v.sin()
+        (-self.plane.x_axis * u * v.sin()
             + self.plane.y_axis * u * v.cos()
-            + self.plane.normal * self.c
+            + self.plane.normal * self.c)
+            * self.scale()
     }
 
     fn dudu_at(&self, _: f64, _: f64) -> Vector3f {
@@ -53,11 +67,13 @@ impl Surface for Helicoid {
 
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
         let (_u, v) = self.map(u, v);
-        -self.plane.x_axis * v.sin() + self.plane.y_axis * v.cos()
+        (-self.plane.x_axis * v.sin() + self.plane.y_axis * v.cos()) * self.scale() * self.scale()
     }
 
     fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
         let (u, v) = self.map(u, v);
-        -self.plane.x_axis * u * v.cos() - self.plane.y_axis * u * v.sin()
+        (-self.plane.x_axis * u * v.cos() - self.plane.y_axis * u * v.sin())
+            * self.scale()
+            * self.scale()
     }
 }
@@ -30,9 +30,22 @@ impl HyperbolicParaboloid {
     fn map(&self, u: f64, v: f64) -> (f64, f64) {
         (self.domain.map(u), self.domain.map(v))
     }
+
+    // du/du01 = dv/dv01
+    fn scale(&self) -> f64 {
+        self.domain.length()
+    }
 }
 
 impl Surface for HyperbolicParaboloid {
+    fn u_domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
     fn point_at(&self, u: f64, v: f64) -> Point3f {
         let (u, v) = self.map(u, v);
         let w = u * u - v * v;
@@ -44,16 +57,16 @@ impl Surface for HyperbolicParaboloid {
 
     fn du_at(&self, u: f64, v: f64) -> Vector3f {
         let (u, _v) = self.map(u, v);
-        self.plane.x_axis * self.a + self.plane.normal * 2.0 * u
+        (self.plane.x_axis * self.a + self.plane.normal * 2.0 * u) * self.scale()
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
         let (_u, v) = self.map(u, v);
-        self.plane.y_axis * self.b - self.plane.normal * 2.0 * v
+        (self.plane.y_axis * self.b - self.plane.normal * 2.0 * v) * self.scale()
     }
 
     fn dudu_at(&self, _: f64, _: f64) -> Vector3f {
-        self.plane.normal * 2.0
+        self.plane.normal * 2.0 * self.scale() * self.scale()
     }
 
     fn dudv_at(&self, _: f64, _: f64) -> Vector3f {
@@ -61,6 +74,6 @@ impl Surface for HyperbolicParaboloid {
     }
 
     fn dvdv_at(&self, _: f64, _: f64) -> Vector3f {
-        -self.plane.normal * 2.0
+        -self.plane.normal * 2.0 * self.scale() * self.scale()
     }
 }
@@ -0,0 +1,449 @@
+use crate::expression::{Expression, Jet};
+use crate::surface_tessellation::SurfaceTessellation;
+use crate::surfaces::surface_curvature::SurfaceCurvature;
+use crate::types::*;
+use core::f64::consts::PI;
+use std::collections::{BTreeMap, HashMap};
+
+// f(x, y, z) as a jet of two of its coordinates at a time
+pub type ImplicitFunction = dyn Fn(Jet, Jet, Jet) -> Jet;
+
+pub enum ImplicitField {
+    Function(Box<ImplicitFunction>),
+    // a formula of x, y and z
+    Expression(Box<Expression>),
+}
+
+// Kuhn's six tetrahedra of the unit cube around its diagonal from (0, 0, 0) to (1, 1, 1),
+// each walking the axes in one order, they meet face to face across neighbouring cubes
+const KUHN: [[usize; 3]; 6] = [
+    [0, 1, 2],
+    [0, 2, 1],
+    [1, 0, 2],
+    [1, 2, 0],
+    [2, 0, 1],
+    [2, 1, 0],
+];
+
+// the level set f(x, y, z) = 0 inside the box from min to max,
+// oriented by the normal ∇f / |∇f| toward f > 0,
+// with the curvatures from the gradient and the Hessian H alone (Goldman),
+// K = ∇f adj(H) ∇f^T / |∇f|^4 and H_mean = (∇f H ∇f^T - |∇f|^2 tr H) / 2 |∇f|^3
+// https://doi.org/10.1016/j.cagd.2005.06.005
+pub struct ImplicitSurface {
+    field: ImplicitField,
+    min: Point3f,
+    max: Point3f,
+}
+
+impl ImplicitSurface {
+    pub fn new(field: ImplicitField, min: Point3f, max: Point3f) -> Self {
+        Self { field, min, max }
+    }
+
+    pub fn function(
+        f: impl Fn(Jet, Jet, Jet) -> Jet + 'static,
+        min: Point3f,
+        max: Point3f,
+    ) -> Self {
+        Self::new(ImplicitField::Function(Box::new(f)), min, max)
+    }
+
+    // f(x, y, z) as a formula, e.g. "x^2 + y^2 + z^2 - 4" for a sphere
+    pub fn try_expression(f: &str, min: Point3f, max: Point3f) -> Result<Self, fasteval::Error> {
+        let expression = Expression::parse(f)?;
+        let center = nalgebra::center(&min, &max);
+        let mut variables: BTreeMap<String, Jet> = BTreeMap::new();
+        variables.insert(String::from("x"), Jet::u(center.x));
+        variables.insert(String::from("y"), Jet::v(center.y));
+        variables.insert(String::from("z"), Jet::constant(center.z));
+        expression.jet(&variables)?;
+        Ok(Self::new(
+            ImplicitField::Expression(Box::new(expression)),
+            min,
+            max,
+        ))
+    }
+
+    pub fn sphere(radius: f64) -> Self {
+        let r = radius * 1.25;
+        Self::function(
+            move |x, y, z| x * x + y * y + z * z - Jet::constant(radius * radius),
+            Point3f::new(-r, -r, -r),
+            Point3f::new(r, r, r),
+        )
+    }
+
+    // Schoen's gyroid closely approximated by sin x cos y + sin y cos z + sin z cos x = 0,
+    // triply periodic with the period 2 π / frequency, over periods^3 cells of the lattice
+    pub fn gyroid(frequency: f64, periods: f64) -> Self {
+        let r = PI * periods / frequency;
+        Self::function(
+            move |x, y, z| {
+                let (x, y, z) = (x.scale(frequency), y.scale(frequency), z.scale(frequency));
+                x.sin() * y.cos() + y.sin() * z.cos() + z.sin() * x.cos()
+            },
+            Point3f::new(-r, -r, -r),
+            Point3f::new(r, r, r),
+        )
+    }
+
+    // the quartic tanglecube x^4 - 5 x^2 + y^4 - 5 y^2 + z^4 - 5 z^2 + 11.8 = 0 of genus 5
+    pub fn tanglecube() -> Self {
+        Self::function(
+            |x, y, z| {
+                let quartic = |t: Jet| t * t * t * t - (t * t).scale(5.0);
+                quartic(x) + quartic(y) + quartic(z) + Jet::constant(11.8)
+            },
+            Point3f::new(-3.0, -3.0, -3.0),
+            Point3f::new(3.0, 3.0, 3.0),
+        )
+    }
+
+    // Blinn's blobs, the level 1 of Σ e^(-|p - c|^2 / r^2) over the balls (c, r)
+    pub fn metaballs(balls: Vec<(Point3f, f64)>) -> Self {
+        let margin = balls.iter().map(|(_, r)| *r).fold(0.0, f64::max) * 2.0;
+        let margin = Vector3f::new(margin, margin, margin);
+        let min = balls.iter().fold(Point3f::origin(), |m, (c, _)| m.inf(c)) - margin;
+        let max = balls.iter().fold(Point3f::origin(), |m, (c, _)| m.sup(c)) + margin;
+        Self::function(
+            move |x, y, z| {
+                balls.iter().fold(Jet::constant(1.0), |sum, (c, r)| {
+                    let dx = x - Jet::constant(c.x);
+                    let dy = y - Jet::constant(c.y);
+                    let dz = z - Jet::constant(c.z);
+                    let d2 = dx * dx + dy * dy + dz * dz;
+                    sum - d2.scale(-1.0 / (r * r)).exp()
+                })
+            },
+            min,
+            max,
+        )
+    }
+
+    pub fn bounds(&self) -> (Point3f, Point3f) {
+        (self.min, self.max)
+    }
+
+    // f at p with its partial derivatives in the coordinates a and b of p
+    fn jet(&self, p: &Point3f, a: usize, b: usize) -> Jet {
+        let seed = |i: usize| {
+            if i == a {
+                Jet::u(p[i])
+            } else if i == b {
+                Jet::v(p[i])
+            } else {
+                Jet::constant(p[i])
+            }
+        };
+        let (x, y, z) = (seed(0), seed(1), seed(2));
+        match &self.field {
+            ImplicitField::Function(f) => f(x, y, z),
+            ImplicitField::Expression(expression) => {
+                let mut variables: BTreeMap<String, Jet> = BTreeMap::new();
+                variables.insert(String::from("x"), x);
+                variables.insert(String::from("y"), y);
+                variables.insert(String::from("z"), z);
+                expression
+                    .jet(&variables)
+                    .unwrap_or_else(|_| Jet::constant(f64::NAN))
+            }
+        }
+    }
+
+    pub fn value_at(&self, p: &Point3f) -> f64 {
+        self.jet(p, 3, 3).value
+    }
+
+    // the value, the gradient and the Hessian from the jets of (x, y), (y, z) and (z, x)
+    fn derivatives_at(&self, p: &Point3f) -> (f64, Vector3f, Matrix3x3f) {
+        let xy = self.jet(p, 0, 1);
+        let yz = self.jet(p, 1, 2);
+        let zx = self.jet(p, 2, 0);
+        let gradient = Vector3f::new(xy.du, xy.dv, yz.dv);
+        let hessian = Matrix3x3f::new(
+            xy.duu, xy.duv, zx.duv, xy.duv, xy.dvv, yz.duv, zx.duv, yz.duv, yz.dvv,
+        );
+        (xy.value, gradient, hessian)
+    }
+
+    pub fn gradient_at(&self, p: &Point3f) -> Vector3f {
+        self.derivatives_at(p).1
+    }
+
+    pub fn hessian_at(&self, p: &Point3f) -> Matrix3x3f {
+        self.derivatives_at(p).2
+    }
+
+    pub fn normal_at(&self, p: &Point3f) -> Vector3f {
+        self.gradient_at(p).normalize()
+    }
+
+    // the nearby point of the surface by Newton's method along the gradient
+    pub fn project(&self, p: &Point3f) -> Point3f {
+        let mut p = *p;
+        for _ in 0..16 {
+            let (f, g, _) = self.derivatives_at(&p);
+            let step = g * (f / g.norm_squared());
+            p -= step;
+            if step.norm() < 1e-12 {
+                break;
+            }
+        }
+        p
+    }
+
+    // Goldman's curvatures at a point of the surface,
+    // the principal directions are the eigenvectors of the shape operator -P H P / |∇f| in the tangent plane
+    pub fn curvature_at(&self, p: &Point3f) -> SurfaceCurvature<f64> {
+        let (_, g, h) = self.derivatives_at(p);
+        let length = g.norm();
+        let normal = g / length;
+        // ∇f adj(H) ∇f^T with the adjugate of the symmetric H from the cross products of its rows
+        let (r0, r1, r2) = (
+            h.row(0).transpose(),
+            h.row(1).transpose(),
+            h.row(2).transpose(),
+        );
+        let adjugate = r1.cross(&r2) * g.x + r2.cross(&r0) * g.y + r0.cross(&r1) * g.z;
+        let gaussian = g.dot(&adjugate) / length.powi(4);
+        let mean = (g.dot(&(h * g)) - length * length * h.trace()) / (2.0 * length.powi(3));
+        let d = (mean * mean - gaussian).max(0.0).sqrt();
+        let (k1, k2) = (mean + d, mean - d);
+
+        let projection = Matrix3x3f::identity() - normal * normal.transpose();
+        let shape = -(projection * h * projection) / length;
+        let eigen = shape.symmetric_eigen();
+        // the two eigenvectors farthest from the normal, by descending eigenvalue
+        let mut tangents: Vec<(f64, Vector3f)> = (0..3)
+            .map(|i| {
+                (
+                    eigen.eigenvalues[i],
+                    eigen.eigenvectors.column(i).into_owned(),
+                )
+            })
+            .collect();
+        tangents.sort_by(|a, b| {
+            a.1.dot(&normal)
+                .abs()
+                .partial_cmp(&b.1.dot(&normal).abs())
+                .unwrap_or(std::cmp::Ordering::Equal)
+        });
+        tangents.truncate(2);
+        tangents.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        SurfaceCurvature {
+            point: *p,
+            // the surface has no parameters
+            uv: (0.0, 0.0),
+            normal,
+            gaussian,
+            mean,
+            k1,
+            k2,
+            principal_directions_uv: (Vector2f::zeros(), Vector2f::zeros()),
+            principal_directions: (tangents[0].1, tangents[1].1),
+        }
+    }
+
+    // the point where f changes sign on the segment from a to b, by regula falsi from the linear guess
+    fn crossing(&self, a: &Point3f, fa: f64, b: &Point3f, fb: f64) -> Point3f {
+        let (mut a, mut fa, mut b, mut fb) = (*a, fa, *b, fb);
+        let mut p = a + (b - a) * (fa / (fa - fb));
+        for _ in 0..4 {
+            let fp = self.value_at(&p);
+            if fp == 0.0 || !fp.is_finite() {
+                break;
+            }
+            if (fp < 0.0) == (fa < 0.0) {
+                a = p;
+                fa = fp;
+            } else {
+                b = p;
+                fb = fp;
+            }
+            p = a + (b - a) * (fa / (fa - fb));
+        }
+        p
+    }
+
+    // marching cubes over count^3 cells of the box, each cube split into Kuhn's six tetrahedra
+    // so that no case table is needed and no face is ambiguous,
+    // the vertices on shared grid edges are shared and carry Goldman's curvatures,
+    // rows and columns are 0 since the mesh has no parameter grid
+    pub fn tessellate(&self, count: usize) -> SurfaceTessellation {
+        let n = count.max(1);
+        let size = self.max - self.min;
+        let index = |i: usize, j: usize, k: usize| (i * (n + 1) + j) * (n + 1) + k;
+        let corner = |i: usize, j: usize, k: usize| {
+            self.min
+                + Vector3f::new(
+                    size.x * i as f64 / n as f64,
+                    size.y * j as f64 / n as f64,
+                    size.z * k as f64 / n as f64,
+                )
+        };
+        let mut corners: Vec<Point3f> = Vec::with_capacity((n + 1).pow(3));
+        for i in 0..=n {
+            for j in 0..=n {
+                for k in 0..=n {
+                    corners.push(corner(i, j, k));
+                }
+            }
+        }
+        let values: Vec<f64> = corners.iter().map(|p| self.value_at(p)).collect();
+
+        let mut points: Vec<Point3f> = vec![];
+        let mut edges: HashMap<(usize, usize), usize> = HashMap::new();
+        let mut indices: Vec<usize> = vec![];
+        for i in 0..n {
+            for j in 0..n {
+                for k in 0..n {
+                    for axes in KUHN.iter() {
+                        let mut c = [i, j, k];
+                        let mut tetrahedron = [index(i, j, k); 4];
+                        for (t, axis) in tetrahedron.iter_mut().skip(1).zip(axes.iter()) {
+                            c[*axis] += 1;
+                            *t = index(c[0], c[1], c[2]);
+                        }
+                        let (inside, outside): (Vec<usize>, Vec<usize>) =
+                            tetrahedron.iter().partition(|t| values[**t] < 0.0);
+                        if inside.is_empty() || outside.is_empty() {
+                            continue;
+                        }
+                        let mut vertex = |a: usize, b: usize| {
+                            *edges.entry((a.min(b), a.max(b))).or_insert_with(|| {
+                                points.push(self.crossing(
+                                    &corners[a],
+                                    values[a],
+                                    &corners[b],
+                                    values[b],
+                                ));
+                                points.len() - 1
+                            })
+                        };
+                        let polygon: Vec<usize> = match (inside.len(), outside.len()) {
+                            (1, _) => outside.iter().map(|o| vertex(inside[0], *o)).collect(),
+                            (_, 1) => inside.iter().map(|i| vertex(*i, outside[0])).collect(),
+                            // the quad around the two edges that do not cross
+                            _ => vec![
+                                vertex(inside[0], outside[0]),
+                                vertex(inside[0], outside[1]),
+                                vertex(inside[1], outside[1]),
+                                vertex(inside[1], outside[0]),
+                            ],
+                        };
+                        // orient toward f > 0
+                        let centroid = |ts: &[usize]| {
+                            ts.iter().map(|t| corners[*t].coords).sum::<Vector3f>()
+                                / ts.len() as f64
+                        };
+                        let direction = centroid(&outside) - centroid(&inside);
+                        for f in 1..polygon.len() - 1 {
+                            let (a, b, c) = (polygon[0], polygon[f], polygon[f + 1]);
+                            let normal = (points[b] - points[a]).cross(&(points[c] - points[a]));
+                            if normal.dot(&direction) >= 0.0 {
+                                indices.extend_from_slice(&[a, b, c]);
+                            } else {
+                                indices.extend_from_slice(&[a, c, b]);
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        let curvatures: Vec<SurfaceCurvature<f64>> =
+            points.iter().map(|p| self.curvature_at(p)).collect();
+        SurfaceTessellation::new(0, 0, &curvatures, indices)
+    }
+}
+
+impl Default for ImplicitSurface {
+    fn default() -> Self {
+        ImplicitSurface::gyroid(1.0, 2.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{surface::Surface, torus::Torus, uv};
+    use std::collections::{HashMap, HashSet};
+
+    #[test]
+    fn sphere_curvature() {
+        let radius = 2.0;
+        let sphere = ImplicitSurface::sphere(radius);
+        for p in [
+            Point3f::new(1.0, 0.5, -0.3),
+            Point3f::new(-2.5, 1.0, 0.2),
+            Point3f::new(0.1, -0.2, 1.9),
+        ] {
+            let p = sphere.project(&p);
+            assert!((p.coords.norm() - radius).abs() < 1e-12);
+            let c = sphere.curvature_at(&p);
+            assert!((c.gaussian - 1.0 / (radius * radius)).abs() < 1e-12);
+            assert!((c.mean.abs() - 1.0 / radius).abs() < 1e-12);
+            assert!((c.k1 - c.k2).abs() < 1e-6);
+        }
+    }
+
+    // (x^2 + y^2 + z^2 + b^2 - a^2)^2 - 4 b^2 (x^2 + y^2) = 0 is the parametric Torus
+    #[test]
+    fn torus_curvature_agrees_with_parametric() {
+        let torus = Torus::default();
+        let (a, b) = (torus.a, torus.b);
+        let r = a + b;
+        let implicit = ImplicitSurface::function(
+            move |x, y, z| {
+                let s = x * x + y * y + z * z + Jet::constant(b * b - a * a);
+                s * s - (x * x + y * y).scale(4.0 * b * b)
+            },
+            Point3f::new(-r, -r, -a),
+            Point3f::new(r, r, a),
+        );
+        for (u, v) in uv::grid(5) {
+            let expected = torus.curvature_at(u, v);
+            let c = implicit.curvature_at(&torus.point_at(u, v));
+            // the mean curvature follows the orientation of the normals
+            let sign = c.normal.dot(&expected.normal).signum();
+            assert!(
+                (c.gaussian - expected.gaussian).abs() < 1e-9,
+                "K at ({}, {})",
+                u,
+                v
+            );
+            assert!(
+                (c.mean * sign - expected.mean).abs() < 1e-9,
+                "H at ({}, {})",
+                u,
+                v
+            );
+        }
+    }
+
+    // every edge of the mesh of the sphere is shared by two triangles of opposite orientation
+    // and V - E + F = 2
+    #[test]
+    fn sphere_mesh_is_closed() {
+        let tessellation = ImplicitSurface::sphere(2.0).tessellate(16);
+        let triangles = tessellation.triangles();
+        // the triangles using each edge and the sum of +1 for those walking it from
+        // the lower index to the higher and -1 for the others
+        let mut edges: HashMap<(usize, usize), (usize, i32)> = HashMap::new();
+        for t in triangles.chunks(3) {
+            for k in 0..3 {
+                let (i, j) = (t[k], t[(k + 1) % 3]);
+                assert_ne!(i, j);
+                let edge = edges.entry((i.min(j), i.max(j))).or_insert((0, 0));
+                edge.0 += 1;
+                edge.1 += if i < j { 1 } else { -1 };
+            }
+        }
+        assert!(edges.values().all(|e| *e == (2, 0)));
+
+        let vertices: HashSet<&usize> = triangles.iter().collect();
+        let euler = vertices.len() as i64 - edges.len() as i64 + (triangles.len() / 3) as i64;
+        assert_eq!(euler, 2);
+    }
+}
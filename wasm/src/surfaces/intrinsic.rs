@@ -0,0 +1,255 @@
+use crate::surfaces::fundamental_form::FirstFundamentalForm;
+use crate::surfaces::surface::Surface;
+use crate::types::Matrix2x2f;
+
+// intrinsic quantities are computed from the first fundamental form alone,
+// its derivatives are taken by central differences of this step in the native parameters
+const DELTA: f64 = 1e-4;
+
+// Γ^k_ij, indexed as gamma[k][i][j] with 0 = u, 1 = v
+#[derive(Clone, Copy, Debug)]
+pub struct ChristoffelSymbols {
+    pub gamma: [[[f64; 2]; 2]; 2],
+}
+
+impl ChristoffelSymbols {
+    // from (Γ^u_ij, Γ^v_ij) as symmetric matrices
+    pub fn from_matrices((gu, gv): (Matrix2x2f, Matrix2x2f)) -> Self {
+        let mut gamma = [[[0.0; 2]; 2]; 2];
+        for (k, m) in [gu, gv].iter().enumerate() {
+            for (i, gki) in gamma[k].iter_mut().enumerate() {
+                for (j, gkij) in gki.iter_mut().enumerate() {
+                    *gkij = m[(i, j)];
+                }
+            }
+        }
+        Self { gamma }
+    }
+
+    pub fn get(&self, k: usize, i: usize, j: usize) -> f64 {
+        self.gamma[k][i][j]
+    }
+
+    // (Γ^u_ij, Γ^v_ij) as symmetric matrices
+    pub fn matrices(&self) -> (Matrix2x2f, Matrix2x2f) {
+        let m = |k: usize| {
+            Matrix2x2f::new(
+                self.gamma[k][0][0],
+                self.gamma[k][0][1],
+                self.gamma[k][1][0],
+                self.gamma[k][1][1],
+            )
+        };
+        (m(0), m(1))
+    }
+}
+
+// R^l_ijk, indexed as r[l][i][j][k]
+#[derive(Clone, Copy, Debug)]
+pub struct RiemannTensor {
+    pub r: [[[[f64; 2]; 2]; 2]; 2],
+}
+
+impl RiemannTensor {
+    pub fn get(&self, l: usize, i: usize, j: usize, k: usize) -> f64 {
+        self.r[l][i][j][k]
+    }
+
+    // R_lijk = g_lm R^m_ijk
+    pub fn lower(
+        &self,
+        first: &FirstFundamentalForm,
+        l: usize,
+        i: usize,
+        j: usize,
+        k: usize,
+    ) -> f64 {
+        let g = first.matrix();
+        (0..2).map(|m| g[(l, m)] * self.r[m][i][j][k]).sum()
+    }
+
+    // K = R_1212 / (EG - FF)
+    pub fn gaussian_curvature(&self, first: &FirstFundamentalForm) -> f64 {
+        self.lower(first, 0, 1, 0, 1) / first.determinant()
+    }
+}
+
+fn metric<S: Surface + ?Sized>(surface: &S, u: f64, v: f64) -> Matrix2x2f {
+    surface.first_fundamental_form_at(u, v).matrix()
+}
+
+// the steps in normalized (u, v) that are the given one in the native parameters
+fn steps<S: Surface + ?Sized>(surface: &S, h: f64) -> [f64; 2] {
+    [
+        h / surface.u_domain().length().abs(),
+        h / surface.v_domain().length().abs(),
+    ]
+}
+
+// (∂g/∂u, ∂g/∂v)
+fn metric_derivatives<S: Surface + ?Sized>(surface: &S, u: f64, v: f64) -> [Matrix2x2f; 2] {
+    let [hu, hv] = steps(surface, DELTA);
+    [
+        (metric(surface, u + hu, v) - metric(surface, u - hu, v)) / (2.0 * hu),
+        (metric(surface, u, v + hv) - metric(surface, u, v - hv)) / (2.0 * hv),
+    ]
+}
+
+// Γ^k_ij = 1/2 g^kl (∂_i g_jl + ∂_j g_il - ∂_l g_ij) by differences of the metric,
+// Surface::christoffel_symbols_at gives them exactly from the second derivatives
+pub fn christoffel_symbols<S: Surface + ?Sized>(surface: &S, u: f64, v: f64) -> ChristoffelSymbols {
+    let ig = surface
+        .first_fundamental_form_at(u, v)
+        .inverse()
+        .unwrap_or_else(Matrix2x2f::zeros);
+    let dg = metric_derivatives(surface, u, v);
+    let mut gamma = [[[0.0; 2]; 2]; 2];
+    for (k, gk) in gamma.iter_mut().enumerate() {
+        for (i, gki) in gk.iter_mut().enumerate() {
+            for (j, gkij) in gki.iter_mut().enumerate() {
+                *gkij = (0..2)
+                    .map(|l| ig[(k, l)] * 0.5 * (dg[i][(j, l)] + dg[j][(i, l)] - dg[l][(i, j)]))
+                    .sum();
+            }
+        }
+    }
+    ChristoffelSymbols { gamma }
+}
+
+// R^l_ijk = ∂_j Γ^l_ik - ∂_k Γ^l_ij + Γ^l_jm Γ^m_ik - Γ^l_km Γ^m_ij
+pub fn riemann_tensor<S: Surface + ?Sized>(surface: &S, u: f64, v: f64) -> RiemannTensor {
+    let h = steps(surface, DELTA * 10.0);
+    let c = christoffel_symbols(surface, u, v);
+    let du = (
+        christoffel_symbols(surface, u + h[0], v),
+        christoffel_symbols(surface, u - h[0], v),
+    );
+    let dv = (
+        christoffel_symbols(surface, u, v + h[1]),
+        christoffel_symbols(surface, u, v - h[1]),
+    );
+    // ∂_d Γ^l_ij
+    let dgamma = |d: usize, l: usize, i: usize, j: usize| {
+        let (p, m) = if d == 0 { &du } else { &dv };
+        (p.get(l, i, j) - m.get(l, i, j)) / (2.0 * h[d])
+    };
+
+    let mut r = [[[[0.0; 2]; 2]; 2]; 2];
+    for (l, rl) in r.iter_mut().enumerate() {
+        for (i, rli) in rl.iter_mut().enumerate() {
+            for (j, rlij) in rli.iter_mut().enumerate() {
+                for (k, rlijk) in rlij.iter_mut().enumerate() {
+                    let quadratic: f64 = (0..2)
+                        .map(|m| c.get(l, j, m) * c.get(m, i, k) - c.get(l, k, m) * c.get(m, i, j))
+                        .sum();
+                    *rlijk = dgamma(j, l, i, k) - dgamma(k, l, i, j) + quadratic;
+                }
+            }
+        }
+    }
+    RiemannTensor { r }
+}
+
+// Brioschi formula
+// https://mathworld.wolfram.com/BrioschiFormula.html
+pub fn brioschi_gaussian_curvature<S: Surface + ?Sized>(surface: &S, u: f64, v: f64) -> f64 {
+    let [hu, hv] = steps(surface, DELTA * 10.0);
+    let first = |u: f64, v: f64| surface.first_fundamental_form_at(u, v);
+    let FirstFundamentalForm { e, f, g } = first(u, v);
+    let d = metric_derivatives(surface, u, v);
+    let (e_u, e_v) = (d[0][(0, 0)], d[1][(0, 0)]);
+    let (f_u, f_v) = (d[0][(0, 1)], d[1][(0, 1)]);
+    let (g_u, g_v) = (d[0][(1, 1)], d[1][(1, 1)]);
+
+    let e_vv = (first(u, v + hv).e - 2.0 * e + first(u, v - hv).e) / (hv * hv);
+    let g_uu = (first(u + hu, v).g - 2.0 * g + first(u - hu, v).g) / (hu * hu);
+    let f_uv = (first(u + hu, v + hv).f - first(u + hu, v - hv).f - first(u - hu, v + hv).f
+        + first(u - hu, v - hv).f)
+        / (4.0 * hu * hv);
+
+    let det3 = |m: [[f64; 3]; 3]| {
+        m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+            - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+            + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+    };
+    let a = det3([
+        [-0.5 * e_vv + f_uv - 0.5 * g_uu, 0.5 * e_u, f_u - 0.5 * e_v],
+        [f_v - 0.5 * g_u, e, f],
+        [0.5 * g_v, f, g],
+    ]);
+    let b = det3([
+        [0.0, 0.5 * e_v, 0.5 * g_u],
+        [0.5 * e_v, e, f],
+        [0.5 * g_u, f, g],
+    ]);
+    let det = e * g - f * f;
+    (a - b) / (det * det)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{surface::tests::surfaces, uv};
+
+    // Theorema Egregium: the curvature from the metric agrees with the one from the shape operator
+    #[test]
+    fn egregium() {
+        for (index, surface) in surfaces().iter().enumerate() {
+            for (u, v) in uv::grid(5) {
+                let extrinsic = surface.curvature_at(u, v).gaussian;
+                let tolerance = 1e-4 * extrinsic.abs().max(1.0);
+                let brioschi = brioschi_gaussian_curvature(surface.as_ref(), u, v);
+                let first = surface.first_fundamental_form_at(u, v);
+                let riemann = riemann_tensor(surface.as_ref(), u, v).gaussian_curvature(&first);
+                assert!(
+                    (brioschi - extrinsic).abs() < tolerance,
+                    "surface {} brioschi {} extrinsic {} at ({}, {})",
+                    index,
+                    brioschi,
+                    extrinsic,
+                    u,
+                    v
+                );
+                assert!(
+                    (riemann - extrinsic).abs() < tolerance,
+                    "surface {} riemann {} extrinsic {} at ({}, {})",
+                    index,
+                    riemann,
+                    extrinsic,
+                    u,
+                    v
+                );
+            }
+        }
+    }
+
+    // the symbols from the metric agree with the exact ones from the second derivatives
+    #[test]
+    fn christoffel_symbols_agree_with_extrinsic() {
+        for (index, surface) in surfaces().iter().enumerate() {
+            for (u, v) in uv::grid(5) {
+                let c = christoffel_symbols(surface.as_ref(), u, v);
+                let expected = surface.christoffel_symbols_at(u, v);
+                for k in 0..2 {
+                    for i in 0..2 {
+                        for j in 0..2 {
+                            let (a, b) = (c.get(k, i, j), expected.get(k, i, j));
+                            assert!(
+                                (a - b).abs() < 1e-6 * b.abs().max(1.0),
+                                "surface {} Γ^{}_{}{} {} expected {} at ({}, {})",
+                                index,
+                                k,
+                                i,
+                                j,
+                                a,
+                                b,
+                                u,
+                                v
+                            );
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
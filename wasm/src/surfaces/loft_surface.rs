@@ -0,0 +1,305 @@
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::quadrature::gauss_legendre;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+
+// nodes of the arc length table of each section
+const ARC_LENGTH_NODES: usize = 128;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoftContinuity {
+    // straight rulings between consecutive sections, C0 across them
+    Ruled,
+    // the natural cubic spline through the sections, C2 in v
+    Cubic,
+}
+
+// how the points of the sections are matched along u
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum LoftMatching {
+    // the same normalized parameter
+    Parameter,
+    // the same fraction of the arc length
+    ArcLength,
+}
+
+struct Section {
+    curve: Box<dyn Curve>,
+    // arc length from the start at the nodes u = i / ARC_LENGTH_NODES
+    lengths: Vec<f64>,
+}
+
+impl Section {
+    fn new(curve: Box<dyn Curve>, matching: LoftMatching) -> Self {
+        let lengths = match matching {
+            LoftMatching::Parameter => vec![],
+            LoftMatching::ArcLength => {
+                let size = 1.0 / ARC_LENGTH_NODES as f64;
+                let mut lengths = vec![0.0];
+                for i in 0..ARC_LENGTH_NODES {
+                    let l = Self::length_between(curve.as_ref(), i as f64 * size, size);
+                    lengths.push(lengths[i] + l);
+                }
+                lengths
+            }
+        };
+        Self { curve, lengths }
+    }
+
+    // ∫|c'| du over [u0, u0 + size]
+    fn length_between(curve: &dyn Curve, u0: f64, size: f64) -> f64 {
+        let (nodes, weights) = gauss_legendre(8);
+        nodes
+            .iter()
+            .zip(weights.iter())
+            .map(|(x, w)| w * curve.velocity_at(u0 + x * size).norm())
+            .sum::<f64>()
+            * size
+    }
+
+    fn arc_length(&self, u: f64) -> f64 {
+        let size = 1.0 / ARC_LENGTH_NODES as f64;
+        let i = ((u / size).floor().max(0.0) as usize).min(ARC_LENGTH_NODES - 1);
+        let u0 = i as f64 * size;
+        self.lengths[i] + Self::length_between(self.curve.as_ref(), u0, u - u0)
+    }
+
+    // the parameter at the fraction s of the arc length by Newton's method
+    fn parameter(&self, s: f64) -> f64 {
+        let total = self.lengths[ARC_LENGTH_NODES];
+        let target = s * total;
+        let i = self
+            .lengths
+            .iter()
+            .position(|l| *l > target)
+            .unwrap_or(ARC_LENGTH_NODES)
+            .clamp(1, ARC_LENGTH_NODES);
+        let (l0, l1) = (self.lengths[i - 1], self.lengths[i]);
+        let size = 1.0 / ARC_LENGTH_NODES as f64;
+        let t = if l1 > l0 {
+            (target - l0) / (l1 - l0)
+        } else {
+            0.0
+        };
+        let mut u = (i - 1) as f64 * size + t * size;
+        for _ in 0..8 {
+            let speed = self.curve.velocity_at(u).norm();
+            if speed == 0.0 {
+                break;
+            }
+            let du = (self.arc_length(u) - target) / speed;
+            u -= du;
+            if du.abs() < 1e-14 {
+                break;
+            }
+        }
+        u
+    }
+
+    // (c, c', c'') at the matched normalized u
+    fn at(&self, s: f64) -> (Point3f, Vector3f, Vector3f) {
+        if self.lengths.is_empty() {
+            return (
+                self.curve.point_at(s),
+                self.curve.velocity_at(s),
+                self.curve.acceleration_at(s),
+            );
+        }
+        let total = self.lengths[ARC_LENGTH_NODES];
+        let u = self.parameter(s);
+        let (p, d1, d2) = (
+            self.curve.point_at(u),
+            self.curve.velocity_at(u),
+            self.curve.acceleration_at(u),
+        );
+        let speed2 = d1.norm_squared();
+        if speed2 == 0.0 {
+            return (p, d1, d2);
+        }
+        // u' = L / |c'|, u'' = -u'^2 (c'・c'') / |c'|^2
+        let u1 = total / speed2.sqrt();
+        let u2 = -u1 * u1 * d1.dot(&d2) / speed2;
+        (p, d1 * u1, d2 * u1 * u1 + d1 * u2)
+    }
+}
+
+// interpolates the sections placed at v = 0, 1, ..., n - 1 in order
+// https://en.wikipedia.org/wiki/Loft_(3D)
+pub struct LoftSurface {
+    sections: Vec<Section>,
+    continuity: LoftContinuity,
+    // second derivatives of the natural cubic spline through the unit data of each section,
+    // moments[k][i] at the knot i
+    moments: Vec<Vec<f64>>,
+}
+
+impl LoftSurface {
+    // at least two sections
+    pub fn new(
+        sections: Vec<Box<dyn Curve>>,
+        continuity: LoftContinuity,
+        matching: LoftMatching,
+    ) -> Option<Self> {
+        if sections.len() < 2 {
+            return None;
+        }
+        let n = sections.len();
+        let moments = (0..n)
+            .map(|k| {
+                let data: Vec<f64> = (0..n).map(|i| if i == k { 1.0 } else { 0.0 }).collect();
+                natural_spline_moments(&data)
+            })
+            .collect();
+        Some(Self {
+            sections: sections
+                .into_iter()
+                .map(|c| Section::new(c, matching))
+                .collect(),
+            continuity,
+            moments,
+        })
+    }
+
+    pub fn section_count(&self) -> usize {
+        self.sections.len()
+    }
+
+    // the weights (w, w', w'') of each section at the native v
+    fn weights(&self, v: f64) -> Vec<(f64, f64, f64)> {
+        let n = self.sections.len();
+        let i = (v.floor().max(0.0) as usize).min(n - 2);
+        let t = v - i as f64;
+        (0..n)
+            .map(|k| match self.continuity {
+                LoftContinuity::Ruled => {
+                    if k == i {
+                        (1.0 - t, -1.0, 0.0)
+                    } else if k == i + 1 {
+                        (t, 1.0, 0.0)
+                    } else {
+                        (0.0, 0.0, 0.0)
+                    }
+                }
+                LoftContinuity::Cubic => {
+                    // on the unit interval [i, i + 1]
+                    let (y0, y1) = (
+                        if k == i { 1.0 } else { 0.0 },
+                        if k == i + 1 { 1.0 } else { 0.0 },
+                    );
+                    let (m0, m1) = (self.moments[k][i], self.moments[k][i + 1]);
+                    let s = 1.0 - t;
+                    (
+                        m0 * s * s * s / 6.0
+                            + m1 * t * t * t / 6.0
+                            + (y0 - m0 / 6.0) * s
+                            + (y1 - m1 / 6.0) * t,
+                        -m0 * s * s * 0.5 + m1 * t * t * 0.5 - (y0 - m0 / 6.0) + (y1 - m1 / 6.0),
+                        m0 * s + m1 * t,
+                    )
+                }
+            })
+            .collect()
+    }
+
+    // Σ w(v) c(u) with the requested derivative orders of the weights and the sections
+    fn blend(&self, u: f64, v: f64, wv: usize, cu: usize) -> Vector3f {
+        let v = self.v_domain().map(v);
+        self.weights(v)
+            .iter()
+            .zip(self.sections.iter())
+            .filter(|((w, w1, w2), _)| *w != 0.0 || *w1 != 0.0 || *w2 != 0.0)
+            .map(|(w, section)| {
+                let w = match wv {
+                    0 => w.0,
+                    1 => w.1,
+                    _ => w.2,
+                };
+                let (p, d1, d2) = section.at(u);
+                let c = match cu {
+                    0 => p.coords,
+                    1 => d1,
+                    _ => d2,
+                };
+                c * w
+            })
+            .sum()
+    }
+}
+
+// the second derivatives of the natural cubic spline through the data at unit spaced knots,
+// M_{i-1} + 4 M_i + M_{i+1} = 6 (y_{i-1} - 2 y_i + y_{i+1}), M_0 = M_{n-1} = 0
+fn natural_spline_moments(data: &[f64]) -> Vec<f64> {
+    let n = data.len();
+    let mut moments = vec![0.0; n];
+    if n < 3 {
+        return moments;
+    }
+    // Thomas algorithm on the interior knots
+    let m = n - 2;
+    let mut c = vec![0.0; m];
+    let mut d = vec![0.0; m];
+    for j in 0..m {
+        let i = j + 1;
+        let rhs = 6.0 * (data[i - 1] - 2.0 * data[i] + data[i + 1]);
+        if j == 0 {
+            c[j] = 1.0 / 4.0;
+            d[j] = rhs / 4.0;
+        } else {
+            let denominator = 4.0 - c[j - 1];
+            c[j] = 1.0 / denominator;
+            d[j] = (rhs - d[j - 1]) / denominator;
+        }
+    }
+    for j in (0..m).rev() {
+        let next = if j + 1 < m { moments[j + 2] } else { 0.0 };
+        moments[j + 1] = d[j] - c[j] * next;
+    }
+    moments
+}
+
+impl Surface for LoftSurface {
+    // periodic when every section is closed
+    fn u_domain(&self) -> Domain {
+        let closed = self.sections.iter().all(|s| {
+            let c = &s.curve;
+            (c.point_at(0.0) - c.point_at(1.0)).norm() <= 1e-9 * c.velocity_at(0.0).norm().max(1.0)
+        });
+        if closed {
+            Domain::periodic(0.0, 1.0)
+        } else {
+            Domain::unit()
+        }
+    }
+
+    // the index of the sections
+    fn v_domain(&self) -> Domain {
+        Domain::new(0.0, (self.sections.len() - 1) as f64)
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.blend(u, v, 0, 0))
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.blend(u, v, 0, 1)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.blend(u, v, 1, 0) * self.v_domain().length()
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.blend(u, v, 0, 2)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.blend(u, v, 1, 1) * self.v_domain().length()
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        let l = self.v_domain().length();
+        self.blend(u, v, 2, 0) * l * l
+    }
+}
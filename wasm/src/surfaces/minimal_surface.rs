@@ -0,0 +1,356 @@
+use crate::domain::Domain;
+use crate::expression::{ComplexJet, Expression};
+use crate::quadrature::gauss_legendre;
+use crate::surfaces::surface::Surface;
+use crate::surfaces::uv;
+use crate::types::*;
+use core::f64::consts::{FRAC_PI_2, TAU};
+use std::collections::BTreeMap;
+
+// (f, g) with their derivatives at w
+pub type WeierstrassFunction = dyn Fn(Complexf) -> (ComplexJet, ComplexJet);
+
+// the Weierstrass data in the parameter w = u + i v
+pub enum WeierstrassData {
+    Function(Box<WeierstrassFunction>),
+    // formulas of w and the imaginary unit i
+    Expression {
+        f: Box<Expression>,
+        g: Box<Expression>,
+    },
+}
+
+// the Weierstrass-Enneper representation
+// X(w) = Re ∫ (f (1 - g^2) / 2, i f (1 + g^2) / 2, f g) dw,
+// conformal and of zero mean curvature wherever f is holomorphic, g meromorphic and f g^2 holomorphic,
+// the integral runs along the straight segment from the center of the domain
+// https://en.wikipedia.org/wiki/Weierstrass%E2%80%93Enneper_parameterization
+pub struct MinimalSurface {
+    data: WeierstrassData,
+    u_domain: Domain,
+    v_domain: Domain,
+    // f is multiplied by e^(i θ), bending the surface isometrically through its associate family
+    angle: f64,
+    // subtracted from the integral to center the surface at the origin
+    center: Vector3f,
+}
+
+impl MinimalSurface {
+    pub fn new(data: WeierstrassData, u: Domain, v: Domain) -> Self {
+        let mut surface = Self {
+            data,
+            u_domain: u,
+            v_domain: v,
+            angle: 0.0,
+            center: Vector3f::zeros(),
+        };
+        surface.update();
+        surface
+    }
+
+    pub fn function(
+        data: impl Fn(Complexf) -> (ComplexJet, ComplexJet) + 'static,
+        u: Domain,
+        v: Domain,
+    ) -> Self {
+        Self::new(WeierstrassData::Function(Box::new(data)), u, v)
+    }
+
+    // f(w) and g(w) as complex formulas, e.g. "exp(-w)" and "exp(w)" for the catenoid,
+    // the domain is made periodic where the surface closes up across it
+    pub fn try_expression(f: &str, g: &str, u: Domain, v: Domain) -> Result<Self, fasteval::Error> {
+        let data = WeierstrassData::Expression {
+            f: Box::new(Expression::parse(f)?),
+            g: Box::new(Expression::parse(g)?),
+        };
+        let mut surface = Self {
+            data,
+            u_domain: u,
+            v_domain: v,
+            angle: 0.0,
+            center: Vector3f::zeros(),
+        };
+        surface.try_data_at(surface.parameter(0.5, 0.5))?;
+        surface.update();
+        Ok(surface)
+    }
+
+    // the member of the associate family at the angle θ, e.g. the helicoid at π / 2 from the catenoid
+    pub fn associate(self, angle: f64) -> Self {
+        let mut surface = Self { angle, ..self };
+        let (u, v) = (surface.u_domain.clone(), surface.v_domain.clone());
+        surface.u_domain = Domain::new(u.start(), u.end());
+        surface.v_domain = Domain::new(v.start(), v.end());
+        surface.update();
+        surface
+    }
+
+    // f = 1, g = w
+    pub fn enneper(r: f64) -> Self {
+        Self::function(
+            |w| {
+                (
+                    ComplexJet::constant(Complexf::new(1.0, 0.0)),
+                    ComplexJet::w(w),
+                )
+            },
+            Domain::new(-r, r),
+            Domain::new(-r, r),
+        )
+    }
+
+    // f = 1 / ζ^2, g = ζ with ζ = e^w, so f dζ = e^(-w) dw
+    pub fn catenoid(height: f64) -> Self {
+        Self::function(
+            |w| {
+                let w = ComplexJet::w(w);
+                ((-w).exp(), w.exp())
+            },
+            Domain::new(-height, height),
+            Domain::new(0.0, TAU),
+        )
+    }
+
+    // the conjugate of the catenoid, isometric to it
+    pub fn helicoid(height: f64) -> Self {
+        Self::catenoid(height).associate(FRAC_PI_2)
+    }
+
+    // Scherk's doubly periodic surface, f = 4 / (1 - w^4), g = w inside the unit disk
+    pub fn scherk(r: f64) -> Self {
+        Self::function(
+            |w| {
+                let w = ComplexJet::w(w);
+                let one = ComplexJet::constant(Complexf::new(1.0, 0.0));
+                let four = ComplexJet::constant(Complexf::new(4.0, 0.0));
+                let w2 = w * w;
+                (four / (one - w2 * w2), w)
+            },
+            Domain::new(-r, r),
+            Domain::new(-r, r),
+        )
+    }
+
+    // the non-orientable Henneberg surface, f = 2 (1 - 1 / ζ^4), g = ζ with ζ = e^w,
+    // singular on the unit circle u = 0
+    pub fn henneberg(u0: f64, u1: f64) -> Self {
+        Self::function(
+            |w| {
+                let w = ComplexJet::w(w);
+                let two = ComplexJet::constant(Complexf::new(2.0, 0.0));
+                let three = ComplexJet::constant(Complexf::new(3.0, 0.0));
+                ((w.exp() - (-w * three).exp()) * two, w.exp())
+            },
+            Domain::new(u0, u1),
+            Domain::new(0.0, TAU),
+        )
+    }
+
+    // Richmond's surface with one planar end, f = 1 / ζ^2, g = ζ^2 with ζ = e^w
+    pub fn richmond(u0: f64, u1: f64) -> Self {
+        Self::function(
+            |w| {
+                let w = ComplexJet::w(w);
+                ((-w).exp(), (w + w).exp())
+            },
+            Domain::new(u0, u1),
+            Domain::new(0.0, TAU),
+        )
+    }
+
+    fn try_data_at(&self, w: Complexf) -> Result<(ComplexJet, ComplexJet), fasteval::Error> {
+        let (f, g) = match &self.data {
+            WeierstrassData::Function(data) => data(w),
+            WeierstrassData::Expression { f, g } => {
+                let mut variables: BTreeMap<String, ComplexJet> = BTreeMap::new();
+                variables.insert(String::from("w"), ComplexJet::w(w));
+                variables.insert(
+                    String::from("i"),
+                    ComplexJet::constant(Complexf::new(0.0, 1.0)),
+                );
+                (f.complex(&variables)?, g.complex(&variables)?)
+            }
+        };
+        let rotation = ComplexJet::constant(Complexf::new(self.angle.cos(), self.angle.sin()));
+        Ok((f * rotation, g))
+    }
+
+    // Φ = (f (1 - g^2) / 2, i f (1 + g^2) / 2, f g) and Φ' at w
+    fn phi(&self, w: Complexf) -> ([Complexf; 3], [Complexf; 3]) {
+        let nan = ComplexJet::constant(Complexf::new(f64::NAN, f64::NAN));
+        let (f, g) = self.try_data_at(w).unwrap_or((nan, nan));
+        let one = ComplexJet::constant(Complexf::new(1.0, 0.0));
+        let half = ComplexJet::constant(Complexf::new(0.5, 0.0));
+        let i_half = ComplexJet::constant(Complexf::new(0.0, 0.5));
+        let g2 = g * g;
+        let phi = [f * (one - g2) * half, f * (one + g2) * i_half, f * g];
+        (
+            [phi[0].value, phi[1].value, phi[2].value],
+            [phi[0].dw, phi[1].dw, phi[2].dw],
+        )
+    }
+
+    // w at normalized (u, v)
+    fn parameter(&self, u: f64, v: f64) -> Complexf {
+        Complexf::new(self.u_domain.map(u), self.v_domain.map(v))
+    }
+
+    fn real(z: &[Complexf; 3]) -> Vector3f {
+        Vector3f::new(z[0].re, z[1].re, z[2].re)
+    }
+
+    fn imaginary(z: &[Complexf; 3]) -> Vector3f {
+        Vector3f::new(z[0].im, z[1].im, z[2].im)
+    }
+
+    // Re ∫ Φ dw from the center of the domain by Gauss-Legendre on cells of at most half a unit
+    fn integrate(&self, w: Complexf) -> Vector3f {
+        let w0 = self.parameter(0.5, 0.5);
+        let delta = w - w0;
+        let cells = ((delta.norm_sqr().sqrt() * 2.0).ceil() as usize).max(1);
+        let (nodes, weights) = gauss_legendre(16);
+        let mut sum = [Complexf::new(0.0, 0.0); 3];
+        for i in 0..cells {
+            for (x, weight) in nodes.iter().zip(weights.iter()) {
+                let t = (i as f64 + x) / cells as f64;
+                let (phi, _) = self.phi(w0 + delta * t);
+                for (s, p) in sum.iter_mut().zip(phi.iter()) {
+                    *s += p * *weight;
+                }
+            }
+        }
+        let scale = delta / cells as f64;
+        Self::real(&[sum[0] * scale, sum[1] * scale, sum[2] * scale])
+    }
+
+    // centers the surface and finds its periods
+    fn update(&mut self) {
+        self.center = Vector3f::zeros();
+        let samples = uv::grid(8);
+        let sum: Vector3f = samples
+            .iter()
+            .map(|(u, v)| self.point_at(*u, *v).coords)
+            .sum();
+        self.center = sum / samples.len() as f64;
+        self.detect_periods();
+    }
+
+    // a domain is made periodic when the surface closes up across it
+    fn detect_periods(&mut self) {
+        let count = 16;
+        let closes = |surface: &Self, sides: fn(f64) -> (Point2f, Point2f)| {
+            let samples: Vec<(Point3f, Point3f)> = (0..=count)
+                .map(|i| {
+                    let (a, b) = sides(i as f64 / count as f64);
+                    (surface.point_at(a.x, a.y), surface.point_at(b.x, b.y))
+                })
+                .collect();
+            let size = samples
+                .iter()
+                .map(|(a, _)| (a - samples[0].0).norm())
+                .fold(1.0, f64::max);
+            samples.iter().all(|(a, b)| (a - b).norm() <= 1e-9 * size)
+        };
+        let (u, v) = (self.u_domain.clone(), self.v_domain.clone());
+        if closes(self, |s| (Point2f::new(0.0, s), Point2f::new(1.0, s))) {
+            self.u_domain = Domain::periodic(u.start(), u.end());
+        }
+        if closes(self, |s| (Point2f::new(s, 0.0), Point2f::new(s, 1.0))) {
+            self.v_domain = Domain::periodic(v.start(), v.end());
+        }
+    }
+}
+
+impl Default for MinimalSurface {
+    fn default() -> Self {
+        MinimalSurface::enneper(1.5)
+    }
+}
+
+impl Surface for MinimalSurface {
+    fn u_domain(&self) -> Domain {
+        self.u_domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.v_domain.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.integrate(self.parameter(u, v)) - self.center)
+    }
+
+    // X_u = Re Φ, X_v = -Im Φ
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        let (phi, _) = self.phi(self.parameter(u, v));
+        Self::real(&phi) * self.u_domain.length()
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (phi, _) = self.phi(self.parameter(u, v));
+        -Self::imaginary(&phi) * self.v_domain.length()
+    }
+
+    // X_uu = Re Φ', X_uv = -Im Φ', X_vv = -Re Φ'
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        let (_, dphi) = self.phi(self.parameter(u, v));
+        let l = self.u_domain.length();
+        Self::real(&dphi) * l * l
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (_, dphi) = self.phi(self.parameter(u, v));
+        -Self::imaginary(&dphi) * self.u_domain.length() * self.v_domain.length()
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (_, dphi) = self.phi(self.parameter(u, v));
+        let l = self.v_domain.length();
+        -Self::real(&dphi) * l * l
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // H vanishes everywhere, relative to the principal curvatures
+    fn assert_minimal(surface: &MinimalSurface) {
+        for (u, v) in uv::grid(6) {
+            let c = surface.curvature_at(u, v);
+            assert!(
+                c.mean.abs() < 1e-8 * c.k1.abs().max(1.0),
+                "H {} k1 {} at ({}, {})",
+                c.mean,
+                c.k1,
+                u,
+                v
+            );
+        }
+    }
+
+    #[test]
+    fn classical_minimal_surfaces() {
+        assert_minimal(&MinimalSurface::enneper(1.5));
+        assert_minimal(&MinimalSurface::catenoid(1.5));
+        assert_minimal(&MinimalSurface::helicoid(1.5));
+        assert_minimal(&MinimalSurface::scherk(0.8));
+        assert_minimal(&MinimalSurface::henneberg(0.3, 1.0));
+        assert_minimal(&MinimalSurface::richmond(0.3, 1.0));
+    }
+
+    #[test]
+    fn associate_family() {
+        assert_minimal(&MinimalSurface::catenoid(1.5).associate(0.7));
+        assert_minimal(&MinimalSurface::enneper(1.5).associate(2.0));
+    }
+
+    #[test]
+    fn weierstrass_expressions() {
+        let (u, v) = (Domain::new(-1.0, 1.0), Domain::new(-1.0, 1.0));
+        assert_minimal(
+            &MinimalSurface::try_expression("exp(-w)", "exp(w)", u.clone(), v.clone()).unwrap(),
+        );
+        assert_minimal(&MinimalSurface::try_expression("1", "w^2", u, v).unwrap());
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::Domain;
 use crate::plane::Plane;
 use crate::surfaces::surface::Surface;
 use crate::types::Point3f;
@@ -28,6 +29,15 @@ impl Mobius {
 }
 
 impl Surface for Mobius {
+    // the seam at u = 0, 1 flips v, so it is not treated as periodic
+    fn u_domain(&self) -> Domain {
+        Domain::new(-PI, PI)
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::new(-0.5, 0.5)
+    }
+
     fn point_at(&self, u: f64, v: f64) -> Point3f {
         let (theta, phi) = self.theta_phi(u, v);
         let dx = theta.cos() + phi * (theta * 0.5).sin() * theta.cos(); // cos(theta) * phi * sin(theta / 2) * cos(theta)
@@ -38,10 +48,12 @@ impl Surface for Mobius {
 
     fn du_at(&self, u: f64, v: f64) -> Vector3f {
         let (theta, phi) = self.theta_phi(u, v);
-        let dx = -theta.sin() + -phi * 0.5 * (theta * 0.5).cos() * theta.sin();
-        let dy = theta.cos() + phi * 0.5 * (theta * 0.5).cos() * theta.cos();
-        let dz = -phi * 0.5 * (theta * 0.5).sin();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (st, ct) = (theta.sin(), theta.cos());
+        let (sh, ch) = ((theta * 0.5).sin(), (theta * 0.5).cos());
+        let dx = -st + phi * (0.5 * ch * ct - sh * st);
+        let dy = ct + phi * (0.5 * ch * st + sh * ct);
+        let dz = -phi * 0.5 * sh;
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * TAU
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -54,25 +66,25 @@ impl Surface for Mobius {
 
     fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
         let (theta, phi) = self.theta_phi(u, v);
-        let dx = -theta.sin() + -phi * 0.5 * (theta * 0.5).cos() * theta.sin();
-        let dy = theta.cos() + phi * 0.5 * (theta * 0.5).cos() * theta.cos();
-        let dz = -phi * 0.5 * (theta * 0.5).sin();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (st, ct) = (theta.sin(), theta.cos());
+        let (sh, ch) = ((theta * 0.5).sin(), (theta * 0.5).cos());
+        let dx = -ct - phi * (1.25 * sh * ct + ch * st);
+        let dy = -st - phi * (1.25 * sh * st - ch * ct);
+        let dz = -phi * 0.25 * ch;
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * TAU * TAU
     }
 
-    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
-        let (theta, _) = self.theta_phi(u, v);
-        let dx = (theta * 0.5).sin() * theta.cos();
-        let dy = (theta * 0.5).sin() * theta.sin();
-        let dz = (theta * 0.5).cos();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+    fn dvdv_at(&self, _u: f64, _v: f64) -> Vector3f {
+        Vector3f::new(0.0, 0.0, 0.0)
     }
 
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
-        let (theta, phi) = self.theta_phi(u, v);
-        let dx = -theta.sin() + -phi * 0.5 * (theta * 0.5).cos() * theta.sin();
-        let dy = theta.cos() + phi * 0.5 * (theta * 0.5).cos() * theta.cos();
-        let dz = -phi * 0.5 * (theta * 0.5).sin();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (theta, _) = self.theta_phi(u, v);
+        let (st, ct) = (theta.sin(), theta.cos());
+        let (sh, ch) = ((theta * 0.5).sin(), (theta * 0.5).cos());
+        let dx = 0.5 * ch * ct - sh * st;
+        let dy = 0.5 * ch * st + sh * ct;
+        let dz = -0.5 * sh;
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * TAU
     }
 }
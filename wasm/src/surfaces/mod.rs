@@ -1,10 +1,32 @@
+pub mod bezier_patch;
+pub mod classical_surface;
+pub mod coons_patch;
+pub mod curvature_line;
 pub mod cylinder;
 pub mod elliptic_paraboloid;
+pub mod expression_surface;
+pub mod fundamental_form;
+pub mod gauss_bonnet;
+pub mod geodesic;
+pub mod gregory_patch;
 pub mod helicoid;
 pub mod hyperbolic_paraboloid;
+pub mod implicit_surface;
+pub mod intrinsic;
+pub mod loft_surface;
+pub mod minimal_surface;
 pub mod mobius;
+pub mod nurbs_surface;
+pub mod parallel_transport;
+pub mod revolution_surface;
+pub mod ruled_surface;
+pub mod sine_gordon_surface;
 pub mod single_leaf_paraboloid;
 pub mod sphere;
 pub mod surface;
 pub mod surface_curvature;
+pub mod surface_curve;
+pub mod sweep_surface;
 pub mod torus;
+pub mod umbilic;
+pub mod uv;
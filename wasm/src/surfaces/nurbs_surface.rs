@@ -0,0 +1,436 @@
+use crate::bspline::{basis_derivatives, find_span, homogeneous, insert_knot, is_valid};
+use crate::curves::nurbs_curve::NurbsCurve;
+use crate::domain::Domain;
+use crate::plane::Plane;
+use crate::surfaces::surface::Surface;
+use crate::types::*;
+use core::f64::consts::{PI, TAU};
+
+// rational tensor product B-spline surface,
+// S(u, v) = Σ N_i(u) N_j(v) w_ij P_ij / Σ N_i(u) N_j(v) w_ij
+// control_points[i][j] runs along u with i and along v with j
+#[derive(Clone)]
+pub struct NurbsSurface {
+    u_degree: usize,
+    v_degree: usize,
+    u_knots: Vec<f64>,
+    v_knots: Vec<f64>,
+    control_points: Vec<Vec<Point3f>>,
+    weights: Vec<Vec<f64>>,
+}
+
+// the homogeneous derivatives ∂^(k+l) (w S, w) / ∂u^k ∂v^l for k + l <= 2
+type HomogeneousDerivatives = [[Vector4f; 3]; 3];
+
+impl NurbsSurface {
+    // None unless the knots fit the degrees and the rectangular net, and the weights are positive
+    pub fn new(
+        u_degree: usize,
+        v_degree: usize,
+        u_knots: Vec<f64>,
+        v_knots: Vec<f64>,
+        control_points: Vec<Vec<Point3f>>,
+        weights: Vec<Vec<f64>>,
+    ) -> Option<Self> {
+        let rows = control_points.len();
+        let columns = control_points.first().map_or(0, |r| r.len());
+        let rectangular = control_points.iter().all(|r| r.len() == columns)
+            && weights.len() == rows
+            && weights.iter().all(|r| r.len() == columns);
+        if !rectangular
+            || !is_valid(u_degree, &u_knots, rows)
+            || !is_valid(v_degree, &v_knots, columns)
+            || weights.iter().flatten().any(|w| *w <= 0.0)
+        {
+            return None;
+        }
+        Some(Self {
+            u_degree,
+            v_degree,
+            u_knots,
+            v_knots,
+            control_points,
+            weights,
+        })
+    }
+
+    // the profile revolved by the angle around the normal of the plane,
+    // turning its x axis toward the y axis, u runs along the profile and v around the axis (A8.1)
+    pub fn revolve(profile: &NurbsCurve, axis: &Plane, angle: f64) -> Self {
+        let unit = Plane::new(
+            Point3f::origin(),
+            Vector3f::x(),
+            Vector3f::y(),
+            Vector3f::z(),
+        );
+        let arc = NurbsCurve::arc(&unit, 1.0, angle);
+        let (control_points, weights) = profile
+            .control_points()
+            .iter()
+            .zip(profile.weights().iter())
+            .map(|(p, w)| {
+                let center = axis.origin + axis.normal * (p - axis.origin).dot(&axis.normal);
+                let radial = p - center;
+                let r = radial.norm();
+                let (x, y) = if r > 0.0 {
+                    let x = radial / r;
+                    (x, axis.normal.cross(&x))
+                } else {
+                    (axis.x_axis, axis.y_axis)
+                };
+                arc.control_points()
+                    .iter()
+                    .zip(arc.weights().iter())
+                    .map(|(q, aw)| (center + (x * q.x + y * q.y) * r, w * aw))
+                    .unzip::<_, _, Vec<_>, Vec<_>>()
+            })
+            .unzip();
+        Self {
+            u_degree: profile.degree(),
+            v_degree: arc.degree(),
+            u_knots: profile.knots().to_vec(),
+            v_knots: arc.knots().to_vec(),
+            control_points,
+            weights,
+        }
+    }
+
+    // the exact sphere, u runs from the north pole along the normal of the plane to the south pole,
+    // the poles are left out by delta like Sphere
+    pub fn sphere(plane: &Plane, radius: f64) -> Self {
+        let delta: f64 = 1e-8;
+        let (s, c) = delta.sin_cos();
+        let meridian = Plane::new(
+            plane.origin,
+            plane.normal * c + plane.x_axis * s,
+            plane.x_axis * c - plane.normal * s,
+            plane.normal.cross(&plane.x_axis),
+        );
+        Self::revolve(
+            &NurbsCurve::arc(&meridian, radius, PI - delta * 2.0),
+            plane,
+            TAU,
+        )
+    }
+
+    // the exact torus of the tube radius a around the center circle of the radius b
+    pub fn torus(plane: &Plane, a: f64, b: f64) -> Self {
+        let tube = Plane::new(
+            plane.origin + plane.x_axis * b,
+            plane.x_axis,
+            plane.normal,
+            plane.x_axis.cross(&plane.normal),
+        );
+        Self::revolve(&NurbsCurve::circle(&tube, a), plane, TAU)
+    }
+
+    // the exact cylinder of the radius along the normal of the plane
+    pub fn cylinder(plane: &Plane, radius: f64, height: f64) -> Self {
+        let base = plane.origin + plane.x_axis * radius;
+        let line = NurbsCurve::new(
+            1,
+            vec![0.0, 0.0, 1.0, 1.0],
+            vec![base, base + plane.normal * height],
+            vec![1.0, 1.0],
+        )
+        .unwrap();
+        Self::revolve(&line, plane, TAU)
+    }
+
+    pub fn degrees(&self) -> (usize, usize) {
+        (self.u_degree, self.v_degree)
+    }
+
+    pub fn u_knots(&self) -> &[f64] {
+        &self.u_knots
+    }
+
+    pub fn v_knots(&self) -> &[f64] {
+        &self.v_knots
+    }
+
+    pub fn control_points(&self) -> &[Vec<Point3f>] {
+        &self.control_points
+    }
+
+    pub fn weights(&self) -> &[Vec<f64>] {
+        &self.weights
+    }
+
+    fn homogeneous_at(&self, i: usize, j: usize) -> Vector4f {
+        homogeneous(&self.control_points[i][j], self.weights[i][j])
+    }
+
+    fn knot_domain(degree: usize, knots: &[f64]) -> Domain {
+        Domain::new(knots[degree], knots[knots.len() - degree - 1])
+    }
+
+    // the native knot domains
+    fn domains(&self) -> (Domain, Domain) {
+        (
+            Self::knot_domain(self.u_degree, &self.u_knots),
+            Self::knot_domain(self.v_degree, &self.v_knots),
+        )
+    }
+
+    // at native (u, v) (A3.6)
+    fn homogeneous_derivatives(&self, u: f64, v: f64) -> HomogeneousDerivatives {
+        let (p, q) = (self.u_degree, self.v_degree);
+        let u_span = find_span(p, &self.u_knots, self.control_points.len(), u);
+        let v_span = find_span(q, &self.v_knots, self.control_points[0].len(), v);
+        let nu = basis_derivatives(u_span, u, p, 2, &self.u_knots);
+        let nv = basis_derivatives(v_span, v, q, 2, &self.v_knots);
+        let mut ders = [[Vector4f::zeros(); 3]; 3];
+        for (k, row) in ders.iter_mut().enumerate() {
+            for (l, d) in row.iter_mut().enumerate().take(3 - k) {
+                for (a, na) in nu[k].iter().enumerate() {
+                    for (b, nb) in nv[l].iter().enumerate() {
+                        let w = self.homogeneous_at(u_span - p + a, v_span - q + b);
+                        *d += w * (na * nb);
+                    }
+                }
+            }
+        }
+        ders
+    }
+
+    // S and its partial derivatives up to second order in normalized (u, v),
+    // by the quotient rule on the homogeneous derivatives (A4.4)
+    fn derivatives(&self, u: f64, v: f64) -> [[Vector3f; 3]; 3] {
+        let (du, dv) = self.domains();
+        let a = self.homogeneous_derivatives(du.map(u), dv.map(v));
+        let w = |k: usize, l: usize| a[k][l].w;
+        let mut s = [[Vector3f::zeros(); 3]; 3];
+        s[0][0] = a[0][0].xyz() / w(0, 0);
+        s[1][0] = (a[1][0].xyz() - s[0][0] * w(1, 0)) / w(0, 0);
+        s[0][1] = (a[0][1].xyz() - s[0][0] * w(0, 1)) / w(0, 0);
+        s[2][0] = (a[2][0].xyz() - s[1][0] * 2.0 * w(1, 0) - s[0][0] * w(2, 0)) / w(0, 0);
+        s[0][2] = (a[0][2].xyz() - s[0][1] * 2.0 * w(0, 1) - s[0][0] * w(0, 2)) / w(0, 0);
+        s[1][1] =
+            (a[1][1].xyz() - s[0][0] * w(1, 1) - s[1][0] * w(0, 1) - s[0][1] * w(1, 0)) / w(0, 0);
+        let (lu, lv) = (du.length(), dv.length());
+        for (k, row) in s.iter_mut().enumerate() {
+            for (l, d) in row.iter_mut().enumerate() {
+                *d *= lu.powi(k as i32) * lv.powi(l as i32);
+            }
+        }
+        s
+    }
+
+    // the same surface with the native knot t inserted once into the u knots
+    pub fn insert_u_knot(&self, t: f64) -> Self {
+        let columns = self.control_points[0].len();
+        let mut knots = self.u_knots.clone();
+        let mut net: Vec<Vec<Vector4f>> = vec![];
+        for j in 0..columns {
+            let column: Vec<Vector4f> = (0..self.control_points.len())
+                .map(|i| self.homogeneous_at(i, j))
+                .collect();
+            let (k, inserted) = insert_knot(self.u_degree, &self.u_knots, &column, t);
+            knots = k;
+            net.push(inserted);
+        }
+        // transpose back into rows along u
+        let rows = (0..net[0].len())
+            .map(|i| net.iter().map(|column| column[i]).collect())
+            .collect();
+        self.with_net(knots, self.v_knots.clone(), rows)
+    }
+
+    // the same surface with the native knot t inserted once into the v knots
+    pub fn insert_v_knot(&self, t: f64) -> Self {
+        let mut knots = self.v_knots.clone();
+        let rows = (0..self.control_points.len())
+            .map(|i| {
+                let row: Vec<Vector4f> = (0..self.control_points[i].len())
+                    .map(|j| self.homogeneous_at(i, j))
+                    .collect();
+                let (k, inserted) = insert_knot(self.v_degree, &self.v_knots, &row, t);
+                knots = k;
+                inserted
+            })
+            .collect();
+        self.with_net(self.u_knots.clone(), knots, rows)
+    }
+
+    fn with_net(&self, u_knots: Vec<f64>, v_knots: Vec<f64>, net: Vec<Vec<Vector4f>>) -> Self {
+        Self {
+            u_degree: self.u_degree,
+            v_degree: self.v_degree,
+            u_knots,
+            v_knots,
+            control_points: net
+                .iter()
+                .map(|r| r.iter().map(|p| Point3f::from(p.xyz() / p.w)).collect())
+                .collect(),
+            weights: net
+                .iter()
+                .map(|r| r.iter().map(|p| p.w).collect())
+                .collect(),
+        }
+    }
+
+    // the curve of constant normalized u running along v, exactly as a NURBS curve
+    pub fn u_isocurve(&self, u: f64) -> NurbsCurve {
+        let p = self.u_degree;
+        let t = self.domains().0.map(u);
+        let span = find_span(p, &self.u_knots, self.control_points.len(), t);
+        let n = basis_derivatives(span, t, p, 0, &self.u_knots);
+        let points: Vec<Vector4f> = (0..self.control_points[0].len())
+            .map(|j| {
+                (0..=p)
+                    .map(|a| self.homogeneous_at(span - p + a, j) * n[0][a])
+                    .sum()
+            })
+            .collect();
+        NurbsCurve::from_homogeneous(self.v_degree, self.v_knots.clone(), &points)
+    }
+
+    // the curve of constant normalized v running along u, exactly as a NURBS curve
+    pub fn v_isocurve(&self, v: f64) -> NurbsCurve {
+        let q = self.v_degree;
+        let t = self.domains().1.map(v);
+        let span = find_span(q, &self.v_knots, self.control_points[0].len(), t);
+        let n = basis_derivatives(span, t, q, 0, &self.v_knots);
+        let points: Vec<Vector4f> = (0..self.control_points.len())
+            .map(|i| {
+                (0..=q)
+                    .map(|b| self.homogeneous_at(i, span - q + b) * n[0][b])
+                    .sum()
+            })
+            .collect();
+        NurbsCurve::from_homogeneous(self.u_degree, self.u_knots.clone(), &points)
+    }
+}
+
+impl Default for NurbsSurface {
+    fn default() -> Self {
+        NurbsSurface::sphere(&Plane::default(), 2.0)
+    }
+}
+
+// boundary rows of control points coincide
+fn closes(a: &[Point3f], b: &[Point3f]) -> bool {
+    a.iter()
+        .zip(b.iter())
+        .all(|(p, q)| (p - q).norm() <= 1e-9 * p.coords.norm().max(1.0))
+}
+
+impl Surface for NurbsSurface {
+    // periodic when the first and last rows of the net coincide
+    fn u_domain(&self) -> Domain {
+        let d = self.domains().0;
+        let net = &self.control_points;
+        if closes(&net[0], &net[net.len() - 1]) {
+            Domain::periodic(d.start(), d.end())
+        } else {
+            d
+        }
+    }
+
+    // periodic when the first and last columns of the net coincide
+    fn v_domain(&self) -> Domain {
+        let d = self.domains().1;
+        let first: Vec<Point3f> = self.control_points.iter().map(|r| r[0]).collect();
+        let last: Vec<Point3f> = self.control_points.iter().map(|r| r[r.len() - 1]).collect();
+        if closes(&first, &last) {
+            Domain::periodic(d.start(), d.end())
+        } else {
+            d
+        }
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.derivatives(u, v)[0][0])
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivatives(u, v)[1][0]
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivatives(u, v)[0][1]
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivatives(u, v)[2][0]
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivatives(u, v)[1][1]
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.derivatives(u, v)[0][2]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::curve::Curve;
+    use crate::surfaces::uv;
+
+    #[test]
+    fn rational_sphere_is_exact() {
+        let radius = 2.0;
+        let sphere = NurbsSurface::sphere(&Plane::default(), radius);
+        for (u, v) in uv::grid(7) {
+            let r = sphere.point_at(u, v).coords.norm();
+            assert!((r - radius).abs() < 1e-12, "radius {} at ({}, {})", r, u, v);
+            let k = sphere.curvature_at(u, v).gaussian;
+            assert!(
+                (k - 1.0 / (radius * radius)).abs() < 1e-9,
+                "K {} at ({}, {})",
+                k,
+                u,
+                v
+            );
+        }
+    }
+
+    // the point and the derivatives up to second order agree
+    fn assert_same(a: &NurbsSurface, b: &NurbsSurface) {
+        for (u, v) in uv::grid(7) {
+            assert!((a.point_at(u, v) - b.point_at(u, v)).norm() < 1e-12);
+            for (da, db) in [
+                (a.du_at(u, v), b.du_at(u, v)),
+                (a.dv_at(u, v), b.dv_at(u, v)),
+                (a.dudu_at(u, v), b.dudu_at(u, v)),
+                (a.dudv_at(u, v), b.dudv_at(u, v)),
+                (a.dvdv_at(u, v), b.dvdv_at(u, v)),
+            ] {
+                assert!(
+                    (da - db).norm() < 1e-9 * db.norm().max(1.0),
+                    "at ({}, {})",
+                    u,
+                    v
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn knot_insertion_keeps_the_surface() {
+        let torus = NurbsSurface::torus(&Plane::default(), 1.0, 3.0);
+        let (du, dv) = torus.domains();
+        for t in [0.3, 0.55] {
+            let u = torus.insert_u_knot(du.map(t));
+            assert_eq!(u.u_knots().len(), torus.u_knots().len() + 1);
+            assert_same(&u, &torus);
+            let v = torus.insert_v_knot(dv.map(t));
+            assert_eq!(v.v_knots().len(), torus.v_knots().len() + 1);
+            assert_same(&v, &torus);
+        }
+    }
+
+    #[test]
+    fn isocurves_lie_on_the_surface() {
+        let torus = NurbsSurface::torus(&Plane::default(), 1.0, 3.0);
+        for (u, v) in uv::grid(7) {
+            let p = torus.point_at(u, v);
+            assert!((torus.u_isocurve(u).point_at(v) - p).norm() < 1e-12);
+            assert!((torus.v_isocurve(v).point_at(u) - p).norm() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use crate::curves::curve::Curve;
+use crate::surfaces::surface::Surface;
+use crate::types::*;
+
+// a tangent vector field along a path on a surface
+pub struct ParallelTransport {
+    pub uv: Vec<Point2f>,
+    pub points: Vec<Point3f>,
+    pub normals: Vec<Vector3f>,
+    pub vectors: Vec<Vector3f>,
+}
+
+impl ParallelTransport {
+    // signed rotation from the first to the last vector around the surface normal,
+    // for a closed path this is the holonomy of the loop
+    pub fn holonomy_angle(&self) -> f64 {
+        let (w0, w1) = match (self.vectors.first(), self.vectors.last()) {
+            (Some(w0), Some(w1)) => (w0, w1),
+            _ => return 0.0,
+        };
+        let n = self.normals.first().unwrap();
+        w0.cross(w1).dot(n).atan2(w0.dot(w1))
+    }
+}
+
+fn uv_at<C: Curve + ?Sized>(path: &C, s: f64) -> (Point2f, Vector2f) {
+    let p = path.point_at(s);
+    let d = path.velocity_at(s);
+    (Point2f::new(p.x, p.y), Vector2f::new(d.x, d.y))
+}
+
+// dW^k/ds = - Γ^k_ij (du^i/ds) W^j
+fn derivative<S: Surface + ?Sized, C: Curve + ?Sized>(
+    surface: &S,
+    path: &C,
+    s: f64,
+    w: &Vector2f,
+) -> Vector2f {
+    let (uv, d) = uv_at(path, s);
+    let (gu, gv) = surface.christoffel_symbols_at(uv.x, uv.y).matrices();
+    Vector2f::new(-(d.transpose() * gu * w)[0], -(d.transpose() * gv * w)[0])
+}
+
+// transport the vector projected onto the tangent plane at the start of the path,
+// the path is a curve whose x and y coordinates are (u, v)
+pub fn parallel_transport<S: Surface + ?Sized, C: Curve + ?Sized>(
+    surface: &S,
+    path: &C,
+    vector: &Vector3f,
+    count: usize,
+) -> ParallelTransport {
+    let count = count.max(2);
+    let substeps = 8;
+    let h = 1.0 / ((count - 1) * substeps) as f64;
+
+    let (uv, _) = uv_at(path, 0.0);
+    let xu = surface.du_at(uv.x, uv.y);
+    let xv = surface.dv_at(uv.x, uv.y);
+    let mut w = surface
+        .first_fundamental_form_at(uv.x, uv.y)
+        .inverse()
+        .map(|ig| ig * Vector2f::new(xu.dot(vector), xv.dot(vector)))
+        .unwrap_or_else(Vector2f::zeros);
+
+    let mut transport = ParallelTransport {
+        uv: vec![],
+        points: vec![],
+        normals: vec![],
+        vectors: vec![],
+    };
+    for i in 0..count {
+        let s = i as f64 / (count - 1) as f64;
+        let (uv, _) = uv_at(path, s);
+        transport.uv.push(uv);
+        transport.points.push(surface.point_at(uv.x, uv.y));
+        transport.normals.push(surface.normal_at(uv.x, uv.y));
+        transport
+            .vectors
+            .push(surface.du_at(uv.x, uv.y) * w.x + surface.dv_at(uv.x, uv.y) * w.y);
+
+        if i + 1 < count {
+            // RK4 between the samples
+            for j in 0..substeps {
+                let s = s + j as f64 * h;
+                let k1 = derivative(surface, path, s, &w);
+                let k2 = derivative(surface, path, s + h * 0.5, &(w + k1 * h * 0.5));
+                let k3 = derivative(surface, path, s + h * 0.5, &(w + k2 * h * 0.5));
+                let k4 = derivative(surface, path, s + h, &(w + k3 * h));
+                w += (k1 + k2 * 2.0 + k3 * 2.0 + k4) * h / 6.0;
+            }
+        }
+    }
+    transport
+}
+
+// ∬K dA over the uv region to the left of the path,
+// by Green's theorem ∬f dudv = ∮P dv with P(u, v) = ∫[0, u] f(t, v) dt
+// (for a path wrapping around a periodic v, the region between u = 0 and the path)
+pub fn enclosed_curvature<S: Surface + ?Sized, C: Curve + ?Sized>(
+    surface: &S,
+    path: &C,
+    count: usize,
+) -> f64 {
+    let count = count.max(2);
+    let inner = 64;
+
+    // K √(EG - FF)
+    let f = |u: f64, v: f64| {
+        surface.curvature_at(u, v).gaussian * surface.first_fundamental_form_at(u, v).area_element()
+    };
+
+    // composite Simpson's rule on [0, u]
+    let p = |u: f64, v: f64| {
+        let h = u / inner as f64;
+        let sum: f64 = (0..=inner)
+            .map(|i| {
+                let w = if i == 0 || i == inner {
+                    1.0
+                } else if i % 2 == 1 {
+                    4.0
+                } else {
+                    2.0
+                };
+                w * f(i as f64 * h, v)
+            })
+            .sum();
+        sum * h / 3.0
+    };
+
+    // Simpson's rule along the path
+    let h = 1.0 / count as f64;
+    (0..count)
+        .map(|i| {
+            let s0 = i as f64 * h;
+            let s1 = s0 + h * 0.5;
+            let s2 = s0 + h;
+            let integrand = |s: f64| {
+                let (uv, d) = uv_at(path, s);
+                p(uv.x, uv.y) * d.y
+            };
+            (integrand(s0) + 4.0 * integrand(s1) + integrand(s2)) * h / 6.0
+        })
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::arc_curve::ArcCurve;
+    use crate::plane::Plane;
+    use crate::surfaces::{sphere::Sphere, torus::Torus};
+    use core::f64::consts::{PI, TAU};
+
+    // a circle of the radius around (u, v) in the uv plane
+    fn circle(u: f64, v: f64, radius: f64) -> ArcCurve {
+        let plane = Plane {
+            origin: Point3f::new(u, v, 0.0),
+            ..Plane::default()
+        };
+        ArcCurve::new(plane, radius, TAU)
+    }
+
+    // the holonomy of a loop is the enclosed integral of gaussian curvature modulo 2π
+    fn assert_holonomy<S: Surface>(surface: &S, path: &ArcCurve) {
+        let vector = surface.du_at(path.plane.origin.x + path.radius, path.plane.origin.y);
+        let holonomy = parallel_transport(surface, path, &vector, 128).holonomy_angle();
+        let enclosed = enclosed_curvature(surface, path, 128);
+        let difference = (holonomy - enclosed + PI).rem_euclid(TAU) - PI;
+        assert!(
+            difference.abs() < 1e-5,
+            "holonomy {} enclosed {}",
+            holonomy,
+            enclosed
+        );
+    }
+
+    #[test]
+    fn holonomy_equals_enclosed_curvature_on_sphere() {
+        assert_holonomy(&Sphere::default(), &circle(0.5, 0.5, 0.2));
+        assert_holonomy(&Sphere::default(), &circle(0.3, 0.2, 0.1));
+    }
+
+    #[test]
+    fn holonomy_equals_enclosed_curvature_on_torus() {
+        // around the outer equator where K > 0 and the inner one where K < 0
+        assert_holonomy(&Torus::default(), &circle(0.0, 0.5, 0.15));
+        assert_holonomy(&Torus::default(), &circle(0.5, 0.5, 0.15));
+    }
+}
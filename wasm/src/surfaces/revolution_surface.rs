@@ -0,0 +1,309 @@
+use crate::curves::curve::Curve;
+use crate::curves::meridian_curve::MeridianCurve;
+use crate::domain::Domain;
+use crate::plane::Plane;
+use crate::surfaces::geodesic::Geodesic;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+use core::f64::consts::{PI, TAU};
+
+// the profile curve rotated around the axis through the origin of the plane along its normal,
+// u runs along the profile and v around the axis, turning the x axis toward the y axis
+pub struct RevolutionSurface {
+    profile: Box<dyn Curve>,
+    axis: Plane,
+    angle: Domain,
+}
+
+impl RevolutionSurface {
+    // a full turn unless the angular range is given
+    pub fn new(profile: Box<dyn Curve>, axis: Plane, angle: Option<Domain>) -> Self {
+        let angle = match angle {
+            Some(a) if a.length().abs() < TAU => a,
+            Some(a) => Domain::periodic(a.start(), a.start() + TAU * a.length().signum()),
+            None => Domain::periodic(0.0, TAU),
+        };
+        Self {
+            profile,
+            axis,
+            angle,
+        }
+    }
+
+    fn meridian<R, H>(plane: &Plane, radius: R, height: H, domain: Domain) -> Self
+    where
+        R: Fn(f64) -> [f64; 3] + 'static,
+        H: Fn(f64) -> [f64; 3] + 'static,
+    {
+        let profile = MeridianCurve::function(plane.clone(), radius, height, domain);
+        Self::new(Box::new(profile), plane.clone(), None)
+    }
+
+    // the poles are left out by delta like Sphere
+    pub fn sphere(plane: &Plane, radius: f64) -> Self {
+        let delta = 1e-8;
+        Self::meridian(
+            plane,
+            move |t| {
+                let (s, c) = t.sin_cos();
+                [radius * s, radius * c, -radius * s]
+            },
+            move |t| {
+                let (s, c) = t.sin_cos();
+                [radius * c, -radius * s, -radius * c]
+            },
+            Domain::new(delta, PI - delta),
+        )
+    }
+
+    pub fn cylinder(plane: &Plane, radius: f64, height: f64) -> Self {
+        Self::meridian(
+            plane,
+            move |_| [radius, 0.0, 0.0],
+            |t| [t, 1.0, 0.0],
+            Domain::new(0.0, height),
+        )
+    }
+
+    // the tube of radius a around the circle of radius b
+    pub fn torus(plane: &Plane, a: f64, b: f64) -> Self {
+        Self::meridian(
+            plane,
+            move |t| {
+                let (s, c) = t.sin_cos();
+                [b + a * c, -a * s, -a * c]
+            },
+            move |t| {
+                let (s, c) = t.sin_cos();
+                [a * s, a * c, -a * s]
+            },
+            Domain::new(0.0, TAU),
+        )
+    }
+
+    // minimal, r = a cosh(h / a)
+    pub fn catenoid(plane: &Plane, a: f64, height: f64) -> Self {
+        Self::meridian(
+            plane,
+            move |t| {
+                let (sh, ch) = ((t / a).sinh(), (t / a).cosh());
+                [a * ch, sh, ch / a]
+            },
+            |t| [t, 1.0, 0.0],
+            Domain::new(-height * 0.5, height * 0.5),
+        )
+    }
+
+    // K = -1 / a^2, the tractrix r = a sech t, h = a (t - tanh t),
+    // starting just off the cuspidal edge at t = 0
+    pub fn pseudosphere(plane: &Plane, a: f64, length: f64) -> Self {
+        Self::meridian(
+            plane,
+            move |t| {
+                let (sech, tanh) = (1.0 / t.cosh(), t.tanh());
+                [
+                    a * sech,
+                    -a * sech * tanh,
+                    a * sech * (tanh * tanh - sech * sech),
+                ]
+            },
+            move |t| {
+                let (sech, tanh) = (1.0 / t.cosh(), t.tanh());
+                [
+                    a * (t - tanh),
+                    a * tanh * tanh,
+                    2.0 * a * tanh * sech * sech,
+                ]
+            },
+            Domain::new(1e-2, length),
+        )
+    }
+
+    // Delaunay's surface of constant mean curvature H = 1 / (2a) for 0 < b < 1
+    // https://en.wikipedia.org/wiki/Unduloid
+    pub fn unduloid(plane: &Plane, a: f64, b: f64, periods: f64) -> Self {
+        let profile = MeridianCurve::unduloid(plane.clone(), a, b, periods);
+        Self::new(Box::new(profile), plane.clone(), None)
+    }
+
+    pub fn vase(plane: &Plane) -> Self {
+        Self::meridian(
+            plane,
+            |t| {
+                let (s, c) = (t * 1.5).sin_cos();
+                let (s5, c5) = (t * 5.0).sin_cos();
+                [
+                    1.0 + 0.5 * s + 0.1 * c5,
+                    0.75 * c - 0.5 * s5,
+                    -1.125 * s - 2.5 * c5,
+                ]
+            },
+            |t| [t, 1.0, 0.0],
+            Domain::new(0.0, 4.0),
+        )
+    }
+
+    pub fn profile(&self) -> &dyn Curve {
+        self.profile.as_ref()
+    }
+
+    fn sign(&self) -> f64 {
+        let n = &self.axis.normal;
+        n.dot(&self.axis.x_axis.cross(&self.axis.y_axis)).signum()
+    }
+
+    // d^order/dφ^order of the rotation of w by φ around the axis
+    fn rotate(&self, w: &Vector3f, phi: f64, order: usize) -> Vector3f {
+        let n = self.axis.normal.normalize();
+        let a = n * w.dot(&n);
+        let p = w - a;
+        let q = n.cross(&p) * self.sign();
+        let (s, c) = phi.sin_cos();
+        match order {
+            0 => a + p * c + q * s,
+            1 => -p * s + q * c,
+            _ => -p * c - q * s,
+        }
+    }
+
+    // distance from the axis at normalized u
+    pub fn radius_at(&self, u: f64) -> f64 {
+        let n = self.axis.normal.normalize();
+        let w = self.profile.point_at(u) - self.axis.origin;
+        (w - n * w.dot(&n)).norm()
+    }
+
+    // Clairaut's relation: r cos ψ is constant along a geodesic,
+    // ψ is the angle between the direction and the parallel through (u, v)
+    // https://en.wikipedia.org/wiki/Clairaut%27s_relation_(differential_geometry)
+    pub fn clairaut_constant(&self, u: f64, v: f64, direction: &Vector3f) -> f64 {
+        // |Xv| = r * angle length
+        self.dv_at(u, v).dot(&direction.normalize()) / self.angle.length()
+    }
+
+    // max |c - c0| of the Clairaut constant along the geodesic, zero up to the integration error
+    pub fn clairaut_deviation(&self, geodesic: &Geodesic) -> f64 {
+        let constants: Vec<f64> = geodesic
+            .uv
+            .windows(2)
+            .filter_map(|w| {
+                let d = w[1] - w[0];
+                // segments wrapped across the seam
+                if d.x.abs() > 0.5 || d.y.abs() > 0.5 {
+                    return None;
+                }
+                let m = w[0] + d * 0.5;
+                let direction = self.du_at(m.x, m.y) * d.x + self.dv_at(m.x, m.y) * d.y;
+                Some(self.clairaut_constant(m.x, m.y, &direction))
+            })
+            .collect();
+        match constants.first() {
+            Some(c0) => constants.iter().map(|c| (c - c0).abs()).fold(0.0, f64::max),
+            None => 0.0,
+        }
+    }
+
+    // normalized u of the parallels r(u) = |c| where a geodesic with the Clairaut constant c
+    // turns back, it stays in the region r >= |c|
+    pub fn turning_parallels(&self, c: f64) -> Vec<f64> {
+        let count = 256;
+        let f = |u: f64| self.radius_at(u) - c.abs();
+        let mut found = vec![];
+        for i in 0..count {
+            let (mut a, mut b) = (i as f64 / count as f64, (i + 1) as f64 / count as f64);
+            if f(a) == 0.0 {
+                found.push(a);
+                continue;
+            }
+            if f(a) * f(b) > 0.0 {
+                continue;
+            }
+            for _ in 0..60 {
+                let m = (a + b) * 0.5;
+                if f(a) * f(m) <= 0.0 {
+                    b = m;
+                } else {
+                    a = m;
+                }
+            }
+            found.push((a + b) * 0.5);
+        }
+        found
+    }
+}
+
+impl Default for RevolutionSurface {
+    fn default() -> Self {
+        RevolutionSurface::vase(&Plane::default())
+    }
+}
+
+impl Surface for RevolutionSurface {
+    // periodic when the profile is closed
+    fn u_domain(&self) -> Domain {
+        let d = self.profile.domain();
+        let closed = (self.profile.point_at(0.0) - self.profile.point_at(1.0)).norm();
+        if closed <= 1e-9 * self.profile.velocity_at(0.0).norm().max(1.0) {
+            Domain::periodic(d.start(), d.end())
+        } else {
+            d
+        }
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.angle.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        let w = self.profile.point_at(u) - self.axis.origin;
+        self.axis.origin + self.rotate(&w, self.angle.map(v), 0)
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.rotate(&self.profile.velocity_at(u), self.angle.map(v), 0)
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        let w = self.profile.point_at(u) - self.axis.origin;
+        self.rotate(&w, self.angle.map(v), 1) * self.angle.length()
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        self.rotate(&self.profile.acceleration_at(u), self.angle.map(v), 0)
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        self.rotate(&self.profile.velocity_at(u), self.angle.map(v), 1) * self.angle.length()
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        let w = self.profile.point_at(u) - self.axis.origin;
+        let l = self.angle.length();
+        self.rotate(&w, self.angle.map(v), 2) * l * l
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // r cos ψ is conserved along a geodesic of the catenoid, which stays outside the parallels r = |c|
+    #[test]
+    fn clairaut_on_catenoid() {
+        let surface = RevolutionSurface::catenoid(&Plane::default(), 1.0, 3.0);
+        let (u, v, psi): (f64, f64, f64) = (0.5, 0.0, 0.8);
+        let (xu, xv) = (surface.du_at(u, v), surface.dv_at(u, v));
+        let direction = xv.normalize() * psi.cos() + xu.normalize() * psi.sin();
+        let c = surface.clairaut_constant(u, v, &direction);
+        assert!((c - surface.radius_at(u) * psi.cos()).abs() < 1e-12);
+
+        let geodesic = surface.geodesic_from(u, v, &direction, 2.0).unwrap();
+        assert!((geodesic.length - 2.0).abs() < 1e-9);
+        let deviation = surface.clairaut_deviation(&geodesic);
+        assert!(deviation < 1e-4 * c.abs(), "deviation {}", deviation);
+        for p in geodesic.uv.iter() {
+            assert!(surface.radius_at(p.x) >= c.abs() - 1e-6);
+        }
+    }
+}
@@ -0,0 +1,267 @@
+use crate::curves::arc_curve::ArcCurve;
+use crate::curves::curve::Curve;
+use crate::curves::line_curve::LineCurve;
+use crate::domain::Domain;
+use crate::plane::Plane;
+use crate::surfaces::surface::Surface;
+use crate::types::Point2f;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+use core::f64::consts::TAU;
+
+// the direction δ(u) of the ruling through the directrix point c(u)
+pub enum Ruling {
+    // δ(u) is the position vector of the curve point
+    Director(Box<dyn Curve>),
+    // δ(u) = rail(u) - c(u)
+    Rail(Box<dyn Curve>),
+}
+
+// X(u, v) = c(u) + v δ(u), u runs along the directrix and v along the rulings
+// https://en.wikipedia.org/wiki/Ruled_surface
+pub struct RuledSurface {
+    directrix: Box<dyn Curve>,
+    ruling: Ruling,
+    v_domain: Domain,
+}
+
+impl RuledSurface {
+    pub fn new(directrix: Box<dyn Curve>, director: Box<dyn Curve>, v: Domain) -> Self {
+        Self {
+            directrix,
+            ruling: Ruling::Director(director),
+            v_domain: v,
+        }
+    }
+
+    // the rulings join the points of the rails at the same normalized parameter,
+    // v = 0 on the first rail and 1 on the second
+    pub fn between(rail0: Box<dyn Curve>, rail1: Box<dyn Curve>) -> Self {
+        Self {
+            directrix: rail0,
+            ruling: Ruling::Rail(rail1),
+            v_domain: Domain::unit(),
+        }
+    }
+
+    // Helicoid with u and v swapped, the rulings leave the axis horizontally
+    pub fn helicoid(plane: &Plane, c: f64, domain: &Domain) -> Self {
+        let axis = LineCurve::new(
+            plane.origin + plane.normal * c * domain.start(),
+            plane.origin + plane.normal * c * domain.end(),
+        );
+        let (s, co) = domain.start().sin_cos();
+        let director = ArcCurve::new(
+            Plane::new(
+                Point3f::origin(),
+                plane.x_axis * co + plane.y_axis * s,
+                -plane.x_axis * s + plane.y_axis * co,
+                plane.normal,
+            ),
+            1.0,
+            domain.length(),
+        );
+        Self::new(Box::new(axis), Box::new(director), domain.clone())
+    }
+
+    // z = x^2 / a^2 - y^2 / b^2 as the bilinear patch between two skew lines,
+    // the same surface as HyperbolicParaboloid in coordinates rotated by 45 degrees
+    pub fn hyperbolic_paraboloid(plane: &Plane, a: f64, b: f64, domain: &Domain) -> Self {
+        let point = |p: f64, q: f64| {
+            plane.origin
+                + plane.x_axis * a * (p + q) * 0.5
+                + plane.y_axis * b * (p - q) * 0.5
+                + plane.normal * p * q
+        };
+        let (d0, d1) = (domain.start(), domain.end());
+        Self::between(
+            Box::new(LineCurve::new(point(d0, d0), point(d1, d0))),
+            Box::new(LineCurve::new(point(d0, d1), point(d1, d1))),
+        )
+    }
+
+    // developable, the rulings meet at the apex above the center of the base circle,
+    // the apex itself is left out by delta
+    pub fn cone(plane: &Plane, radius: f64, height: f64) -> Self {
+        let delta = 1e-8;
+        let apex = plane.origin + plane.normal * height;
+        let director = ArcCurve::new(
+            Plane::new(
+                Point3f::origin() - plane.normal * height,
+                plane.x_axis,
+                plane.y_axis,
+                plane.normal,
+            ),
+            radius,
+            TAU,
+        );
+        Self::new(
+            Box::new(LineCurve::new(apex, apex)),
+            Box::new(director),
+            Domain::new(delta, 1.0),
+        )
+    }
+
+    // developable, parallel rulings
+    pub fn cylinder(plane: &Plane, radius: f64, height: f64) -> Self {
+        let base = ArcCurve::new(plane.clone(), radius, TAU);
+        let up = Point3f::origin() + plane.normal * height;
+        Self::new(
+            Box::new(base),
+            Box::new(LineCurve::new(up, up)),
+            Domain::unit(),
+        )
+    }
+
+    pub fn directrix(&self) -> &dyn Curve {
+        self.directrix.as_ref()
+    }
+
+    // c, c', c'' at normalized u
+    fn directrix_at(&self, u: f64) -> (Point3f, Vector3f, Vector3f) {
+        let c = &self.directrix;
+        (c.point_at(u), c.velocity_at(u), c.acceleration_at(u))
+    }
+
+    // δ, δ', δ'' at normalized u
+    fn director_at(&self, u: f64) -> (Vector3f, Vector3f, Vector3f) {
+        match &self.ruling {
+            Ruling::Director(d) => (
+                d.point_at(u) - Point3f::origin(),
+                d.velocity_at(u),
+                d.acceleration_at(u),
+            ),
+            Ruling::Rail(r) => {
+                let (c, c1, c2) = self.directrix_at(u);
+                (
+                    r.point_at(u) - c,
+                    r.velocity_at(u) - c1,
+                    r.acceleration_at(u) - c2,
+                )
+            }
+        }
+    }
+
+    // the unit ruling direction g = δ / |δ| and g', None where the rulings are parallel
+    fn unit_director_at(&self, u: f64) -> Option<(f64, Vector3f, Vector3f)> {
+        let (d, d1, _) = self.director_at(u);
+        let length = d.norm();
+        let g = d / length;
+        let g1 = (d1 - g * g.dot(&d1)) / length;
+        if g1.norm() <= 1e-12 * self.directrix_at(u).1.norm().max(1.0) {
+            None
+        } else {
+            Some((length, g, g1))
+        }
+    }
+
+    // native v of the striction point on the ruling at normalized u,
+    // the point closest to the neighboring rulings
+    pub fn striction_parameter_at(&self, u: f64) -> Option<f64> {
+        let (length, _, g1) = self.unit_director_at(u)?;
+        let (_, c1, _) = self.directrix_at(u);
+        // the distance along g is w = v |δ|
+        Some(-c1.dot(&g1) / g1.dot(&g1) / length)
+    }
+
+    pub fn striction_point_at(&self, u: f64) -> Option<Point3f> {
+        let v = self.striction_parameter_at(u)?;
+        let (c, _, _) = self.directrix_at(u);
+        Some(c + self.director_at(u).0 * v)
+    }
+
+    // normalized uv of the striction curve sampled at count points of u,
+    // the points off the patch or on cylindrical rulings are left out
+    pub fn striction_curve(&self, count: usize) -> Vec<Point2f> {
+        let count = count.max(2);
+        (0..count)
+            .filter_map(|i| {
+                let u = i as f64 / (count - 1) as f64;
+                let v = self.v_domain.normalize(self.striction_parameter_at(u)?);
+                if (0.0..=1.0).contains(&v) {
+                    Some(Point2f::new(u, v))
+                } else {
+                    None
+                }
+            })
+            .collect()
+    }
+
+    // λ = det(c', g, g') / |g'|^2, K = -λ^2 / (λ^2 + w^2)^2 at the distance w from the striction point
+    pub fn distribution_parameter_at(&self, u: f64) -> Option<f64> {
+        let (_, g, g1) = self.unit_director_at(u)?;
+        let (_, c1, _) = self.directrix_at(u);
+        Some(c1.dot(&g.cross(&g1)) / g1.dot(&g1))
+    }
+
+    // K ≡ 0 iff det(c', δ, δ') vanishes along the directrix,
+    // sampled at count points of u relative to |c'| |δ| |δ'|
+    pub fn is_developable(&self, count: usize) -> bool {
+        let count = count.max(2);
+        (0..count).all(|i| {
+            let u = i as f64 / (count - 1) as f64;
+            let (_, c1, _) = self.directrix_at(u);
+            let (d, d1, _) = self.director_at(u);
+            let scale = c1.norm() * d.norm() * d1.norm();
+            c1.dot(&d.cross(&d1)).abs() <= 1e-9 * scale
+        })
+    }
+}
+
+impl Default for RuledSurface {
+    fn default() -> Self {
+        RuledSurface::helicoid(&Plane::default(), 2.0, &Domain::new(-2.0, 2.0))
+    }
+}
+
+impl Surface for RuledSurface {
+    // periodic when both the directrix and the rulings close up
+    fn u_domain(&self) -> Domain {
+        // a cone's directrix shrinks to the apex, its parameter is that of the director
+        let d = match &self.ruling {
+            Ruling::Director(director) if self.directrix.domain().length() == 0.0 => {
+                director.domain()
+            }
+            _ => self.directrix.domain(),
+        };
+        let closed = |p: Point3f, q: Point3f| (p - q).norm() <= 1e-9 * p.coords.norm().max(1.0);
+        let (c0, c1) = (self.directrix.point_at(0.0), self.directrix.point_at(1.0));
+        let (d0, d1) = (self.director_at(0.0).0, self.director_at(1.0).0);
+        if closed(c0, c1) && closed(Point3f::from(d0), Point3f::from(d1)) {
+            Domain::periodic(d.start(), d.end())
+        } else {
+            d
+        }
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.v_domain.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        let (c, _, _) = self.directrix_at(u);
+        c + self.director_at(u).0 * self.v_domain.map(v)
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        let (_, c1, _) = self.directrix_at(u);
+        c1 + self.director_at(u).1 * self.v_domain.map(v)
+    }
+
+    fn dv_at(&self, u: f64, _v: f64) -> Vector3f {
+        self.director_at(u).0 * self.v_domain.length()
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        let (_, _, c2) = self.directrix_at(u);
+        c2 + self.director_at(u).2 * self.v_domain.map(v)
+    }
+
+    fn dudv_at(&self, u: f64, _v: f64) -> Vector3f {
+        self.director_at(u).1 * self.v_domain.length()
+    }
+
+    fn dvdv_at(&self, _u: f64, _v: f64) -> Vector3f {
+        Vector3f::zeros()
+    }
+}
@@ -0,0 +1,394 @@
+use crate::domain::Domain;
+use crate::expression::{Expression, Jet};
+use crate::surfaces::surface::Surface;
+use crate::types::*;
+use std::collections::BTreeMap;
+
+// ω(u, v) as a jet of the asymptotic coordinates
+pub type AngleFunction = dyn Fn(Jet, Jet) -> Jet;
+
+// the angle between the asymptotic lines
+pub enum SineGordonAngle {
+    Function(Box<AngleFunction>),
+    // a formula of u and v
+    Expression(Box<Expression>),
+}
+
+// e1 = X_u, e2 = N x e1, N and the point X
+type State = [Vector3f; 4];
+
+// the surface of curvature K = -1 whose asymptotic lines form the Chebyshev net of angle ω,
+// I = du^2 + 2 cos ω du dv + dv^2 and II = 2 sin ω du dv,
+// which exists exactly when ω solves the sine-Gordon equation ω_uv = sin ω (Gauss),
+// the orthonormal frame (e1, e2, N) follows the structure equations (Codazzi)
+// e1_u = -ω_u e2, e2_u = ω_u e1 + N, N_u = -e2,
+// e1_v = sin ω N, e2_v = -cos ω N, N_v = -sin ω e1 + cos ω e2,
+// with X_u = e1 and X_v = cos ω e1 + sin ω e2, singular on the cuspidal edges where sin ω = 0
+// https://en.wikipedia.org/wiki/Sine-Gordon_equation#Origin_of_the_equation_and_its_name
+pub struct SineGordonSurface {
+    angle: SineGordonAngle,
+    u_domain: Domain,
+    v_domain: Domain,
+    // the frames on a (count + 1) x (count + 1) grid, integrated from the center of the domain
+    count: usize,
+    grid: Vec<State>,
+}
+
+impl SineGordonSurface {
+    // the number of grid cells in each direction
+    const COUNT: usize = 64;
+    // Runge-Kutta steps per grid cell
+    const STEPS: usize = 4;
+
+    pub fn new(angle: SineGordonAngle, u: Domain, v: Domain) -> Self {
+        let mut surface = Self {
+            angle,
+            u_domain: u,
+            v_domain: v,
+            count: Self::COUNT,
+            grid: vec![],
+        };
+        surface.integrate_frames();
+        surface
+    }
+
+    pub fn function(angle: impl Fn(Jet, Jet) -> Jet + 'static, u: Domain, v: Domain) -> Self {
+        Self::new(SineGordonAngle::Function(Box::new(angle)), u, v)
+    }
+
+    // ω(u, v) as a formula, e.g. "4 * atan(e()^(u + v))" for the pseudosphere
+    pub fn try_expression(angle: &str, u: Domain, v: Domain) -> Result<Self, fasteval::Error> {
+        let expression = Expression::parse(angle)?;
+        let mut variables: BTreeMap<String, Jet> = BTreeMap::new();
+        variables.insert(String::from("u"), Jet::u(u.map(0.5)));
+        variables.insert(String::from("v"), Jet::v(v.map(0.5)));
+        expression.jet(&variables)?;
+        Ok(Self::new(
+            SineGordonAngle::Expression(Box::new(expression)),
+            u,
+            v,
+        ))
+    }
+
+    // the kink ω = 4 atan(e^(u + v)), the surface of revolution of the tractrix
+    pub fn pseudosphere() -> Self {
+        Self::dini(1.0)
+    }
+
+    // the boosted kink ω = 4 atan(e^(a u + v / a)), a helicoidal pseudosphere for a != 1,
+    // on the side a u + v / a > 0 of the cuspidal edge, starting just off it
+    pub fn dini(a: f64) -> Self {
+        Self::function(
+            move |u, v| (u.scale(a) + v.scale(1.0 / a)).exp().atan().scale(4.0),
+            Domain::new(1e-2, 3.0),
+            Domain::new(1e-2, 3.0),
+        )
+    }
+
+    // the breather ω = 4 atan(√(1 - ν^2) / ν sin(ν (u - v)) / cosh(√(1 - ν^2) (u + v))) for 0 < ν < 1,
+    // periodic along u - v, on the side u > v of the cuspidal edge u = v
+    pub fn breather(nu: f64) -> Self {
+        let k = (1.0 - nu * nu).sqrt();
+        Self::function(
+            move |u, v| {
+                let t = (u - v).scale(nu).sin().scale(k / nu);
+                (t * (u + v).scale(k).cosh().inv()).atan().scale(4.0)
+            },
+            Domain::new(1e-2, 6.0),
+            Domain::new(-6.0, -1e-2),
+        )
+    }
+
+    // the breather in the limit ν -> 0, ω = 4 atan((u - v) / cosh(u + v)),
+    // on the side u > v of the cuspidal edge u = v
+    pub fn kuen() -> Self {
+        Self::function(
+            |u, v| ((u - v) * (u + v).cosh().inv()).atan().scale(4.0),
+            Domain::new(1e-2, 4.0),
+            Domain::new(-4.0, -1e-2),
+        )
+    }
+
+    // ω_uv - sin ω at normalized (u, v), zero up to rounding for a solution
+    pub fn residual_at(&self, u: f64, v: f64) -> f64 {
+        let w = self.angle_at(self.u_domain.map(u), self.v_domain.map(v));
+        w.duv - w.value.sin()
+    }
+
+    // ω and its partial derivatives at (u, v) of the asymptotic coordinates
+    fn angle_at(&self, u: f64, v: f64) -> Jet {
+        let (u, v) = (Jet::u(u), Jet::v(v));
+        match &self.angle {
+            SineGordonAngle::Function(angle) => angle(u, v),
+            SineGordonAngle::Expression(expression) => {
+                let mut variables: BTreeMap<String, Jet> = BTreeMap::new();
+                variables.insert(String::from("u"), u);
+                variables.insert(String::from("v"), v);
+                expression
+                    .jet(&variables)
+                    .unwrap_or_else(|_| Jet::constant(f64::NAN))
+            }
+        }
+    }
+
+    // the derivative of the frame and the point along u or v at (u, v)
+    fn rate(&self, state: &State, u: f64, v: f64, along_u: bool) -> State {
+        let [e1, e2, n, _] = *state;
+        let w = self.angle_at(u, v);
+        if along_u {
+            [-e2 * w.du, e1 * w.du + n, -e2, e1]
+        } else {
+            let (s, c) = w.value.sin_cos();
+            [n * s, -n * c, e2 * c - e1 * s, e1 * c + e2 * s]
+        }
+    }
+
+    // the classical Runge-Kutta method from (u, v) to t along u or v
+    fn march(&self, state: &State, u: f64, v: f64, t: f64, along_u: bool, steps: usize) -> State {
+        let add = |a: &State, b: &State, h: f64| -> State {
+            [
+                a[0] + b[0] * h,
+                a[1] + b[1] * h,
+                a[2] + b[2] * h,
+                a[3] + b[3] * h,
+            ]
+        };
+        let t0 = if along_u { u } else { v };
+        let h = (t - t0) / steps as f64;
+        let at = |t: f64| if along_u { (t, v) } else { (u, t) };
+        let mut state = *state;
+        for i in 0..steps {
+            let t = t0 + h * i as f64;
+            let (u1, v1) = at(t);
+            let (u2, v2) = at(t + h * 0.5);
+            let (u3, v3) = at(t + h);
+            let k1 = self.rate(&state, u1, v1, along_u);
+            let k2 = self.rate(&add(&state, &k1, h * 0.5), u2, v2, along_u);
+            let k3 = self.rate(&add(&state, &k2, h * 0.5), u2, v2, along_u);
+            let k4 = self.rate(&add(&state, &k3, h), u3, v3, along_u);
+            for (k, s) in state.iter_mut().enumerate() {
+                *s += (k1[k] + k2[k] * 2.0 + k3[k] * 2.0 + k4[k]) * (h / 6.0);
+            }
+        }
+        state
+    }
+
+    // keeps the frame orthonormal against the drift of the integration
+    fn orthonormalize(state: &State) -> State {
+        let e1 = state[0].normalize();
+        let e2 = (state[1] - e1 * e1.dot(&state[1])).normalize();
+        [e1, e2, e1.cross(&e2), state[3]]
+    }
+
+    // the frames on the grid, along the middle row in u and then along every column in v
+    fn integrate_frames(&mut self) {
+        let n = self.count;
+        let half = n / 2;
+        let parameter = |i: usize, d: &Domain| d.map(i as f64 / n as f64);
+        // pairs of (from, to) indices running outward from the middle
+        let outward: Vec<(usize, usize)> = (half + 1..=n)
+            .map(|i| (i - 1, i))
+            .chain((0..half).rev().map(|i| (i + 1, i)))
+            .collect();
+        let mut grid = vec![[Vector3f::zeros(); 4]; (n + 1) * (n + 1)];
+        grid[half * (n + 1) + half] = [
+            Vector3f::new(1.0, 0.0, 0.0),
+            Vector3f::new(0.0, 1.0, 0.0),
+            Vector3f::new(0.0, 0.0, 1.0),
+            Vector3f::zeros(),
+        ];
+        let v = parameter(half, &self.v_domain);
+        for &(from, to) in outward.iter() {
+            let (u0, u1) = (
+                parameter(from, &self.u_domain),
+                parameter(to, &self.u_domain),
+            );
+            let state = self.march(&grid[from * (n + 1) + half], u0, v, u1, true, Self::STEPS);
+            grid[to * (n + 1) + half] = Self::orthonormalize(&state);
+        }
+        for i in 0..=n {
+            let u = parameter(i, &self.u_domain);
+            for &(from, to) in outward.iter() {
+                let (v0, v1) = (
+                    parameter(from, &self.v_domain),
+                    parameter(to, &self.v_domain),
+                );
+                let state = self.march(&grid[i * (n + 1) + from], u, v0, v1, false, Self::STEPS);
+                grid[i * (n + 1) + to] = Self::orthonormalize(&state);
+            }
+        }
+        self.grid = grid;
+    }
+
+    // the frame and the point at normalized (u, v) from the nearest grid node
+    fn state_at(&self, u: f64, v: f64) -> State {
+        let n = self.count;
+        let node = |t: f64| ((t * n as f64).round().max(0.0) as usize).min(n);
+        let (i, j) = (node(u), node(v));
+        let (u0, v0) = (
+            self.u_domain.map(i as f64 / n as f64),
+            self.v_domain.map(j as f64 / n as f64),
+        );
+        let (u1, v1) = (self.u_domain.map(u), self.v_domain.map(v));
+        let state = &self.grid[i * (n + 1) + j];
+        let steps = Self::STEPS / 2;
+        let state = self.march(state, u0, v0, u1, true, steps);
+        self.march(&state, u1, v0, v1, false, steps)
+    }
+}
+
+impl Default for SineGordonSurface {
+    fn default() -> Self {
+        SineGordonSurface::kuen()
+    }
+}
+
+impl Surface for SineGordonSurface {
+    fn u_domain(&self) -> Domain {
+        self.u_domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.v_domain.clone()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        Point3f::from(self.state_at(u, v)[3])
+    }
+
+    // X_u = e1
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        self.state_at(u, v)[0] * self.u_domain.length()
+    }
+
+    // X_v = cos ω e1 + sin ω e2
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        let [e1, e2, _, _] = self.state_at(u, v);
+        let w = self.angle_at(self.u_domain.map(u), self.v_domain.map(v));
+        let (s, c) = w.value.sin_cos();
+        (e1 * c + e2 * s) * self.v_domain.length()
+    }
+
+    // X_uu = -ω_u e2
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        let e2 = self.state_at(u, v)[1];
+        let w = self.angle_at(self.u_domain.map(u), self.v_domain.map(v));
+        let l = self.u_domain.length();
+        -e2 * w.du * l * l
+    }
+
+    // X_uv = sin ω N
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        let n = self.state_at(u, v)[2];
+        let w = self.angle_at(self.u_domain.map(u), self.v_domain.map(v));
+        n * w.value.sin() * self.u_domain.length() * self.v_domain.length()
+    }
+
+    // X_vv = ω_v (-sin ω e1 + cos ω e2)
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        let [e1, e2, _, _] = self.state_at(u, v);
+        let w = self.angle_at(self.u_domain.map(u), self.v_domain.map(v));
+        let (s, c) = w.value.sin_cos();
+        let l = self.v_domain.length();
+        (e2 * c - e1 * s) * w.dv * l * l
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::uv;
+
+    // the presets solve the sine-Gordon equation, and the integrated frames agree with
+    // the points they sweep: differences of point_at match du_at and dv_at
+    fn assert_integrated(surface: &SineGordonSurface) {
+        let h = 1e-5;
+        for (u, v) in uv::grid(5) {
+            let residual = surface.residual_at(u, v);
+            assert!(
+                residual.abs() < 1e-9,
+                "residual {} at ({}, {})",
+                residual,
+                u,
+                v
+            );
+            let du = (surface.point_at(u + h, v) - surface.point_at(u - h, v)) / (2.0 * h);
+            let dv = (surface.point_at(u, v + h) - surface.point_at(u, v - h)) / (2.0 * h);
+            for (difference, exact) in [(du, surface.du_at(u, v)), (dv, surface.dv_at(u, v))] {
+                assert!(
+                    (difference - exact).norm() < 1e-6 * exact.norm().max(1.0),
+                    "difference {:?} derivative {:?} at ({}, {})",
+                    difference,
+                    exact,
+                    u,
+                    v
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn pseudosphere_is_integrated() {
+        assert_integrated(&SineGordonSurface::pseudosphere());
+    }
+
+    #[test]
+    fn dini_is_integrated() {
+        assert_integrated(&SineGordonSurface::dini(0.6));
+    }
+
+    #[test]
+    fn breather_is_integrated() {
+        assert_integrated(&SineGordonSurface::breather(0.5));
+    }
+
+    #[test]
+    fn kuen_is_integrated() {
+        assert_integrated(&SineGordonSurface::kuen());
+    }
+
+    #[test]
+    fn expression_is_integrated() {
+        let surface = SineGordonSurface::try_expression(
+            "4 * atan((u - v) / cosh(u + v))",
+            Domain::new(1e-2, 4.0),
+            Domain::new(-4.0, -1e-2),
+        )
+        .unwrap();
+        assert_integrated(&surface);
+    }
+
+    // the parallels u + v = s of the pseudosphere are circles of the tractrix radius sech s
+    #[test]
+    fn pseudosphere_is_revolved_tractrix() {
+        let surface = SineGordonSurface::pseudosphere();
+        let point = |u: f64, v: f64| {
+            surface.point_at(surface.u_domain.normalize(u), surface.v_domain.normalize(v))
+        };
+        for s in [0.5, 1.0, 2.0, 3.0] {
+            let points: Vec<Point3f> = [-0.2, -0.1, 0.1, 0.2]
+                .iter()
+                .map(|a| point(s * 0.5 + a, s * 0.5 - a))
+                .collect();
+            // the circumcircle of the first three
+            let (a, b) = (points[1] - points[0], points[2] - points[0]);
+            let n = a.cross(&b);
+            let center = points[0]
+                + (b.cross(&n) * a.norm_squared() + n.cross(&a) * b.norm_squared())
+                    / (2.0 * n.norm_squared());
+            let radius = s.cosh().recip();
+            for p in points.iter() {
+                let r = (p - center).norm();
+                assert!(
+                    (r - radius).abs() < 1e-6,
+                    "radius {} expected {} on the parallel {}",
+                    r,
+                    radius,
+                    s
+                );
+            }
+            // the fourth lies in the plane of the circle as well
+            assert!((points[3] - center).dot(&n.normalize()).abs() < 1e-6);
+        }
+    }
+}
@@ -33,9 +33,22 @@ impl SingleLeafParaboloid {
     fn map(&self, u: f64, v: f64) -> (f64, f64) {
         (self.domain.map(u), v * TAU)
     }
+
+    // du/du01, dv/dv01
+    fn scale(&self) -> (f64, f64) {
+        (self.domain.length(), TAU)
+    }
 }
 
 impl Surface for SingleLeafParaboloid {
+    fn u_domain(&self) -> Domain {
+        self.domain.clone()
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::periodic(0.0, TAU)
+    }
+
     fn point_at(&self, u: f64, v: f64) -> Point3f {
         let (u, v) = self.map(u, v);
         let dx = self.a * u.cosh() * v.cos();
@@ -49,7 +62,8 @@ impl Surface for SingleLeafParaboloid {
         let dx = self.a * u.sinh() * v.cos();
         let dy = self.b * u.sinh() * v.sin();
         let dz = self.c * u.cosh();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (su, _sv) = self.scale();
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * su
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -57,7 +71,8 @@ impl Surface for SingleLeafParaboloid {
         let dx = -self.a * u.cosh() * v.sin();
         let dy = self.b * u.cosh() * v.cos();
         let dz = 0.0;
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (_su, sv) = self.scale();
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * sv
     }
 
     fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
@@ -65,7 +80,8 @@ impl Surface for SingleLeafParaboloid {
         let dx = self.a * u.cosh() * v.cos();
         let dy = self.b * u.cosh() * v.sin();
         let dz = self.c * u.sinh();
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (su, _sv) = self.scale();
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * su * su
     }
 
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -73,7 +89,8 @@ impl Surface for SingleLeafParaboloid {
         let dx = -self.a * u.sinh() * v.sin();
         let dy = self.b * u.sinh() * v.cos();
         let dz = 0.0;
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (su, sv) = self.scale();
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * su * sv
     }
 
     fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -81,6 +98,7 @@ impl Surface for SingleLeafParaboloid {
         let dx = -self.a * u.cosh() * v.cos();
         let dy = -self.b * u.cosh() * v.sin();
         let dz = 0.0;
-        self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz
+        let (_su, sv) = self.scale();
+        (self.plane.x_axis * dx + self.plane.y_axis * dy + self.plane.normal * dz) * sv * sv
     }
 }
@@ -1,3 +1,4 @@
+use crate::domain::Domain;
 use crate::plane::Plane;
 use crate::surfaces::surface::Surface;
 use crate::types::Point3f;
@@ -21,6 +22,12 @@ impl Sphere {
         Self { plane, radius }
     }
 
+    // dtheta/du, dphi/dv
+    fn scale(&self) -> (f64, f64) {
+        let delta = 1e-8;
+        (PI * (1.0 - delta * 2.0), TAU * (1.0 - delta))
+    }
+
     fn sin_cos(&self, u: f64, v: f64) -> (f64, f64, f64, f64) {
         let delta = 1e-8;
         let theta = u * PI * (1.0 - delta * 2.0) + delta; // 0.0 < u < pi
@@ -34,6 +41,14 @@ impl Sphere {
 }
 
 impl Surface for Sphere {
+    fn u_domain(&self) -> Domain {
+        Domain::new(0.0, PI)
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::periodic(0.0, TAU)
+    }
+
     fn area(&self) -> f64 {
         4.0 * PI * self.radius * self.radius
     }
@@ -51,7 +66,8 @@ impl Surface for Sphere {
         let dx = self.radius * ct * cp * self.plane.x_axis; // cos(theta) * cos(phi)
         let dy = self.radius * ct * sp * self.plane.y_axis; // cos(theta) * sin(phi)
         let dz = -self.radius * st * self.plane.normal; // - sin(theta)
-        dx + dy + dz
+        let (su, _sv) = self.scale();
+        (dx + dy + dz) * su
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -59,7 +75,8 @@ impl Surface for Sphere {
         let dx = -self.radius * st * sp * self.plane.x_axis; // - sin(theta) * sin(phi)
         let dy = self.radius * st * cp * self.plane.y_axis; // sin(theta) * cos(phi)
         let dz = 0.0 * self.plane.normal; // 0
-        dx + dy + dz
+        let (_su, sv) = self.scale();
+        (dx + dy + dz) * sv
     }
 
     fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
@@ -67,7 +84,8 @@ impl Surface for Sphere {
         let dx = -self.radius * st * cp * self.plane.x_axis; // - sin(theta) * cos(phi)
         let dy = -self.radius * st * sp * self.plane.y_axis; // - sin(theta) * sin(phi)
         let dz = -self.radius * ct * self.plane.normal; // - cos(theta)
-        dx + dy + dz
+        let (su, _sv) = self.scale();
+        (dx + dy + dz) * su * su
     }
 
     fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -75,7 +93,8 @@ impl Surface for Sphere {
         let dx = -self.radius * st * cp * self.plane.x_axis; // - sin(theta) * cos(phi)
         let dy = -self.radius * st * sp * self.plane.y_axis; // - sin(theta) * sin(phi)
         let dz = 0.0 * self.plane.normal; // 0
-        dx + dy + dz
+        let (_su, sv) = self.scale();
+        (dx + dy + dz) * sv * sv
     }
 
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -83,6 +102,7 @@ impl Surface for Sphere {
         let dx = -self.radius * ct * sp * self.plane.x_axis; // - cos(theta) * sin(phi)
         let dy = self.radius * ct * cp * self.plane.y_axis; // cos(theta) * cos(phi)
         let dz = 0.0 * self.plane.normal; // 0.0
-        dx + dy + dz
+        let (su, sv) = self.scale();
+        (dx + dy + dz) * su * sv
     }
 }
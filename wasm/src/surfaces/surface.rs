@@ -1,15 +1,33 @@
-use nalgebra::Point3;
-use nalgebra::Vector3;
-
+use crate::domain::Domain;
 use crate::plane::Plane;
+use crate::quadrature::Quadrature;
 use crate::surface_tessellation::SurfaceTessellation;
+use crate::surfaces::curvature_line::{self, CurvatureLine, LineFamily};
+use crate::surfaces::fundamental_form::{FirstFundamentalForm, SecondFundamentalForm};
+use crate::surfaces::gauss_bonnet::{self, GaussBonnet};
+use crate::surfaces::geodesic::{self, Geodesic};
+use crate::surfaces::intrinsic::{self, ChristoffelSymbols, RiemannTensor};
 use crate::surfaces::surface_curvature::SurfaceCurvature;
+use crate::surfaces::umbilic::{self, Umbilic};
+use crate::types::Matrix2x2f;
+use crate::types::Point2f;
 use crate::types::Point3f;
+use crate::types::Vector2f;
 use crate::types::Vector3f;
 
 pub trait Surface {
     fn area(&self) -> f64 {
-        todo!()
+        self.integrate(&|_, _| 1.0, &Quadrature::default())
+    }
+
+    // native parameter domains that the normalized (u, v) in [0, 1] are mapped onto,
+    // periodic domains let curves on the surface wrap around the seam
+    fn u_domain(&self) -> Domain {
+        Domain::unit()
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::unit()
     }
 
     fn point_at(&self, u: f64, v: f64) -> Point3f;
@@ -27,9 +45,10 @@ pub trait Surface {
         (x, y)
     }
 
+    // unit normal, du x dv is not unit length unless du and dv are orthogonal
     fn normal_at(&self, u: f64, v: f64) -> Vector3f {
         let (x, y) = self.derivatives_at(u, v);
-        x.cross(&y)
+        x.cross(&y).normalize()
     }
 
     fn du_at(&self, u: f64, v: f64) -> Vector3f;
@@ -38,38 +57,222 @@ pub trait Surface {
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f;
     fn dvdv_at(&self, u: f64, v: f64) -> Vector3f;
 
-    // https://mathworld.wolfram.com/GaussianCurvature.html
-    fn curvature_at(&self, u: f64, v: f64) -> SurfaceCurvature<f64> {
-        let normal = self.normal_at(u, v);
+    // https://mathworld.wolfram.com/FirstFundamentalForm.html
+    fn first_fundamental_form_at(&self, u: f64, v: f64) -> FirstFundamentalForm {
         let du = self.du_at(u, v);
         let dv = self.dv_at(u, v);
-        let l = self.dudu_at(u, v).dot(&normal); // dudu * e
-        let m = self.dudv_at(u, v).dot(&normal); // dudv * e
-        let n = self.dvdv_at(u, v).dot(&normal); // dvdv * e
         let e = du.dot(&du); // du * du
         let f = du.dot(&dv); // du * dv
         let g = dv.dot(&dv); // dv * dv
+        FirstFundamentalForm::new(e, f, g)
+    }
+
+    // https://mathworld.wolfram.com/SecondFundamentalForm.html
+    fn second_fundamental_form_at(&self, u: f64, v: f64) -> SecondFundamentalForm {
+        let normal = self.normal_at(u, v);
+        let l = self.dudu_at(u, v).dot(&normal); // dudu * e
+        let m = self.dudv_at(u, v).dot(&normal); // dudv * e
+        let n = self.dvdv_at(u, v).dot(&normal); // dvdv * e
+        SecondFundamentalForm::new(l, m, n)
+    }
+
+    // Weingarten map (shape operator) in the (du, dv) basis: S = I^-1 II
+    // https://mathworld.wolfram.com/WeingartenEquations.html
+    fn shape_operator_at(&self, u: f64, v: f64) -> Matrix2x2f {
+        let first = self.first_fundamental_form_at(u, v);
+        self.second_fundamental_form_at(u, v).shape_operator(&first)
+    }
+
+    // https://mathworld.wolfram.com/GaussianCurvature.html
+    fn curvature_at(&self, u: f64, v: f64) -> SurfaceCurvature<f64> {
+        let normal = self.normal_at(u, v);
+        let du = self.du_at(u, v);
+        let dv = self.dv_at(u, v);
+        let first = self.first_fundamental_form_at(u, v);
+        let second = self.second_fundamental_form_at(u, v);
+        let FirstFundamentalForm { e, f, g } = first;
+        let SecondFundamentalForm { l, m, n } = second;
 
         // k1k2 = (LN - MM) / (EG - FF)
-        // (k1 + k1) / 2 = (EN + GL - 2FM) / 2(EG - FF)
+        // (k1 + k2) / 2 = (EN + GL - 2FM) / 2(EG - FF)
+        let gaussian = second.determinant() / first.determinant();
+        let mean = (e * n + g * l - 2.0 * f * m) / (2.0 * first.determinant());
+
+        // k = H ± √(H^2 - K)
+        let d = (mean * mean - gaussian).max(0.0).sqrt();
+        let (k1, k2) = (mean + d, mean - d);
+
+        // eigenvectors of the shape operator
+        let shape = second.shape_operator(&first);
+        let (d1, d2) = if d > 1e-10 * mean.abs().max(1.0) {
+            (shape_eigenvector(&shape, k1), shape_eigenvector(&shape, k2))
+        } else {
+            // umbilic: any direction is principal, take du and its orthogonal in the metric
+            (Vector2f::new(1.0, 0.0), Vector2f::new(-f, e))
+        };
+        let d1 = d1 / first.length(&d1);
+        let d2 = d2 / first.length(&d2);
+
         SurfaceCurvature {
             point: self.point_at(u, v),
             uv: (u, v),
             normal,
-            gaussian: (l * n - m * m) / (e * g - f * f),
-            mean: (e * n + g * l - 2.0 * f * m) / 2.0 * (e * g - f * f),
+            gaussian,
+            mean,
+            k1,
+            k2,
+            principal_directions_uv: (d1, d2),
+            principal_directions: (du * d1.x + dv * d1.y, du * d2.x + dv * d2.y),
         }
     }
 
     // https://mathworld.wolfram.com/AreaElement.html
     fn area_element_at(&self, u: f64, v: f64, delta_u: f64, delta_v: f64) -> f64 {
-        let du = self.du_at(u, v);
-        let dv = self.dv_at(u, v);
-        let e = du.dot(&du); // du * du
-        let f = du.dot(&dv); // du * dv
-        let g = dv.dot(&dv); // dv * dv
-                             // √(EG - FF)dudv
-        (e * g - f * f).sqrt() * delta_u * delta_v
+        // √(EG - FF)dudv
+        self.first_fundamental_form_at(u, v).area_element() * delta_u * delta_v
+    }
+
+    // ∬f dA of a scalar field given in normalized (u, v)
+    fn integrate(&self, field: &dyn Fn(f64, f64) -> f64, quadrature: &Quadrature) -> f64 {
+        self.integrate_over(field, &Domain::unit(), &Domain::unit(), quadrature)
+    }
+
+    // ∬f dA over the patch u x v of normalized (u, v)
+    fn integrate_over(
+        &self,
+        field: &dyn Fn(f64, f64) -> f64,
+        u: &Domain,
+        v: &Domain,
+        quadrature: &Quadrature,
+    ) -> f64 {
+        let (du, dv) = (u.length().abs(), v.length().abs());
+        quadrature.integrate(&|s, t| {
+            let (s, t) = (u.map(s), v.map(t));
+            field(s, t) * self.area_element_at(s, t, du, dv)
+        })
+    }
+
+    // center of mass of the surface as a thin shell
+    fn centroid(&self) -> Point3f {
+        let q = Quadrature::default();
+        let area = self.area();
+        let x = self.integrate(&|u, v| self.point_at(u, v).x, &q);
+        let y = self.integrate(&|u, v| self.point_at(u, v).y, &q);
+        let z = self.integrate(&|u, v| self.point_at(u, v).z, &q);
+        Point3f::new(x, y, z) / area
+    }
+
+    // enclosed volume of a closed surface by the divergence theorem, V = 1/3 ∬X・N dA,
+    // signed by the orientation of the normal (negative when it points inward)
+    fn volume(&self) -> f64 {
+        self.integrate(
+            &|u, v| self.point_at(u, v).coords.dot(&self.normal_at(u, v)) / 3.0,
+            &Quadrature::default(),
+        )
+    }
+
+    // center of mass of the enclosed solid, ∭x dV = 1/2 ∬x^2 N_x dA for each axis
+    fn volume_centroid(&self) -> Point3f {
+        let q = Quadrature::default();
+        let volume = self.volume();
+        let moment = |i: usize| {
+            self.integrate(
+                &|u, v| {
+                    let p = self.point_at(u, v);
+                    p[i] * p[i] * self.normal_at(u, v)[i] * 0.5
+                },
+                &q,
+            )
+        };
+        Point3f::new(moment(0), moment(1), moment(2)) / volume
+    }
+
+    // ∬K dA, 2πχ for a closed surface by Gauss-Bonnet
+    fn total_gaussian_curvature(&self) -> f64 {
+        self.integrate(
+            &|u, v| self.curvature_at(u, v).gaussian,
+            &Quadrature::default(),
+        )
+    }
+
+    // ∬H dA
+    fn total_mean_curvature(&self) -> f64 {
+        self.integrate(&|u, v| self.curvature_at(u, v).mean, &Quadrature::default())
+    }
+
+    // ∬K dA + ∮k_g ds + Σθ compared with 2πχ over the patch u x v of normalized (u, v),
+    // the whole surface for unit domains
+    fn gauss_bonnet(&self, u: &Domain, v: &Domain) -> GaussBonnet {
+        gauss_bonnet::gauss_bonnet(self, u, v)
+    }
+
+    // Γ^k_ij of the Levi-Civita connection, exact from the second derivatives,
+    // the metric alone gives the same up to the differencing of intrinsic::christoffel_symbols
+    // https://mathworld.wolfram.com/ChristoffelSymboloftheSecondKind.html
+    fn christoffel_symbols_at(&self, u: f64, v: f64) -> ChristoffelSymbols {
+        ChristoffelSymbols::from_matrices(geodesic::christoffel_symbols(self, u, v))
+    }
+
+    // from the metric alone
+    // https://mathworld.wolfram.com/RiemannTensor.html
+    fn riemann_tensor_at(&self, u: f64, v: f64) -> RiemannTensor {
+        intrinsic::riemann_tensor(self, u, v)
+    }
+
+    // gaussian curvature from the metric alone (Theorema Egregium),
+    // agrees with the extrinsic curvature_at(u, v).gaussian
+    fn intrinsic_gaussian_at(&self, u: f64, v: f64) -> f64 {
+        intrinsic::brioschi_gaussian_curvature(self, u, v)
+    }
+
+    // shoot a geodesic from (u, v) toward the direction projected on the tangent plane,
+    // integrating the geodesic equation for the given arc length
+    fn geodesic_from(&self, u: f64, v: f64, direction: &Vector3f, length: f64) -> Option<Geodesic> {
+        let (du, dv) = geodesic::uv_direction(self, u, v, direction)?;
+        let (uv, length) = geodesic::shoot(self, u, v, du, dv, length);
+        let tessellation = geodesic::tessellate(self, &uv);
+        Some(Geodesic {
+            tessellation,
+            uv,
+            length,
+        })
+    }
+
+    // the shortest geodesic between two uv points,
+    // tried from several initial guesses to escape local solutions
+    fn shortest_geodesic(&self, from: &Point2f, to: &Point2f) -> Option<Geodesic> {
+        geodesic::geodesics_between(self, from, to, 3)
+            .into_iter()
+            .next()
+    }
+
+    // trace a line of curvature or an asymptotic line through the seed in both directions,
+    // stopping at the boundary, umbilics (principal) or parabolic points (asymptotic)
+    fn curvature_line_from(
+        &self,
+        family: LineFamily,
+        seed: &Point2f,
+        step: f64,
+        length: f64,
+    ) -> Option<CurvatureLine> {
+        curvature_line::trace(self, family, seed, step, length)
+    }
+
+    // lines of the family evenly spaced in arc length along the transversal line through the seed
+    fn curvature_line_net(
+        &self,
+        family: LineFamily,
+        seed: &Point2f,
+        spacing: f64,
+        count: usize,
+        length: f64,
+    ) -> Vec<CurvatureLine> {
+        curvature_line::net(self, family, seed, spacing, count, length)
+    }
+
+    // isolated umbilics searched on a count x count grid, refined and classified
+    fn umbilics(&self, count: usize) -> Vec<Umbilic> {
+        umbilic::umbilics(self, count)
     }
 
     fn tessellate(&self, delta: Option<f64>) -> SurfaceTessellation {
@@ -81,10 +284,7 @@ pub trait Surface {
                 i as f64 / (count - 1) as f64
             })
             .collect();
-        let mut points: Vec<Point3<f32>> = vec![];
-        let mut normals: Vec<Vector3<f32>> = vec![];
-        let mut gauss_curvature: Vec<f32> = vec![];
-        let mut mean_curvature: Vec<f32> = vec![];
+        let mut curvatures: Vec<SurfaceCurvature<f64>> = vec![];
         let mut indices: Vec<usize> = vec![];
         for iu in 0..count {
             let i = iu * count;
@@ -92,13 +292,7 @@ pub trait Surface {
             for iv in 0..count {
                 let j = i + iv;
                 let v = *parameters.get(iv).unwrap();
-                let curvature = self.curvature_at(u, v);
-                // let point = self.point_at(u, v);
-                // let normal = self.normal_at(u, v);
-                points.push(curvature.point.cast());
-                normals.push(curvature.normal.cast());
-                gauss_curvature.push(curvature.gaussian as f32);
-                mean_curvature.push(curvature.mean as f32);
+                curvatures.push(self.curvature_at(u, v));
                 if iu < count - 1 && iv < count - 1 {
                     let k = j + count; // next row
                     indices.push(j);
@@ -112,14 +306,75 @@ pub trait Surface {
             }
         }
 
-        SurfaceTessellation::new(
-            count,
-            count,
-            points,
-            normals,
-            gauss_curvature,
-            mean_curvature,
-            indices,
-        )
+        SurfaceTessellation::new(count, count, &curvatures, indices)
+    }
+}
+
+// solve (S - kI)x = 0 taking the better conditioned row
+fn shape_eigenvector(s: &Matrix2x2f, k: f64) -> Vector2f {
+    let a = Vector2f::new(s[(0, 1)], k - s[(0, 0)]);
+    let b = Vector2f::new(k - s[(1, 1)], s[(1, 0)]);
+    if a.norm_squared() >= b.norm_squared() {
+        a
+    } else {
+        b
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::surfaces::{
+        cylinder::Cylinder, elliptic_paraboloid::EllipticParaboloid, helicoid::Helicoid,
+        hyperbolic_paraboloid::HyperbolicParaboloid, mobius::Mobius,
+        single_leaf_paraboloid::SingleLeafParaboloid, sphere::Sphere, torus::Torus, uv,
+    };
+
+    // the surfaces of the first eight SurfaceTypes
+    pub(crate) fn surfaces() -> Vec<Box<dyn Surface>> {
+        vec![
+            Box::new(Sphere::default()),
+            Box::new(Cylinder::default()),
+            Box::new(Torus::default()),
+            Box::new(Mobius::default()),
+            Box::new(Helicoid::default()),
+            Box::new(EllipticParaboloid::default()),
+            Box::new(HyperbolicParaboloid::default()),
+            Box::new(SingleLeafParaboloid::default()),
+        ]
+    }
+
+    // the derivatives are taken in the normalized (u, v) that point_at receives
+    #[test]
+    fn derivatives_agree_with_differences() {
+        let h = 1e-5;
+        for (index, surface) in surfaces().iter().enumerate() {
+            for (u, v) in uv::grid(5) {
+                let du = (surface.point_at(u + h, v) - surface.point_at(u - h, v)) / (2.0 * h);
+                let dv = (surface.point_at(u, v + h) - surface.point_at(u, v - h)) / (2.0 * h);
+                let dudu = (surface.du_at(u + h, v) - surface.du_at(u - h, v)) / (2.0 * h);
+                let dudv = (surface.du_at(u, v + h) - surface.du_at(u, v - h)) / (2.0 * h);
+                let dvdu = (surface.dv_at(u + h, v) - surface.dv_at(u - h, v)) / (2.0 * h);
+                let dvdv = (surface.dv_at(u, v + h) - surface.dv_at(u, v - h)) / (2.0 * h);
+                for (d, exact) in [
+                    (du, surface.du_at(u, v)),
+                    (dv, surface.dv_at(u, v)),
+                    (dudu, surface.dudu_at(u, v)),
+                    (dudv, surface.dudv_at(u, v)),
+                    (dvdu, surface.dudv_at(u, v)),
+                    (dvdv, surface.dvdv_at(u, v)),
+                ] {
+                    assert!(
+                        (d - exact).norm() < 1e-6 * exact.norm().max(1.0),
+                        "surface {} difference {:?} derivative {:?} at ({}, {})",
+                        index,
+                        d,
+                        exact,
+                        u,
+                        v
+                    );
+                }
+            }
+        }
     }
 }
@@ -1,5 +1,6 @@
 use nalgebra::Point3;
 use nalgebra::Scalar;
+use nalgebra::Vector2;
 use nalgebra::Vector3;
 
 pub struct SurfaceCurvature<T: Scalar> {
@@ -8,6 +9,22 @@ pub struct SurfaceCurvature<T: Scalar> {
     pub normal: Vector3<T>,
     pub gaussian: T,
     pub mean: T,
+    // principal curvatures k1 >= k2
+    pub k1: T,
+    pub k2: T,
+    // principal directions as (du, dv) and in space, unit length
+    pub principal_directions_uv: (Vector2<T>, Vector2<T>),
+    pub principal_directions: (Vector3<T>, Vector3<T>),
 }
 
 impl<T: Scalar> SurfaceCurvature<T> {}
+
+impl SurfaceCurvature<f64> {
+    // Euler's formula, theta is measured from the first principal direction
+    // k_n = k1 cos^2θ + k2 sin^2θ
+    pub fn normal_curvature(&self, theta: f64) -> f64 {
+        let c = theta.cos();
+        let s = theta.sin();
+        self.k1 * c * c + self.k2 * s * s
+    }
+}
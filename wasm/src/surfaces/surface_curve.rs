@@ -0,0 +1,195 @@
+use crate::curves::curve::Curve;
+use crate::darboux_frame::DarbouxFrame;
+use crate::domain::Domain;
+use crate::surfaces::parallel_transport::{self, ParallelTransport};
+use crate::surfaces::surface::Surface;
+use crate::types::*;
+
+// a curve on a surface: the x and y coordinates of the curve are used as (u, v)
+// r(s) = X(u(s), v(s))
+pub struct SurfaceCurve<'a, S: Surface + ?Sized, C: Curve + ?Sized> {
+    surface: &'a S,
+    curve: &'a C,
+}
+
+impl<'a, S: Surface + ?Sized, C: Curve + ?Sized> SurfaceCurve<'a, S, C> {
+    pub fn new(surface: &'a S, curve: &'a C) -> Self {
+        Self { surface, curve }
+    }
+
+    pub fn surface(&self) -> &S {
+        self.surface
+    }
+
+    pub fn curve(&self) -> &C {
+        self.curve
+    }
+
+    pub fn uv_at(&self, s: f64) -> Point2f {
+        let p = self.curve.point_at(s);
+        Point2f::new(p.x, p.y)
+    }
+
+    // (u', v')
+    fn uv_velocity_at(&self, s: f64) -> (f64, f64) {
+        let d = self.curve.velocity_at(s);
+        (d.x, d.y)
+    }
+
+    // (u'', v'')
+    fn uv_acceleration_at(&self, s: f64) -> (f64, f64) {
+        let d = self.curve.acceleration_at(s);
+        (d.x, d.y)
+    }
+
+    pub fn darboux_frame_at(&self, s: f64) -> DarbouxFrame {
+        let uv = self.uv_at(s);
+        let p = self.surface.point_at(uv.x, uv.y);
+        let t = self.tangent_at(s);
+        let n = self.surface.normal_at(uv.x, uv.y);
+        let u = n.cross(&t);
+        DarbouxFrame::new(&p, &t, &n, &u)
+    }
+
+    // curvature vector k = dT/dσ (σ: arc length)
+    fn curvature_vector_at(&self, s: f64) -> Vector3f {
+        let v = self.velocity_at(s);
+        let a = self.acceleration_at(s);
+        let t = v.normalize();
+        (a - t * a.dot(&t)) / v.norm_squared()
+    }
+
+    // k_n = k・N
+    pub fn normal_curvature_at(&self, s: f64) -> f64 {
+        let frame = self.darboux_frame_at(s);
+        self.curvature_vector_at(s).dot(&frame.normal)
+    }
+
+    // k_g = k・(N x T)
+    pub fn geodesic_curvature_at(&self, s: f64) -> f64 {
+        let frame = self.darboux_frame_at(s);
+        self.curvature_vector_at(s).dot(&frame.tangent_normal)
+    }
+
+    // dN/dσ = - k_n T - t_g (N x T)
+    pub fn geodesic_torsion_at(&self, s: f64) -> f64 {
+        let frame = self.darboux_frame_at(s);
+        let dn = self.normal_derivative_at(s) / self.velocity_at(s).norm();
+        -dn.dot(&frame.tangent_normal)
+    }
+
+    // transport the vector along the curve with the Levi-Civita connection
+    pub fn parallel_transport(&self, vector: &Vector3f, count: usize) -> ParallelTransport {
+        parallel_transport::parallel_transport(self.surface, self.curve, vector, count)
+    }
+
+    // rotation of a vector transported around the closed curve,
+    // which equals the enclosed integral of gaussian curvature (mod 2π)
+    pub fn holonomy_angle(&self, count: usize) -> f64 {
+        let uv = self.uv_at(0.0);
+        let vector = self.surface.du_at(uv.x, uv.y);
+        self.parallel_transport(&vector, count).holonomy_angle()
+    }
+
+    // ∬K dA over the uv region enclosed by the curve
+    pub fn enclosed_curvature(&self, count: usize) -> f64 {
+        parallel_transport::enclosed_curvature(self.surface, self.curve, count)
+    }
+
+    // derivative of the unit surface normal along the curve with respect to s
+    fn normal_derivative_at(&self, s: f64) -> Vector3f {
+        let uv = self.uv_at(s);
+        let (u, v) = (uv.x, uv.y);
+        let (du, dv) = self.uv_velocity_at(s);
+        let xu = self.surface.du_at(u, v);
+        let xv = self.surface.dv_at(u, v);
+        let xuu = self.surface.dudu_at(u, v);
+        let xuv = self.surface.dudv_at(u, v);
+        let xvv = self.surface.dvdv_at(u, v);
+        let n = xu.cross(&xv);
+        let dn = (xuu * du + xuv * dv).cross(&xv) + xu.cross(&(xuv * du + xvv * dv));
+        let unit = n.normalize();
+        (dn - unit * unit.dot(&dn)) / n.norm()
+    }
+}
+
+impl<'a, S: Surface + ?Sized, C: Curve + ?Sized> Curve for SurfaceCurve<'a, S, C> {
+    fn domain(&self) -> Domain {
+        self.curve.domain()
+    }
+
+    fn length(&self) -> f64 {
+        self.integral_length(None)
+    }
+
+    fn point_at(&self, s: f64) -> Point3f {
+        let uv = self.uv_at(s);
+        self.surface.point_at(uv.x, uv.y)
+    }
+
+    // r' = Xu u' + Xv v'
+    fn velocity_at(&self, s: f64) -> Vector3f {
+        let uv = self.uv_at(s);
+        let (du, dv) = self.uv_velocity_at(s);
+        self.surface.du_at(uv.x, uv.y) * du + self.surface.dv_at(uv.x, uv.y) * dv
+    }
+
+    // r'' = Xuu u'^2 + 2 Xuv u'v' + Xvv v'^2 + Xu u'' + Xv v''
+    fn acceleration_at(&self, s: f64) -> Vector3f {
+        let uv = self.uv_at(s);
+        let (u, v) = (uv.x, uv.y);
+        let (du, dv) = self.uv_velocity_at(s);
+        let (ddu, ddv) = self.uv_acceleration_at(s);
+        self.surface.dudu_at(u, v) * du * du
+            + self.surface.dudv_at(u, v) * 2.0 * du * dv
+            + self.surface.dvdv_at(u, v) * dv * dv
+            + self.surface.du_at(u, v) * ddu
+            + self.surface.dv_at(u, v) * ddv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::curves::ellipse_arc_curve::EllipseArcCurve;
+    use crate::plane::Plane;
+    use crate::surfaces::surface::tests::surfaces;
+    use core::f64::consts::TAU;
+
+    // k^2 = k_n^2 + k_g^2, with k from differences of the points on the surface
+    #[test]
+    fn curvature_decomposes_into_normal_and_geodesic() {
+        let plane = Plane {
+            origin: Point3f::new(0.5, 0.5, 0.0),
+            ..Plane::default()
+        };
+        let ellipse = EllipseArcCurve::new(plane, 0.3, 0.2, TAU);
+        let h = 1e-4;
+        for (index, surface) in surfaces().iter().enumerate() {
+            let curve = SurfaceCurve::new(surface.as_ref(), &ellipse);
+            for i in 0..16 {
+                let s = (i as f64 + 0.5) / 16.0;
+                let (p0, p1, p2) = (
+                    curve.point_at(s - h),
+                    curve.point_at(s),
+                    curve.point_at(s + h),
+                );
+                let velocity = (p2 - p0) / (2.0 * h);
+                let acceleration = (p2 - p1 * 2.0 + p0.coords) / (h * h);
+                let k = velocity.cross(&acceleration).norm() / velocity.norm().powi(3);
+                let kn = curve.normal_curvature_at(s);
+                let kg = curve.geodesic_curvature_at(s);
+                let decomposed = (kn * kn + kg * kg).sqrt();
+                assert!(
+                    (k - decomposed).abs() < 1e-4 * k.max(1.0),
+                    "surface {} k {} k_n {} k_g {} at {}",
+                    index,
+                    k,
+                    kn,
+                    kg,
+                    s
+                );
+            }
+        }
+    }
+}
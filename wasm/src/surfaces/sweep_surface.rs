@@ -0,0 +1,256 @@
+use crate::curves::arc_curve::ArcCurve;
+use crate::curves::curve::Curve;
+use crate::domain::Domain;
+use crate::expression::{Expression, Jet};
+use crate::plane::Plane;
+use crate::surfaces::surface::Surface;
+use crate::types::Point3f;
+use crate::types::Vector3f;
+use core::f64::consts::TAU;
+use std::collections::BTreeMap;
+
+// nodes of the rotation minimizing frame along the rail
+const FRAME_NODES: usize = 256;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SweepFrame {
+    // principal normal and binormal, undefined where the rail is straight
+    Frenet,
+    // no rotation around the tangent, by the double reflection method
+    // https://www.microsoft.com/en-us/research/publication/computation-rotation-minimizing-frames/
+    RotationMinimizing,
+}
+
+// (e, e', e'') of a vector along the rail
+type Moving = (Vector3f, Vector3f, Vector3f);
+
+// the planar profile, drawn in the xy plane around the origin, moved along the rail,
+// its x and y axes follow the normal and binormal of the frame scaled and twisted by the laws,
+// u runs along the profile and v along the rail
+pub struct SweepSurface {
+    profile: Box<dyn Curve>,
+    rail: Box<dyn Curve>,
+    frame: SweepFrame,
+    // scale(t) and twist(t) in radians of the normalized rail parameter t
+    scale: Expression,
+    twist: Expression,
+    // the first frame axis at the nodes of the rotation minimizing frame
+    nodes: Vec<Vector3f>,
+}
+
+impl SweepSurface {
+    pub fn new(profile: Box<dyn Curve>, rail: Box<dyn Curve>, frame: SweepFrame) -> Self {
+        let mut sweep = Self {
+            profile,
+            rail,
+            frame,
+            scale: Expression::parse("1").unwrap(),
+            twist: Expression::parse("0").unwrap(),
+            nodes: vec![],
+        };
+        if frame == SweepFrame::RotationMinimizing {
+            sweep.nodes = sweep.rotation_minimizing_nodes();
+        }
+        sweep
+    }
+
+    // scale(t) and twist(t) formulas of t in [0, 1] along the rail
+    pub fn with_laws(self, scale: &str, twist: &str) -> Result<Self, fasteval::Error> {
+        let scale = Expression::parse(scale)?;
+        let twist = Expression::parse(twist)?;
+        let sweep = Self {
+            scale,
+            twist,
+            ..self
+        };
+        sweep.law(&sweep.scale, 0.0)?;
+        sweep.law(&sweep.twist, 0.0)?;
+        Ok(sweep)
+    }
+
+    // the circle of the radius around the rail
+    pub fn tube(rail: Box<dyn Curve>, radius: f64) -> Self {
+        let circle = ArcCurve::new(Plane::default(), radius, TAU);
+        Self::new(Box::new(circle), rail, SweepFrame::RotationMinimizing)
+    }
+
+    pub fn rail(&self) -> &dyn Curve {
+        self.rail.as_ref()
+    }
+
+    fn law(&self, law: &Expression, t: f64) -> Result<Jet, fasteval::Error> {
+        let mut variables: BTreeMap<String, Jet> = BTreeMap::new();
+        variables.insert(String::from("t"), Jet::u(t));
+        law.jet(&variables)
+    }
+
+    // (T, T', T'') of the unit tangent, T'' by central differences
+    fn tangent_at(&self, v: f64) -> Moving {
+        let derivative = |v: f64| {
+            let r1 = self.rail.velocity_at(v);
+            let r2 = self.rail.acceleration_at(v);
+            let t = r1.normalize();
+            (t, (r2 - t * t.dot(&r2)) / r1.norm())
+        };
+        let h = 1e-4;
+        let (t, t1) = derivative(v);
+        let t2 = (derivative(v + h).1 - derivative(v - h).1) / (2.0 * h);
+        (t, t1, t2)
+    }
+
+    // any unit vector perpendicular to the tangent at the start
+    fn initial_axis(&self) -> Vector3f {
+        let t = self.rail.tangent_at(0.0);
+        let n = self.rail.normal_at(0.0);
+        if n.iter().all(|x| x.is_finite()) && n.norm() > 0.5 {
+            return n;
+        }
+        let helper = if t.x.abs() < 0.9 {
+            Vector3f::x()
+        } else {
+            Vector3f::y()
+        };
+        (helper - t * t.dot(&helper)).normalize()
+    }
+
+    // one double reflection step of the axis r from the rail at v0 to v1
+    fn reflect(&self, r: &Vector3f, v0: f64, v1: f64) -> Vector3f {
+        let reflection = |x: &Vector3f, n: &Vector3f| {
+            let c = n.dot(n);
+            if c > 0.0 {
+                x - n * (2.0 / c * n.dot(x))
+            } else {
+                *x
+            }
+        };
+        let v = self.rail.point_at(v1) - self.rail.point_at(v0);
+        let rl = reflection(r, &v);
+        let tl = reflection(&self.rail.tangent_at(v0), &v);
+        let w = self.rail.tangent_at(v1) - tl;
+        reflection(&rl, &w).normalize()
+    }
+
+    fn rotation_minimizing_nodes(&self) -> Vec<Vector3f> {
+        let mut nodes = vec![self.initial_axis()];
+        for i in 0..FRAME_NODES {
+            let v0 = i as f64 / FRAME_NODES as f64;
+            let v1 = (i + 1) as f64 / FRAME_NODES as f64;
+            let next = self.reflect(&nodes[i], v0, v1);
+            nodes.push(next);
+        }
+        nodes
+    }
+
+    // (e1, e1', e1'') of the first frame axis
+    fn axis_at(&self, v: f64) -> Moving {
+        match self.frame {
+            SweepFrame::Frenet => {
+                // the normal already depends on the second derivative of the rail
+                let h = 1e-3;
+                let (n0, n, n1) = (
+                    self.rail.normal_at(v - h),
+                    self.rail.normal_at(v),
+                    self.rail.normal_at(v + h),
+                );
+                (n, (n1 - n0) / (2.0 * h), (n1 - n * 2.0 + n0) / (h * h))
+            }
+            SweepFrame::RotationMinimizing => {
+                let i = ((v * FRAME_NODES as f64).floor().max(0.0) as usize).min(FRAME_NODES - 1);
+                let e = self.reflect(&self.nodes[i], i as f64 / FRAME_NODES as f64, v);
+                // e' = -(e・T') T keeps e from rotating around the tangent
+                let (t, t1, t2) = self.tangent_at(v);
+                let e1 = -t * e.dot(&t1);
+                let e2 = -t * (e1.dot(&t1) + e.dot(&t2)) - t1 * e.dot(&t1);
+                (e, e1, e2)
+            }
+        }
+    }
+
+    // the profile x and y axes at v, scaled and twisted by the laws
+    fn axes_at(&self, v: f64) -> (Moving, Moving) {
+        let (e, e1, e2) = self.axis_at(v);
+        let (t, t1, t2) = self.tangent_at(v);
+        // b = t x e
+        let b = t.cross(&e);
+        let b1 = t1.cross(&e) + t.cross(&e1);
+        let b2 = t2.cross(&e) + t1.cross(&e1) * 2.0 + t.cross(&e2);
+
+        let nan = Jet::constant(f64::NAN);
+        let scale = self.law(&self.scale, v).unwrap_or(nan);
+        let twist = self.law(&self.twist, v).unwrap_or(nan);
+        let c = scale * twist.cos();
+        let s = scale * twist.sin();
+        // p e + q b and its derivatives
+        let combine = |p: &Jet, q: &Jet| -> Moving {
+            (
+                e * p.value + b * q.value,
+                e * p.du + e1 * p.value + b * q.du + b1 * q.value,
+                e * p.duu
+                    + e1 * 2.0 * p.du
+                    + e2 * p.value
+                    + b * q.duu
+                    + b1 * 2.0 * q.du
+                    + b2 * q.value,
+            )
+        };
+        (combine(&c, &s), combine(&-s, &c))
+    }
+
+    // the profile in local coordinates
+    fn local(p: &Vector3f) -> (f64, f64) {
+        (p.x, p.y)
+    }
+}
+
+impl Surface for SweepSurface {
+    // periodic when the profile is closed
+    fn u_domain(&self) -> Domain {
+        let d = self.profile.domain();
+        let closed = (self.profile.point_at(0.0) - self.profile.point_at(1.0)).norm();
+        if closed <= 1e-9 * self.profile.velocity_at(0.0).norm().max(1.0) {
+            Domain::periodic(d.start(), d.end())
+        } else {
+            d
+        }
+    }
+
+    fn v_domain(&self) -> Domain {
+        self.rail.domain()
+    }
+
+    fn point_at(&self, u: f64, v: f64) -> Point3f {
+        let (x, y) = Self::local(&self.profile.point_at(u).coords);
+        let ((a, _, _), (b, _, _)) = self.axes_at(v);
+        self.rail.point_at(v) + a * x + b * y
+    }
+
+    fn du_at(&self, u: f64, v: f64) -> Vector3f {
+        let (x, y) = Self::local(&self.profile.velocity_at(u));
+        let ((a, _, _), (b, _, _)) = self.axes_at(v);
+        a * x + b * y
+    }
+
+    fn dv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (x, y) = Self::local(&self.profile.point_at(u).coords);
+        let ((_, a, _), (_, b, _)) = self.axes_at(v);
+        self.rail.velocity_at(v) + a * x + b * y
+    }
+
+    fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
+        let (x, y) = Self::local(&self.profile.acceleration_at(u));
+        let ((a, _, _), (b, _, _)) = self.axes_at(v);
+        a * x + b * y
+    }
+
+    fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (x, y) = Self::local(&self.profile.velocity_at(u));
+        let ((_, a, _), (_, b, _)) = self.axes_at(v);
+        a * x + b * y
+    }
+
+    fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
+        let (x, y) = Self::local(&self.profile.point_at(u).coords);
+        let ((_, _, a), (_, _, b)) = self.axes_at(v);
+        self.rail.acceleration_at(v) + a * x + b * y
+    }
+}
@@ -1,3 +1,4 @@
+use crate::domain::Domain;
 use crate::plane::Plane;
 use crate::surfaces::surface::Surface;
 use crate::types::Point3f;
@@ -21,6 +22,11 @@ impl Torus {
         Self { plane, a, b }
     }
 
+    // dtheta/du = dphi/dv
+    fn scale(&self) -> f64 {
+        TAU
+    }
+
     fn sin_cos(&self, u: f64, v: f64) -> (f64, f64, f64, f64) {
         let theta = u * TAU;
         let phi = v * TAU;
@@ -33,6 +39,14 @@ impl Torus {
 }
 
 impl Surface for Torus {
+    fn u_domain(&self) -> Domain {
+        Domain::periodic(0.0, TAU)
+    }
+
+    fn v_domain(&self) -> Domain {
+        Domain::periodic(0.0, TAU)
+    }
+
     fn point_at(&self, u: f64, v: f64) -> Point3f {
         let (st, ct, sp, cp) = self.sin_cos(u, v);
         let dx = (self.a * ct + self.b) * cp; // (acost + b) cosp
@@ -46,7 +60,7 @@ impl Surface for Torus {
         let dx = -self.a * st * cp; // - asint cosp
         let dy = -self.a * st * sp; // - asint sinp
         let dz = self.a * ct; // acost
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * self.scale()
     }
 
     fn dv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -54,7 +68,7 @@ impl Surface for Torus {
         let dx = -(self.a * ct + self.b) * sp; // - (acost + b) sinp
         let dy = (self.a * ct + self.b) * cp; // (acost + b) cosp
         let dz = 0.0; // 0
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * self.scale()
     }
 
     fn dudu_at(&self, u: f64, v: f64) -> Vector3f {
@@ -62,7 +76,7 @@ impl Surface for Torus {
         let dx = -self.a * ct * cp; // - acost cosp
         let dy = -self.a * ct * sp; // - acost sinp
         let dz = -self.a * st; // -asint
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * self.scale() * self.scale()
     }
 
     fn dvdv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -70,7 +84,7 @@ impl Surface for Torus {
         let dx = -(self.a * ct + self.b) * cp; // - (acost + b) cosp
         let dy = -(self.a * ct + self.b) * sp; // - (acost + b) sinp
         let dz = 0.0; // 0
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * self.scale() * self.scale()
     }
 
     fn dudv_at(&self, u: f64, v: f64) -> Vector3f {
@@ -78,6 +92,6 @@ impl Surface for Torus {
         let dx = self.a * st * sp; // asint sinp
         let dy = -self.a * st * cp; // - asint cosp
         let dz = 0.0; // 0.0
-        Vector3f::new(dx, dy, dz)
+        Vector3f::new(dx, dy, dz) * self.scale() * self.scale()
     }
 }
@@ -0,0 +1,223 @@
+use crate::surfaces::surface::Surface;
+use crate::surfaces::uv::wrap;
+use crate::types::*;
+use core::f64::consts::{PI, TAU};
+use wasm_bindgen::prelude::*;
+
+// generic umbilics of the principal line fields
+// https://en.wikipedia.org/wiki/Umbilical_point
+#[wasm_bindgen]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UmbilicType {
+    // index +1/2, one line ends at the umbilic
+    Lemon,
+    // index -1/2, three lines end at the umbilic
+    Star,
+    // index +1/2, three lines end at the umbilic
+    Monstar,
+    // any other index, e.g. +1 at the vertex of a surface of revolution
+    Nongeneric,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct Umbilic {
+    pub uv: Point2f,
+    pub kind: UmbilicType,
+    // index of the principal line field around the umbilic
+    pub index: f64,
+}
+
+// (EM - FL, EN - GL) vanishes iff the second fundamental form is proportional to the first
+fn discriminant<S: Surface + ?Sized>(surface: &S, p: &Point2f) -> Vector2f {
+    let first = surface.first_fundamental_form_at(p.x, p.y);
+    let second = surface.second_fundamental_form_at(p.x, p.y);
+    Vector2f::new(
+        first.e * second.m - first.f * second.l,
+        first.e * second.n - first.g * second.l,
+    )
+}
+
+// (k1 - k2) / (|k1| + |k2|), zero at umbilics
+fn relative_difference<S: Surface + ?Sized>(surface: &S, p: &Point2f) -> f64 {
+    let c = surface.curvature_at(p.x, p.y);
+    let scale = c.k1.abs() + c.k2.abs();
+    if scale > 0.0 {
+        (c.k1 - c.k2) / scale
+    } else {
+        0.0
+    }
+}
+
+fn newton<S: Surface + ?Sized>(surface: &S, start: &Point2f, max_step: f64) -> Option<Point2f> {
+    let h = 1e-6;
+    let mut p = *start;
+    for _ in 0..32 {
+        if relative_difference(surface, &p) < 1e-10 {
+            break;
+        }
+        let f = discriminant(surface, &p);
+        let fu = (discriminant(surface, &Point2f::new(p.x + h, p.y))
+            - discriminant(surface, &Point2f::new(p.x - h, p.y)))
+            / (2.0 * h);
+        let fv = (discriminant(surface, &Point2f::new(p.x, p.y + h))
+            - discriminant(surface, &Point2f::new(p.x, p.y - h)))
+            / (2.0 * h);
+        let jacobian = Matrix2x2f::new(fu.x, fv.x, fu.y, fv.y);
+        // degenerate umbilics (e.g. on an axis of revolution) have a singular jacobian
+        let mut step = match jacobian.try_inverse() {
+            Some(inverse) => -(inverse * f),
+            None => break,
+        };
+        if step.norm() > max_step {
+            step *= max_step / step.norm();
+        }
+        p += step;
+        if step.norm() < 1e-12 {
+            break;
+        }
+    }
+    if relative_difference(surface, &p) < 1e-6 {
+        Some(p)
+    } else {
+        None
+    }
+}
+
+// angle of the first principal direction in the uv plane
+fn principal_angle<S: Surface + ?Sized>(surface: &S, p: &Point2f) -> f64 {
+    let (d1, _) = surface.curvature_at(p.x, p.y).principal_directions_uv;
+    d1.y.atan2(d1.x)
+}
+
+// wrap into (-π, π]
+fn wrap_angle(a: f64) -> f64 {
+    let a = a.rem_euclid(TAU);
+    if a > PI {
+        a - TAU
+    } else {
+        a
+    }
+}
+
+// the index from the turning of the line field on a small circle around the umbilic,
+// and lemon and monstar told apart by the number of radial directions of the field
+pub fn classify<S: Surface + ?Sized>(surface: &S, uv: &Point2f, radius: f64) -> (UmbilicType, f64) {
+    let count = 128;
+    let samples: Vec<(f64, f64)> = (0..=count)
+        .map(|i| {
+            let alpha = TAU * i as f64 / count as f64;
+            let p = uv + Vector2f::new(alpha.cos(), alpha.sin()) * radius;
+            (alpha, principal_angle(surface, &p))
+        })
+        .collect();
+
+    // a line field turns by multiples of π, so follow the doubled angle
+    let turning: f64 = samples
+        .windows(2)
+        .map(|w| wrap_angle(2.0 * (w[1].1 - w[0].1)))
+        .sum();
+    let index = (turning / TAU).round() * 0.5;
+
+    // directions where the field is radial: 2(θ - α) crosses zero
+    let radial = samples
+        .windows(2)
+        .filter(|w| {
+            let a = wrap_angle(2.0 * (w[0].1 - w[0].0));
+            let b = wrap_angle(2.0 * (w[1].1 - w[1].0));
+            (a <= 0.0) != (b <= 0.0) && (a - b).abs() < PI
+        })
+        .count();
+
+    let kind = if index == -0.5 {
+        UmbilicType::Star
+    } else if index == 0.5 && radial >= 3 {
+        UmbilicType::Monstar
+    } else if index == 0.5 {
+        UmbilicType::Lemon
+    } else {
+        UmbilicType::Nongeneric
+    };
+    (kind, index)
+}
+
+// isolated umbilics from local minima of (k1 - k2) / (|k1| + |k2|) on a count x count grid,
+// refined with Newton's method, totally umbilic regions (planes, spheres) are skipped
+pub fn umbilics<S: Surface + ?Sized>(surface: &S, count: usize) -> Vec<Umbilic> {
+    let count = count.max(3);
+    let cell = 1.0 / (count - 1) as f64;
+    let grid: Vec<Vec<f64>> = (0..count)
+        .map(|i| {
+            (0..count)
+                .map(|j| {
+                    relative_difference(surface, &Point2f::new(i as f64 * cell, j as f64 * cell))
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut found: Vec<Umbilic> = vec![];
+    for i in 0..count {
+        for j in 0..count {
+            let d = grid[i][j];
+            let neighbors: Vec<f64> = (-1..=1)
+                .flat_map(|di: i32| (-1..=1).map(move |dj: i32| (di, dj)))
+                .filter(|&(di, dj)| di != 0 || dj != 0)
+                .filter_map(|(di, dj)| {
+                    let ni = i as i32 + di;
+                    let nj = j as i32 + dj;
+                    if ni < 0 || nj < 0 || ni >= count as i32 || nj >= count as i32 {
+                        None
+                    } else {
+                        Some(grid[ni as usize][nj as usize])
+                    }
+                })
+                .collect();
+            let minimum = neighbors.iter().all(|n| d <= *n);
+            let isolated = neighbors.iter().any(|n| *n > 1e-6);
+            if !minimum || !isolated {
+                continue;
+            }
+
+            let start = Point2f::new(i as f64 * cell, j as f64 * cell);
+            let uv = match newton(surface, &start, cell).and_then(|p| wrap(surface, &p)) {
+                Some(uv) => uv,
+                None => continue,
+            };
+            if found.iter().any(|u| (u.uv - uv).norm() < cell * 1e-3) {
+                continue;
+            }
+            let (kind, index) = classify(surface, &uv, cell * 1e-2);
+            found.push(Umbilic { uv, kind, index });
+        }
+    }
+    found
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::Domain;
+    use crate::plane::Plane;
+    use crate::surfaces::elliptic_paraboloid::EllipticParaboloid;
+
+    // z = x^2 / 2p + y^2 / 2q with p = 2, q = 1 / 2 has two lemons at x = 0, y = ±√(q (p - q))
+    #[test]
+    fn lemons_of_elliptic_paraboloid() {
+        let surface = EllipticParaboloid::new(Plane::default(), 2.0, 1.0, Domain::new(-2.0, 2.0));
+        for count in [24, 33, 48] {
+            let found = umbilics(&surface, count);
+            assert_eq!(
+                found.len(),
+                2,
+                "{} umbilics on the {} grid",
+                found.len(),
+                count
+            );
+            for (umbilic, y) in found.iter().zip([-0.75f64.sqrt(), 0.75f64.sqrt()]) {
+                let expected = Point2f::new(0.5, (y + 2.0) / 4.0);
+                assert!((umbilic.uv - expected).norm() < 1e-8, "{:?}", umbilic.uv);
+                assert_eq!(umbilic.kind, UmbilicType::Lemon);
+                assert_eq!(umbilic.index, 0.5);
+            }
+        }
+    }
+}
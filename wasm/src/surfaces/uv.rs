@@ -0,0 +1,59 @@
+use crate::surfaces::surface::Surface;
+use crate::types::Point2f;
+
+// the periodic flags of the u and v domains
+fn periodic<S: Surface + ?Sized>(surface: &S) -> [bool; 2] {
+    [
+        surface.u_domain().is_periodic(),
+        surface.v_domain().is_periodic(),
+    ]
+}
+
+// wrap periodic coordinates into [0, 1), None if a non periodic one left [0, 1]
+pub(crate) fn wrap<S: Surface + ?Sized>(surface: &S, p: &Point2f) -> Option<Point2f> {
+    let mut next = *p;
+    for (i, periodic) in periodic(surface).iter().enumerate() {
+        if *periodic {
+            next[i] = next[i].rem_euclid(1.0);
+        } else if next[i] < 0.0 || next[i] > 1.0 {
+            return None;
+        }
+    }
+    Some(next)
+}
+
+// the fraction of the step from p0 to p1 that stays inside [0, 1] on non periodic coordinates
+pub(crate) fn boundary_fraction<S: Surface + ?Sized>(
+    surface: &S,
+    p0: &Point2f,
+    p1: &Point2f,
+) -> f64 {
+    let mut t: f64 = 1.0;
+    for (i, periodic) in periodic(surface).iter().enumerate() {
+        let d = p1[i] - p0[i];
+        if *periodic || d == 0.0 {
+            continue;
+        }
+        if p1[i] < 0.0 {
+            t = t.min(-p0[i] / d);
+        } else if p1[i] > 1.0 {
+            t = t.min((1.0 - p0[i]) / d);
+        }
+    }
+    t.clamp(0.0, 1.0)
+}
+
+// the point where the segment leaves the non periodic sides of the unit square
+pub(crate) fn clip<S: Surface + ?Sized>(surface: &S, p0: &Point2f, p1: &Point2f) -> Point2f {
+    let p = p0 + (p1 - p0) * boundary_fraction(surface, p0, p1);
+    Point2f::new(p.x.clamp(0.0, 1.0), p.y.clamp(0.0, 1.0))
+}
+
+// the centers of the cells of a count x count grid over the unit square
+pub(crate) fn grid(count: usize) -> Vec<(f64, f64)> {
+    let count = count.max(1);
+    let parameter = move |i: usize| (i as f64 + 0.5) / count as f64;
+    (0..count)
+        .flat_map(|i| (0..count).map(move |j| (parameter(i), parameter(j))))
+        .collect()
+}
@@ -0,0 +1,449 @@
+use crate::surface_tessellation::SurfaceTessellation;
+use crate::types::{Point3f, Vector3f};
+use core::f64::consts::{FRAC_PI_2, PI, TAU};
+use std::collections::HashMap;
+
+// the half of an edge belonging to one triangle, the three of triangle f are 3 f, 3 f + 1 and 3 f + 2
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct HalfEdge {
+    // the vertex it starts from
+    pub origin: usize,
+    // the opposite half in the neighbouring triangle, None on the boundary
+    pub twin: Option<usize>,
+}
+
+// a consistently oriented manifold triangle mesh with half-edge connectivity
+// and the discrete curvature operators of Meyer, Desbrun, Schröder and Barr,
+// https://doi.org/10.1007/978-3-662-05105-4_2
+pub struct TriangleMesh {
+    points: Vec<Point3f>,
+    half_edges: Vec<HalfEdge>,
+    // an outgoing half-edge of each vertex, the one on the boundary if any, None for unused vertices
+    outgoing: Vec<Option<usize>>,
+    // the vertex that stands for each one after welding, itself unless it was merged
+    representatives: Vec<usize>,
+}
+
+// the per-vertex sums over the incident corners of the triangles
+struct Accumulation {
+    angles: Vec<f64>,
+    voronoi: Vec<f64>,
+    mixed: Vec<f64>,
+    // Σ (cot α + cot β) (x_i - x_j)
+    laplacian: Vec<Vector3f>,
+    // Σ of the area vectors
+    normals: Vec<Vector3f>,
+}
+
+impl TriangleMesh {
+    // None if an index is out of range, or an edge is shared by more than two triangles
+    // or by two of opposite orientation, triangles with repeated vertices are dropped
+    pub fn new(points: Vec<Point3f>, triangles: Vec<[usize; 3]>) -> Option<Self> {
+        let representatives = (0..points.len()).collect();
+        Self::connect(points, triangles, representatives)
+    }
+
+    // flat x, y, z coordinates and three vertex indices per triangle
+    pub fn from_arrays(positions: &[f64], indices: &[usize]) -> Option<Self> {
+        if !positions.len().is_multiple_of(3) || !indices.len().is_multiple_of(3) {
+            return None;
+        }
+        let points = positions
+            .chunks(3)
+            .map(|p| Point3f::new(p[0], p[1], p[2]))
+            .collect();
+        let triangles = indices.chunks(3).map(|t| [t[0], t[1], t[2]]).collect();
+        Self::new(points, triangles)
+    }
+
+    // the mesh of a tessellation with the coincident vertices along seams and poles welded,
+    // the vertex indices stay those of the tessellation so that the discrete curvatures
+    // compare with its analytic ones vertex by vertex,
+    // the triangles are turned to agree with the normals of the tessellation
+    pub fn from_tessellation(tessellation: &SurfaceTessellation) -> Option<Self> {
+        let points: Vec<Point3f> = tessellation.vertices().iter().map(|p| p.cast()).collect();
+        let normals: Vec<Vector3f> = tessellation
+            .vertex_normals()
+            .iter()
+            .map(|n| n.cast())
+            .collect();
+        let representatives = weld(&points);
+        let mut triangles: Vec<[usize; 3]> = tessellation
+            .triangles()
+            .chunks(3)
+            .map(|t| {
+                [
+                    representatives[t[0]],
+                    representatives[t[1]],
+                    representatives[t[2]],
+                ]
+            })
+            .collect();
+        let agreement: f64 = triangles
+            .iter()
+            .map(|[a, b, c]| {
+                let area = (points[*b] - points[*a]).cross(&(points[*c] - points[*a]));
+                let normal = normals[*a] + normals[*b] + normals[*c];
+                area.dot(&normal).signum()
+            })
+            .filter(|s| s.is_finite())
+            .sum();
+        if agreement < 0.0 {
+            for t in triangles.iter_mut() {
+                t.swap(1, 2);
+            }
+        }
+        Self::connect(points, triangles, representatives)
+    }
+
+    fn connect(
+        points: Vec<Point3f>,
+        triangles: Vec<[usize; 3]>,
+        representatives: Vec<usize>,
+    ) -> Option<Self> {
+        let n = points.len();
+        let triangles: Vec<[usize; 3]> = triangles
+            .into_iter()
+            .filter(|[a, b, c]| a != b && b != c && c != a)
+            .collect();
+        if triangles.iter().flatten().any(|i| *i >= n) {
+            return None;
+        }
+        let mut half_edges: Vec<HalfEdge> = triangles
+            .iter()
+            .flatten()
+            .map(|origin| HalfEdge {
+                origin: *origin,
+                twin: None,
+            })
+            .collect();
+        let mut directed: HashMap<(usize, usize), usize> = HashMap::new();
+        for h in 0..half_edges.len() {
+            let key = (half_edges[h].origin, half_edges[next(h)].origin);
+            if directed.insert(key, h).is_some() {
+                return None;
+            }
+        }
+        for h in 0..half_edges.len() {
+            let (a, b) = (half_edges[h].origin, half_edges[next(h)].origin);
+            half_edges[h].twin = directed.get(&(b, a)).copied();
+        }
+        let mut outgoing: Vec<Option<usize>> = vec![None; n];
+        for (h, e) in half_edges.iter().enumerate() {
+            let o = &mut outgoing[e.origin];
+            if o.is_none() || e.twin.is_none() {
+                *o = Some(h);
+            }
+        }
+        Some(Self {
+            points,
+            half_edges,
+            outgoing,
+            representatives,
+        })
+    }
+
+    pub fn points(&self) -> &[Point3f] {
+        &self.points
+    }
+
+    pub fn half_edges(&self) -> &[HalfEdge] {
+        &self.half_edges
+    }
+
+    pub fn triangles_count(&self) -> usize {
+        self.half_edges.len() / 3
+    }
+
+    pub fn vertices_count(&self) -> usize {
+        self.points.len()
+    }
+
+    // the vertices of the triangle in order
+    pub fn triangle(&self, f: usize) -> [usize; 3] {
+        [
+            self.half_edges[3 * f].origin,
+            self.half_edges[3 * f + 1].origin,
+            self.half_edges[3 * f + 2].origin,
+        ]
+    }
+
+    pub fn next(&self, h: usize) -> usize {
+        next(h)
+    }
+
+    pub fn previous(&self, h: usize) -> usize {
+        previous(h)
+    }
+
+    // the vertex the half-edge points to
+    pub fn target(&self, h: usize) -> usize {
+        self.half_edges[next(h)].origin
+    }
+
+    // the vertex this one was welded to, itself if it was kept
+    pub fn representative(&self, v: usize) -> usize {
+        self.representatives[v]
+    }
+
+    pub fn is_boundary_vertex(&self, v: usize) -> bool {
+        let v = self.representatives[v];
+        self.outgoing[v].is_none_or(|h| self.half_edges[h].twin.is_none())
+    }
+
+    // the outgoing half-edges around the vertex, starting on the boundary if it lies there
+    pub fn outgoing_half_edges(&self, v: usize) -> Vec<usize> {
+        let v = self.representatives[v];
+        let mut edges = vec![];
+        if let Some(first) = self.outgoing[v] {
+            let mut h = first;
+            loop {
+                edges.push(h);
+                match self.half_edges[previous(h)].twin {
+                    Some(t) if t != first => h = t,
+                    _ => break,
+                }
+            }
+        }
+        edges
+    }
+
+    // the neighbouring vertices in order around the vertex
+    pub fn one_ring(&self, v: usize) -> Vec<usize> {
+        let edges = self.outgoing_half_edges(v);
+        let mut ring: Vec<usize> = edges.iter().map(|h| self.target(*h)).collect();
+        // the last neighbour of a boundary vertex closes no edge of its own
+        if let Some(last) = edges.last() {
+            if self.half_edges[previous(*last)].twin.is_none() {
+                ring.push(self.half_edges[previous(*last)].origin);
+            }
+        }
+        ring
+    }
+
+    fn accumulate(&self) -> Accumulation {
+        let n = self.points.len();
+        let mut a = Accumulation {
+            angles: vec![0.0; n],
+            voronoi: vec![0.0; n],
+            mixed: vec![0.0; n],
+            laplacian: vec![Vector3f::zeros(); n],
+            normals: vec![Vector3f::zeros(); n],
+        };
+        for f in 0..self.triangles_count() {
+            let t = self.triangle(f);
+            let p = t.map(|i| self.points[i]);
+            let cross = (p[1] - p[0]).cross(&(p[2] - p[0]));
+            let area = cross.norm() * 0.5;
+            // the angle and its cotangent at each corner
+            let corner = |i: usize| {
+                let (e1, e2) = (p[(i + 1) % 3] - p[i], p[(i + 2) % 3] - p[i]);
+                let (dot, sin) = (e1.dot(&e2), e1.cross(&e2).norm());
+                (sin.atan2(dot), dot / sin)
+            };
+            let corners = [corner(0), corner(1), corner(2)];
+            let obtuse = corners.iter().position(|(angle, _)| *angle > FRAC_PI_2);
+            for i in 0..3 {
+                let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+                let v = t[i];
+                a.angles[v] += corners[i].0;
+                // the edges to j and k weighted by the cotangents of the angles opposite them
+                let voronoi = ((p[j] - p[i]).norm_squared() * corners[k].1
+                    + (p[k] - p[i]).norm_squared() * corners[j].1)
+                    / 8.0;
+                a.voronoi[v] += voronoi;
+                a.mixed[v] += match obtuse {
+                    None => voronoi,
+                    Some(o) if o == i => area / 2.0,
+                    Some(_) => area / 4.0,
+                };
+                a.laplacian[v] += (p[i] - p[j]) * corners[k].1 + (p[i] - p[k]) * corners[j].1;
+                a.normals[v] += cross;
+            }
+        }
+        a
+    }
+
+    // per vertex of the original indexing from the values of the representatives
+    fn spread<T: Copy>(&self, values: Vec<T>) -> Vec<T> {
+        self.representatives.iter().map(|r| values[*r]).collect()
+    }
+
+    // 2 π - Σ θ at interior vertices, π - Σ θ on the boundary
+    pub fn angle_defects(&self) -> Vec<f64> {
+        let angles = self.accumulate().angles;
+        let defects = (0..self.points.len())
+            .map(|v| {
+                let full = if self.is_boundary_vertex(v) { PI } else { TAU };
+                full - angles[v]
+            })
+            .collect();
+        self.spread(defects)
+    }
+
+    // the area of the Voronoi cell around each vertex, from the circumcenters of its triangles
+    pub fn voronoi_areas(&self) -> Vec<f64> {
+        self.spread(self.accumulate().voronoi)
+    }
+
+    // the Voronoi area with the centers of obtuse triangles moved to the midpoint of their long edge,
+    // the mixed areas tile the surface
+    pub fn mixed_areas(&self) -> Vec<f64> {
+        self.spread(self.accumulate().mixed)
+    }
+
+    // the area weighted normals of the incident triangles
+    pub fn vertex_normals(&self) -> Vec<Vector3f> {
+        let normals = self
+            .accumulate()
+            .normals
+            .iter()
+            .map(|n| n.normalize())
+            .collect();
+        self.spread(normals)
+    }
+
+    // Σ (cot α + cot β) (x_i - x_j) / 2 A_mixed by the cotangent Laplacian, 2 H n for the mean curvature
+    pub fn mean_curvature_normals(&self) -> Vec<Vector3f> {
+        let a = self.accumulate();
+        let normals = a
+            .laplacian
+            .iter()
+            .zip(a.mixed.iter())
+            .map(|(l, area)| l / (2.0 * area))
+            .collect();
+        self.spread(normals)
+    }
+
+    // the angle defect over the mixed area
+    pub fn gaussian_curvatures(&self) -> Vec<f64> {
+        let areas = self.mixed_areas();
+        self.angle_defects()
+            .iter()
+            .zip(areas.iter())
+            .map(|(d, a)| d / a)
+            .collect()
+    }
+
+    // the mean curvature signed against the vertex normal as for the analytic surfaces,
+    // negative where the surface bends away from its normal
+    pub fn mean_curvatures(&self) -> Vec<f64> {
+        self.mean_curvature_normals()
+            .iter()
+            .zip(self.vertex_normals().iter())
+            .map(|(k, n)| -k.dot(n) / 2.0)
+            .collect()
+    }
+
+    // mean |K_discrete - K| and |H_discrete - H| over the interior vertices of the mesh of the tessellation,
+    // the angle defect and the cotangent Laplacian against its analytic curvatures
+    pub fn curvature_deviation(tessellation: &SurfaceTessellation) -> Option<(f64, f64)> {
+        let mesh = Self::from_tessellation(tessellation)?;
+        let (gaussian, mean) = (mesh.gaussian_curvatures(), mesh.mean_curvatures());
+        let interior: Vec<usize> = (0..mesh.vertices_count())
+            .filter(|v| mesh.representative(*v) == *v && !mesh.is_boundary_vertex(*v))
+            .collect();
+        let deviation = |discrete: &[f64], analytic: &[f32]| {
+            interior
+                .iter()
+                .map(|v| (discrete[*v] - analytic[*v] as f64).abs())
+                .sum::<f64>()
+                / interior.len() as f64
+        };
+        Some((
+            deviation(&gaussian, tessellation.gaussian_curvatures()),
+            deviation(&mean, tessellation.mean_curvatures()),
+        ))
+    }
+}
+
+fn next(h: usize) -> usize {
+    h - h % 3 + (h + 1) % 3
+}
+
+fn previous(h: usize) -> usize {
+    h - h % 3 + (h + 2) % 3
+}
+
+// the first vertex within a millionth of the size of the mesh for each vertex,
+// looked up in the neighbouring cells of a grid of that spacing
+fn weld(points: &[Point3f]) -> Vec<usize> {
+    let (min, max) = points.iter().fold(
+        (
+            Point3f::new(f64::MAX, f64::MAX, f64::MAX),
+            Point3f::new(f64::MIN, f64::MIN, f64::MIN),
+        ),
+        |(min, max), p| (min.inf(p), max.sup(p)),
+    );
+    let tolerance = ((max - min).norm() * 1e-6).max(f64::MIN_POSITIVE);
+    let cell = |p: &Point3f| {
+        let c = p.coords / tolerance;
+        (c.x.floor() as i64, c.y.floor() as i64, c.z.floor() as i64)
+    };
+    let mut grid: HashMap<(i64, i64, i64), Vec<usize>> = HashMap::new();
+    let mut representatives = Vec::with_capacity(points.len());
+    for (i, p) in points.iter().enumerate() {
+        let (x, y, z) = cell(p);
+        let found = (-1..=1)
+            .flat_map(|dx| (-1..=1).flat_map(move |dy| (-1..=1).map(move |dz| (dx, dy, dz))))
+            .filter_map(|(dx, dy, dz)| grid.get(&(x + dx, y + dy, z + dz)))
+            .flatten()
+            .find(|j| (points[**j] - p).norm() <= tolerance)
+            .copied();
+        match found {
+            Some(j) => representatives.push(j),
+            None => {
+                grid.entry((x, y, z)).or_default().push(i);
+                representatives.push(i);
+            }
+        }
+    }
+    representatives
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::surfaces::{
+        implicit_surface::ImplicitSurface, sphere::Sphere, surface::Surface, torus::Torus,
+    };
+
+    // the angle defect and the cotangent Laplacian approach the analytic curvatures
+    // at second order in the spacing of the tessellation
+    fn assert_converges<S: Surface>(surface: &S) {
+        let deviation =
+            |delta| TriangleMesh::curvature_deviation(&surface.tessellate(Some(delta))).unwrap();
+        let (coarse, fine) = (deviation(0.02), deviation(0.01));
+        for (c, f) in [(coarse.0, fine.0), (coarse.1, fine.1)] {
+            assert!(c < 2e-3, "deviation {} at spacing 0.02", c);
+            assert!(f < c / 3.0, "deviation {} at spacing 0.01 after {}", f, c);
+        }
+    }
+
+    #[test]
+    fn discrete_curvatures_converge_on_sphere() {
+        assert_converges(&Sphere::default());
+    }
+
+    #[test]
+    fn discrete_curvatures_converge_on_torus() {
+        assert_converges(&Torus::default());
+    }
+
+    // the marched sphere makes a closed mesh of χ = 2 whose angle defects add up to 4π
+    #[test]
+    fn marched_sphere_is_closed() {
+        let tessellation = ImplicitSurface::sphere(2.0).tessellate(16);
+        let mesh = TriangleMesh::from_tessellation(&tessellation).unwrap();
+        let vertices: Vec<usize> = (0..mesh.vertices_count())
+            .filter(|v| mesh.representative(*v) == *v && !mesh.outgoing_half_edges(*v).is_empty())
+            .collect();
+        assert!(vertices.iter().all(|v| !mesh.is_boundary_vertex(*v)));
+        assert!(mesh.half_edges().iter().all(|h| h.twin.is_some()));
+        let edges = mesh.half_edges().len() / 2;
+        let euler = vertices.len() as i64 - edges as i64 + mesh.triangles_count() as i64;
+        assert_eq!(euler, 2);
+        let defects: f64 = mesh.angle_defects().iter().sum();
+        assert!((defects - 2.0 * TAU).abs() < 1e-9, "Σ defects {}", defects);
+    }
+}
@@ -1,8 +1,11 @@
-use nalgebra::{Matrix2, Matrix2x1, Point2, Point3, Vector3, Vector4};
+use nalgebra::{Complex, Matrix2, Matrix2x1, Matrix3, Point2, Point3, Vector2, Vector3, Vector4};
 
 pub type Point2f = Point2<f64>;
 pub type Point3f = Point3<f64>;
+pub type Vector2f = Vector2<f64>;
 pub type Vector3f = Vector3<f64>;
 pub type Vector4f = Vector4<f64>;
 pub type Matrix2x2f = Matrix2<f64>;
 pub type Matrix2x1f = Matrix2x1<f64>;
+pub type Matrix3x3f = Matrix3<f64>;
+pub type Complexf = Complex<f64>;
@@ -0,0 +1,43 @@
+use nalgebra::{Point3, Vector3};
+use wasm_bindgen::prelude::*;
+
+// umbilic points marked on a surface, kinds are UmbilicType values
+#[wasm_bindgen]
+pub struct UmbilicTessellation {
+    points: Vec<Point3<f32>>,
+    normals: Vec<Vector3<f32>>,
+    kinds: Vec<u32>,
+}
+
+impl UmbilicTessellation {
+    pub fn new(points: Vec<Point3<f32>>, normals: Vec<Vector3<f32>>, kinds: Vec<u32>) -> Self {
+        Self {
+            points,
+            normals,
+            kinds,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl UmbilicTessellation {
+    pub fn count(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn stride(&self) -> usize {
+        3
+    }
+
+    pub fn points(&self) -> *const Point3<f32> {
+        self.points.as_ptr()
+    }
+
+    pub fn normals(&self) -> *const Vector3<f32> {
+        self.normals.as_ptr()
+    }
+
+    pub fn kinds(&self) -> *const u32 {
+        self.kinds.as_ptr()
+    }
+}
@@ -0,0 +1,55 @@
+use nalgebra::{Point3, Vector3};
+use wasm_bindgen::prelude::*;
+
+// vectors attached to points, e.g. a vector parallel transported along a path
+#[wasm_bindgen]
+pub struct VectorFieldTessellation {
+    points: Vec<Point3<f32>>,
+    vectors: Vec<Vector3<f32>>,
+    angle: f64,
+    enclosed_curvature: f64,
+}
+
+impl VectorFieldTessellation {
+    pub fn new(
+        points: Vec<Point3<f32>>,
+        vectors: Vec<Vector3<f32>>,
+        angle: f64,
+        enclosed_curvature: f64,
+    ) -> Self {
+        Self {
+            points,
+            vectors,
+            angle,
+            enclosed_curvature,
+        }
+    }
+}
+
+#[wasm_bindgen]
+impl VectorFieldTessellation {
+    pub fn count(&self) -> usize {
+        self.points.len()
+    }
+
+    pub fn stride(&self) -> usize {
+        3
+    }
+
+    pub fn points(&self) -> *const Point3<f32> {
+        self.points.as_ptr()
+    }
+
+    pub fn vectors(&self) -> *const Vector3<f32> {
+        self.vectors.as_ptr()
+    }
+
+    // rotation between the first and the last vector
+    pub fn angle(&self) -> f64 {
+        self.angle
+    }
+
+    pub fn enclosed_curvature(&self) -> f64 {
+        self.enclosed_curvature
+    }
+}
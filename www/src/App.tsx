@@ -7,10 +7,108 @@ import { Controls } from "./Control";
 import * as wasm from "../../wasm/pkg";
 import { memory } from "../../wasm/pkg/index_bg.wasm";
 import styled from "styled-components";
-import { CurveTessellation, SurfaceTessellation } from "../../wasm/pkg";
+import { CurveTessellation, SurfaceTessellation, UmbilicTessellation, VectorFieldTessellation } from "../../wasm/pkg";
 import { FrenetFrames } from "./preview/FrenetFrames";
+import { Polyline } from "./preview/Polyline";
+import { Umbilics } from "./preview/Umbilics";
+import { VectorField } from "./preview/VectorField";
 import { Curvature, CurvatureType, Surface } from "./preview/Surface";
 
+type GeodesicParameters = {
+  enabled: boolean;
+  u: number;
+  v: number;
+  angle: number;
+  length: number;
+  shortest: boolean;
+  targetU: number;
+  targetV: number;
+};
+
+type TransportParameters = {
+  enabled: boolean;
+  u: number;
+  v: number;
+  radius: number;
+};
+
+type LineParameters = {
+  lineType: string;
+  u: number;
+  v: number;
+  spacing: number;
+  count: number;
+};
+
+type CurveExpression = {
+  x: string;
+  y: string;
+  z: string;
+  start: number;
+  end: number;
+};
+
+type Rails = {
+  x0: string;
+  y0: string;
+  z0: string;
+  x1: string;
+  y1: string;
+  z1: string;
+};
+
+type Loft = {
+  sections: string;
+  cubic: boolean;
+  arcLength: boolean;
+};
+
+type Coons = {
+  boundaries: string;
+  bicubic: boolean;
+};
+
+type Weierstrass = {
+  f: string;
+  g: string;
+  u0: number;
+  u1: number;
+  v0: number;
+  v1: number;
+  angle: number;
+};
+
+type SineGordon = {
+  angle: string;
+  u0: number;
+  u1: number;
+  v0: number;
+  v1: number;
+};
+
+type Implicit = {
+  implicitType: string;
+  f: string;
+  x0: number;
+  x1: number;
+  y0: number;
+  y1: number;
+  z0: number;
+  z1: number;
+  count: number;
+};
+
+type SurfaceExpression = {
+  x: string;
+  y: string;
+  z: string;
+  u0: number;
+  u1: number;
+  v0: number;
+  v1: number;
+  parameters: string;
+};
+
 const App = () => {
   const light = useRef<DirectionalLight>(null!);
   const [edges, setEdges] = useState<CurveTessellation[]>([]);
@@ -23,44 +121,471 @@ const App = () => {
   const [curvature, setCurvature] = useState<CurvatureType>(Curvature.Gaussian);
   const [useArrow, setUseArrow] = useState<boolean>(false);
   const [useIsoCurve, setUseIsoCurve] = useState<boolean>(true);
+  const [usePrincipal, setUsePrincipal] = useState<boolean>(false);
+  const [useUmbilic, setUseUmbilic] = useState<boolean>(false);
+  const [surfaceExpression, setSurfaceExpression] = useState<SurfaceExpression>({
+    x: '(a * cos(u) + b) * cos(v)',
+    y: '(a * cos(u) + b) * sin(v)',
+    z: 'a * sin(u)',
+    u0: 0,
+    u1: Math.PI * 2,
+    v0: 0,
+    v1: Math.PI * 2,
+    parameters: 'a = 1, b = 3',
+  });
+  const [profile, setProfile] = useState<CurveExpression>({
+    x: '1 + 0.5 * sin(t * 1.5)',
+    y: '0',
+    z: 't',
+    start: 0,
+    end: 4,
+  });
+  const [rails, setRails] = useState<Rails>({
+    x0: '2 * cos(t * 3)',
+    y0: '2 * sin(t * 3)',
+    z0: '-1',
+    x1: '2 * cos(t * 3 + 1)',
+    y1: '2 * sin(t * 3 + 1)',
+    z1: '1',
+  });
+  const [loft, setLoft] = useState<Loft>({
+    sections: [
+      'cos(t * 2 * pi()); sin(t * 2 * pi()); -2',
+      '2 * cos(t * 2 * pi()); 0.8 * sin(t * 2 * pi()); -0.7',
+      '0.8 * cos(t * 2 * pi()); 1.6 * sin(t * 2 * pi()); 0.7',
+      '1.2 * cos(t * 2 * pi()); 1.2 * sin(t * 2 * pi()); 2',
+    ].join(' | '),
+    cubic: true,
+    arcLength: false,
+  });
+  const [coons, setCoons] = useState<Coons>({
+    boundaries: [
+      't * 4 - 2; -2; sin(t * pi())',
+      '2; t * 4 - 2; -0.5 * sin(t * 2 * pi())',
+      '2 - t * 4; 2; 0.8 * sin(t * pi())',
+      '-2; 2 - t * 4; -sin(t * pi())',
+    ].join(' | '),
+    bicubic: true,
+  });
+  const [weierstrass, setWeierstrass] = useState<Weierstrass>({
+    f: '1',
+    g: 'w',
+    u0: -1.5,
+    u1: 1.5,
+    v0: -1.5,
+    v1: 1.5,
+    angle: 0,
+  });
+  const [sineGordon, setSineGordon] = useState<SineGordon>({
+    angle: '4 * atan((u - v) / cosh(u + v))',
+    u0: 1e-2,
+    u1: 4,
+    v0: -4,
+    v1: -1e-2,
+  });
+  const [implicit, setImplicit] = useState<Implicit>({
+    implicitType: '',
+    f: 'x^4 + y^4 + z^4 - (x^2 + y^2 + z^2) * 2 + 0.5',
+    x0: -2,
+    x1: 2,
+    y0: -2,
+    y1: 2,
+    z0: -2,
+    z1: 2,
+    count: 48,
+  });
+  const [implicits, setImplicits] = useState<SurfaceTessellation[]>([]);
+  const [useControlNet, setUseControlNet] = useState<boolean>(false);
+  const [controlNets, setControlNets] = useState<CurveTessellation[]>([]);
+  const [strictions, setStrictions] = useState<CurveTessellation[]>([]);
+  // bumped whenever the expression surface, the revolution profile, the rails, the loft sections, the Coons boundaries, the Weierstrass data or the sine-Gordon angle change so that everything on it is rebuilt
+  const [surfaceRevision, setSurfaceRevision] = useState<number>(0);
+  const [umbilics, setUmbilics] = useState<UmbilicTessellation[]>([]);
 
-  const app = useMemo(() => {
-    const app = wasm.App.new();
+  const [curveType, setCurveType] = useState<string>('');
+  const [expression, setExpression] = useState<CurveExpression>({
+    x: 'cos(t)',
+    y: 'sin(t)',
+    z: 't * 0.25',
+    start: 0,
+    end: 10,
+  });
+
+  const [tube, setTube] = useState<{ enabled: boolean, radius: number }>({
+    enabled: false,
+    radius: 0.1,
+  });
+  const [tubes, setTubes] = useState<SurfaceTessellation[]>([]);
+
+  const [geodesicParameters, setGeodesicParameters] = useState<GeodesicParameters>({
+    enabled: false,
+    u: 0.5,
+    v: 0.5,
+    angle: Math.PI * 0.25,
+    length: 10,
+    shortest: false,
+    targetU: 0.25,
+    targetV: 0.75,
+  });
+  const [geodesics, setGeodesics] = useState<CurveTessellation[]>([]);
+
+  const [transportParameters, setTransportParameters] = useState<TransportParameters>({
+    enabled: false,
+    u: 0.25,
+    v: 0.5,
+    radius: 0.1,
+  });
+  const [transports, setTransports] = useState<VectorFieldTessellation[]>([]);
 
-    /*
-    const n = app.curves_count();
-    const e: wasm.CurveTessellation[] = Array.from(Array(n).keys()).map(i => {
-      return app.tessellate_curve(i, delta);
-    }).filter(e => e !== undefined) as wasm.CurveTessellation[];
-    setEdges(e);
-    */
+  const [lineParameters, setLineParameters] = useState<LineParameters>({
+    lineType: '',
+    u: 0.5,
+    v: 0.5,
+    spacing: 0.25,
+    count: 8,
+  });
+  const [lines, setLines] = useState<CurveTessellation[]>([]);
 
+  // values computed on the wasm side, shown read only in the gui
+  const readout = useMemo(() => ({
+    holonomy: '',
+    enclosed: '',
+    egregium: '',
+    discreteGaussian: '',
+    discreteMean: '',
+    area: '',
+    volume: '',
+    totalGaussian: '',
+    totalMean: '',
+    gaussBonnet: '',
+    developable: '',
+    distribution: '',
+  }), []);
+  // the integrals are computed on demand, they are slow on surfaces of expensive evaluation
+  const [measureRevision, setMeasureRevision] = useState<number>(0);
+
+  const app = useMemo(() => {
+    const app = wasm.App.new();
     return app;
   }, []);
 
+  useEffect(() => {
+    const { x, y, z, u0, u1, v0, v1, parameters } = surfaceExpression;
+    if (app.set_surface_expression(x, y, z, new Float64Array([u0, u1, v0, v1]), parameters)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid surface expression: ${x}, ${y}, ${z} (${parameters})`);
+    }
+  }, [surfaceExpression]);
+
+  useEffect(() => {
+    const { x, y, z, start, end } = profile;
+    if (app.set_revolution_profile(x, y, z, start, end)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid revolution profile: ${x}, ${y}, ${z}`);
+    }
+  }, [profile]);
+
+  useEffect(() => {
+    const { x0, y0, z0, x1, y1, z1 } = rails;
+    if (app.set_ruled_rails(x0, y0, z0, x1, y1, z1)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid rails: ${x0}, ${y0}, ${z0} / ${x1}, ${y1}, ${z1}`);
+    }
+  }, [rails]);
+
+  useEffect(() => {
+    const { sections, cubic, arcLength } = loft;
+    if (app.set_loft_sections(sections, cubic, arcLength)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid loft sections: ${sections}`);
+    }
+  }, [loft]);
+
+  useEffect(() => {
+    const { boundaries, bicubic } = coons;
+    if (app.set_coons_boundaries(boundaries, bicubic)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid coons boundaries: ${boundaries}`);
+    }
+  }, [coons]);
+
+  useEffect(() => {
+    const { f, g, u0, u1, v0, v1, angle } = weierstrass;
+    if (app.set_weierstrass_data(f, g, new Float64Array([u0, u1, v0, v1]), angle)) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid weierstrass data: ${f}, ${g}`);
+    }
+  }, [weierstrass]);
+
+  useEffect(() => {
+    const { angle, u0, u1, v0, v1 } = sineGordon;
+    if (app.set_sine_gordon_angle(angle, new Float64Array([u0, u1, v0, v1]))) {
+      setSurfaceRevision(r => r + 1);
+    } else {
+      console.warn(`invalid sine-gordon angle: ${angle}`);
+    }
+  }, [sineGordon]);
+
+  useEffect(() => {
+    const { implicitType, f, x0, x1, y0, y1, z0, z1, count } = implicit;
+    if (implicitType === '') {
+      setImplicits([]);
+      return;
+    }
+    const t = Number(implicitType);
+    if (t === wasm.ImplicitType.Expression && !app.set_implicit_expression(f, new Float64Array([x0, x1, y0, y1, z0, z1]))) {
+      console.warn(`invalid implicit function: ${f}`);
+      return;
+    }
+    setImplicits([app.create_implicit_surface(t, count)]);
+  }, [implicit]);
+
+  useEffect(() => {
+    setControlNets(useControlNet ? app.create_control_net(Number(surfaceType)) : []);
+  }, [surfaceType, surfaceRevision, useControlNet]);
+
+  useEffect(() => {
+    if (Number(surfaceType) !== wasm.SurfaceType.Ruled) {
+      setStrictions([]);
+      readout.developable = readout.distribution = '';
+      return;
+    }
+    readout.developable = String(app.is_ruled_developable());
+    readout.distribution = app.distribution_parameter(0.5).toFixed(6);
+    const c = app.create_striction_curve(64);
+    setStrictions(c !== undefined ? [c] : []);
+  }, [surfaceType, surfaceRevision]);
+
   useEffect(() => {
     const delta = 1e-2 * 2.0;
     const s = app.create_surface(Number(surfaceType), delta);
     if (s !== undefined) {
       setSurfaces([s]);
     }
-  }, [surfaceType]);
+    // Theorema Egregium: the intrinsic gaussian curvature agrees with the extrinsic one
+    readout.egregium = app.gaussian_curvature_deviation(Number(surfaceType), 16).toExponential(3);
+    const t = Number(surfaceType);
+    const [dk, dh] = app.discrete_curvature_deviation(t, delta);
+    readout.discreteGaussian = dk.toExponential(3);
+    readout.discreteMean = dh.toExponential(3);
+    readout.area = readout.volume = readout.totalGaussian = readout.totalMean = readout.gaussBonnet = '';
+  }, [surfaceType, surfaceRevision]);
+
+  useEffect(() => {
+    if (measureRevision === 0) {
+      return;
+    }
+    const t = Number(surfaceType);
+    readout.area = app.surface_area(t).toFixed(6);
+    readout.volume = app.surface_volume(t).toFixed(6);
+    readout.totalGaussian = app.total_gaussian_curvature(t).toFixed(6);
+    readout.totalMean = app.total_mean_curvature(t).toFixed(6);
+    const gb = app.gauss_bonnet(t, 0, 1, 0, 1);
+    readout.gaussBonnet = `${gb.total().toFixed(6)}, χ = ${gb.euler_characteristic} (inferred ${gb.inferred_euler_characteristic()})`;
+  }, [measureRevision]);
+
+  useEffect(() => {
+    setUmbilics(useUmbilic ? [app.create_umbilics(Number(surfaceType), 33)] : []);
+  }, [surfaceType, surfaceRevision, useUmbilic]);
+
+  useEffect(() => {
+    const { enabled, u, v, angle, length, shortest, targetU, targetV } = geodesicParameters;
+    if (!enabled) {
+      setGeodesics([]);
+      return;
+    }
+    const g = shortest ?
+      app.create_shortest_geodesic(Number(surfaceType), u, v, targetU, targetV) :
+      app.create_geodesic(Number(surfaceType), u, v, angle, length);
+    setGeodesics(g !== undefined ? [g] : []);
+  }, [surfaceType, surfaceRevision, geodesicParameters]);
+
+  useEffect(() => {
+    const { enabled, u, v, radius } = transportParameters;
+    if (!enabled) {
+      setTransports([]);
+      readout.holonomy = readout.enclosed = '';
+      return;
+    }
+    const f = app.create_parallel_transport(Number(surfaceType), u, v, radius, 48);
+    readout.holonomy = f !== undefined ? f.angle().toFixed(6) : '';
+    readout.enclosed = f !== undefined ? f.enclosed_curvature().toFixed(6) : '';
+    setTransports(f !== undefined ? [f] : []);
+  }, [surfaceType, surfaceRevision, transportParameters]);
+
+  useEffect(() => {
+    const { lineType, u, v, spacing, count } = lineParameters;
+    if (lineType === '') {
+      setLines([]);
+      return;
+    }
+    setLines(app.create_line_net(Number(surfaceType), Number(lineType), u, v, spacing, count));
+  }, [surfaceType, surfaceRevision, lineParameters]);
+
+  useEffect(() => {
+    const delta = 1 / 32;
+    if (curveType === '') {
+      setEdges([]);
+      return;
+    }
+    const t = Number(curveType);
+    const e = t === wasm.CurveType.Expression ?
+      app.create_expression_curve(expression.x, expression.y, expression.z, expression.start, expression.end, delta) :
+      app.create_curve(t, new Float64Array([]), delta);
+    setEdges(e !== undefined ? [e] : []);
+  }, [curveType, expression]);
+
+  useEffect(() => {
+    if (curveType === '' || !tube.enabled) {
+      setTubes([]);
+      return;
+    }
+    const t = Number(curveType);
+    const s = t === wasm.CurveType.Expression ?
+      app.create_expression_tube(expression.x, expression.y, expression.z, expression.start, expression.end, tube.radius) :
+      app.create_tube(t, new Float64Array([]), tube.radius, 1 / 64);
+    setTubes(s !== undefined ? [s] : []);
+  }, [curveType, expression, tube]);
 
   const gui = useMemo(() => {
     const gui = new dat.GUI({ name: 'Differential Geometry Study', width: 300 });
-    const types = Object.keys(wasm.SurfaceType).filter(k => !isNaN(Number(k))).map(k => {
+    const enumOptions = (e: { [key: string]: string | number }) => Object.keys(e).filter(k => !isNaN(Number(k))).map(k => {
       const i = Number(k);
-      return { index: k, value: wasm.SurfaceType[i] };
+      return { index: k, value: e[i] as string };
     }).reduce((pre: { [index: string]: string }, cur) => {
       const { index, value } = cur;
       pre[value] = index;
       return pre;
     }, {});
-    gui.add({ surfaceType }, 'surfaceType', types).name('type').onChange(setSurfaceType);
+    gui.add({ surfaceType }, 'surfaceType', enumOptions(wasm.SurfaceType)).name('type').onChange(setSurfaceType);
     gui.add({ useSurface }, 'useSurface').name('surface').onChange(setUseSurface);
     gui.add({ curvature }, 'curvature', Curvature).onChange(setCurvature);
     gui.add({ useArrow }, 'useArrow').name('arrow').onChange(setUseArrow);
     gui.add({ useIsoCurve }, 'useIsoCurve').name('iso curve').onChange(setUseIsoCurve);
+    gui.add({ usePrincipal }, 'usePrincipal').name('principal directions').onChange(setUsePrincipal);
+    gui.add({ useUmbilic }, 'useUmbilic').name('umbilics').onChange(setUseUmbilic);
+    gui.add({ useControlNet }, 'useControlNet').name('control net').onChange(setUseControlNet);
+
+    const surface = gui.addFolder('surface expression');
+    const se = { ...surfaceExpression };
+    const onChangeSurfaceExpression = () => setSurfaceExpression({ ...se });
+    surface.add(se, 'x').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'y').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'z').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'u0').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'u1').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'v0').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'v1').onFinishChange(onChangeSurfaceExpression);
+    surface.add(se, 'parameters').onFinishChange(onChangeSurfaceExpression);
+
+    const revolution = gui.addFolder('revolution profile');
+    const rp = { ...profile };
+    const onChangeProfile = () => setProfile({ ...rp });
+    revolution.add(rp, 'x').onFinishChange(onChangeProfile);
+    revolution.add(rp, 'y').onFinishChange(onChangeProfile);
+    revolution.add(rp, 'z').onFinishChange(onChangeProfile);
+    revolution.add(rp, 'start').onFinishChange(onChangeProfile);
+    revolution.add(rp, 'end').onFinishChange(onChangeProfile);
+
+    const ruled = gui.addFolder('ruled rails');
+    const rr = { ...rails };
+    const onChangeRails = () => setRails({ ...rr });
+    (['x0', 'y0', 'z0', 'x1', 'y1', 'z1'] as (keyof Rails)[]).forEach(k => ruled.add(rr, k).onFinishChange(onChangeRails));
+    ruled.add(readout, 'developable').listen();
+    ruled.add(readout, 'distribution').name('distribution at u = 0.5').listen();
+
+    const lofted = gui.addFolder('loft sections');
+    const ls = { ...loft };
+    const onChangeLoft = () => setLoft({ ...ls });
+    lofted.add(ls, 'sections').onFinishChange(onChangeLoft);
+    lofted.add(ls, 'cubic').onChange(onChangeLoft);
+    lofted.add(ls, 'arcLength').name('arc length').onChange(onChangeLoft);
+
+    const patch = gui.addFolder('coons boundaries');
+    const cb = { ...coons };
+    const onChangeCoons = () => setCoons({ ...cb });
+    patch.add(cb, 'boundaries').onFinishChange(onChangeCoons);
+    patch.add(cb, 'bicubic').onChange(onChangeCoons);
+
+    const minimal = gui.addFolder('weierstrass data');
+    const wd = { ...weierstrass };
+    const onChangeWeierstrass = () => setWeierstrass({ ...wd });
+    (['f', 'g', 'u0', 'u1', 'v0', 'v1'] as (keyof Weierstrass)[]).forEach(k => minimal.add(wd, k).onFinishChange(onChangeWeierstrass));
+    minimal.add(wd, 'angle', 0, Math.PI * 2).name('associate angle').onFinishChange(onChangeWeierstrass);
+
+    const pseudospherical = gui.addFolder('sine-gordon angle');
+    const sg = { ...sineGordon };
+    const onChangeSineGordon = () => setSineGordon({ ...sg });
+    (['angle', 'u0', 'u1', 'v0', 'v1'] as (keyof SineGordon)[]).forEach(k => pseudospherical.add(sg, k).onFinishChange(onChangeSineGordon));
+
+    const level = gui.addFolder('implicit surface');
+    const im = { ...implicit };
+    const onChangeImplicit = () => setImplicit({ ...im });
+    level.add(im, 'implicitType', { None: '', ...enumOptions(wasm.ImplicitType) }).name('type').onChange(onChangeImplicit);
+    (['f', 'x0', 'x1', 'y0', 'y1', 'z0', 'z1'] as (keyof Implicit)[]).forEach(k => level.add(im, k).onFinishChange(onChangeImplicit));
+    level.add(im, 'count', 8, 96, 1).onFinishChange(onChangeImplicit);
+
+    const curve = gui.addFolder('curve');
+    curve.add({ curveType }, 'curveType', { None: '', ...enumOptions(wasm.CurveType) }).name('type').onChange(setCurveType);
+    const expr = { ...expression };
+    const onChangeExpression = () => setExpression({ ...expr });
+    curve.add(expr, 'x').onFinishChange(onChangeExpression);
+    curve.add(expr, 'y').onFinishChange(onChangeExpression);
+    curve.add(expr, 'z').onFinishChange(onChangeExpression);
+    curve.add(expr, 'start').onFinishChange(onChangeExpression);
+    curve.add(expr, 'end').onFinishChange(onChangeExpression);
+    const tp0 = { ...tube };
+    const onChangeTube = () => setTube({ ...tp0 });
+    curve.add(tp0, 'enabled').name('tube').onChange(onChangeTube);
+    curve.add(tp0, 'radius', 0.01, 1).name('tube radius').onFinishChange(onChangeTube);
+
+    const geodesic = gui.addFolder('geodesic');
+    const gp = { ...geodesicParameters };
+    const onChangeGeodesic = () => setGeodesicParameters({ ...gp });
+    geodesic.add(gp, 'enabled').onChange(onChangeGeodesic);
+    geodesic.add(gp, 'u', 0, 1).onFinishChange(onChangeGeodesic);
+    geodesic.add(gp, 'v', 0, 1).onFinishChange(onChangeGeodesic);
+    geodesic.add(gp, 'angle', -Math.PI, Math.PI).onFinishChange(onChangeGeodesic);
+    geodesic.add(gp, 'length', 0, 100).onFinishChange(onChangeGeodesic);
+    geodesic.add(gp, 'shortest').onChange(onChangeGeodesic);
+    geodesic.add(gp, 'targetU', 0, 1).name('target u').onFinishChange(onChangeGeodesic);
+    geodesic.add(gp, 'targetV', 0, 1).name('target v').onFinishChange(onChangeGeodesic);
+
+    const transport = gui.addFolder('parallel transport');
+    const tp = { ...transportParameters };
+    const onChangeTransport = () => setTransportParameters({ ...tp });
+    transport.add(tp, 'enabled').onChange(onChangeTransport);
+    transport.add(tp, 'u', 0, 1).onFinishChange(onChangeTransport);
+    transport.add(tp, 'v', 0, 1).onFinishChange(onChangeTransport);
+    transport.add(tp, 'radius', 0, 0.5).onFinishChange(onChangeTransport);
+    transport.add(readout, 'holonomy').listen();
+    transport.add(readout, 'enclosed').name('enclosed ∬K dA').listen();
+
+    const measurement = gui.addFolder('measurements');
+    measurement.add(readout, 'egregium').name('max |K - K intrinsic|').listen();
+    measurement.add(readout, 'discreteGaussian').name('mean |K - K discrete|').listen();
+    measurement.add(readout, 'discreteMean').name('mean |H - H discrete|').listen();
+    measurement.add({ measure: () => setMeasureRevision(r => r + 1) }, 'measure').name('measure integrals');
+    measurement.add(readout, 'area').listen();
+    measurement.add(readout, 'volume').listen();
+    measurement.add(readout, 'totalGaussian').name('∬K dA').listen();
+    measurement.add(readout, 'totalMean').name('∬H dA').listen();
+    measurement.add(readout, 'gaussBonnet').name('∬K + ∮kg + Σθ').listen();
+
+    const line = gui.addFolder('curvature lines');
+    const lp = { ...lineParameters };
+    const onChangeLine = () => setLineParameters({ ...lp });
+    line.add(lp, 'lineType', { None: '', ...enumOptions(wasm.LineType) }).name('type').onChange(onChangeLine);
+    line.add(lp, 'u', 0, 1).onFinishChange(onChangeLine);
+    line.add(lp, 'v', 0, 1).onFinishChange(onChangeLine);
+    line.add(lp, 'spacing', 0.01, 1).onFinishChange(onChangeLine);
+    line.add(lp, 'count', 0, 32, 1).onFinishChange(onChangeLine);
     return gui;
   }, [])
 
@@ -85,9 +610,49 @@ const App = () => {
                 return <FrenetFrames key={ `edge-${i}` } edge={edge} length={0.05}></FrenetFrames>
               })
             }
+            {
+              geodesics.map((edge, i) => {
+                return <Polyline key={ `geodesic-${i}` } edge={edge} color={ new Color(0xff2200) }></Polyline>
+              })
+            }
+            {
+              controlNets.map((edge, i) => {
+                return <Polyline key={ `control-net-${i}` } edge={edge} color={ new Color(0x888888) }></Polyline>
+              })
+            }
+            {
+              strictions.map((edge, i) => {
+                return <Polyline key={ `striction-${i}` } edge={edge} color={ new Color(0xffaa00) }></Polyline>
+              })
+            }
+            {
+              lines.map((edge, i) => {
+                return <Polyline key={ `line-${i}` } edge={edge} color={ new Color(0x0044ff) }></Polyline>
+              })
+            }
+            {
+              umbilics.map((u, i) => {
+                return <Umbilics key={ `umbilics-${i}` } umbilics={u} radius={0.05}></Umbilics>
+              })
+            }
+            {
+              transports.map((field, i) => {
+                return <VectorField key={ `transport-${i}` } field={field} length={0.3} color={ new Color(0x22ff00) }></VectorField>
+              })
+            }
+            {
+              tubes.map((tess, i) => {
+                return <Surface key={ `tube-${i}` } tessellation={tess} surface={ true } curvature={ curvature } arrow={ false } isoCurve={ false } principal={ false } arrowLength={0.2}></Surface>
+              })
+            }
+            {
+              implicits.map((tess, i) => {
+                return <Surface key={ `implicit-${i}` } tessellation={tess} surface={ true } curvature={ curvature } arrow={ false } isoCurve={ false } principal={ usePrincipal } arrowLength={0.2}></Surface>
+              })
+            }
             {
               surfaces.map((tess, i) => {
-                return <Surface key={ `surface-${i}` } tessellation={tess} surface={ useSurface } curvature={ curvature } arrow={ useArrow } isoCurve={ useIsoCurve } arrowLength={0.2}></Surface>
+                return <Surface key={ `surface-${i}` } tessellation={tess} surface={ useSurface } curvature={ curvature } arrow={ useArrow } isoCurve={ useIsoCurve } principal={ usePrincipal } arrowLength={0.2}></Surface>
               })
             }
           </group>
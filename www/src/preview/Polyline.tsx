@@ -0,0 +1,34 @@
+import React from "react";
+import { useEffect, useState } from "react";
+import { BufferGeometry, Color, Float32BufferAttribute } from "three";
+import { CurveTessellation } from "../../../wasm/pkg";
+import { memory } from "../../../wasm/pkg/index_bg.wasm";
+
+const Polyline = (props: {
+  edge: CurveTessellation;
+  color: Color;
+}): JSX.Element => {
+  const { edge, color } = props;
+  const [lineGeometry, setLineGeometry] = useState<BufferGeometry>(new BufferGeometry());
+
+  useEffect(() => {
+    const count = edge.count();
+    const stride = edge.stride();
+    const point = new Float32Array(memory.buffer, edge.points(), count * stride);
+
+    const g = new BufferGeometry();
+    g.setAttribute('position', new Float32BufferAttribute(point, 3));
+    setLineGeometry(g);
+  }, [edge]);
+
+  return (
+    <line>
+      <primitive object={lineGeometry} attach="geometry" />
+      <lineBasicMaterial attach="material" color={ color } />
+    </line>
+  );
+}
+
+export {
+  Polyline
+};
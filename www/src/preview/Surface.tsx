@@ -8,6 +8,8 @@ import { memory } from "../../../wasm/pkg/index_bg.wasm";
 export const Curvature = {
   Gaussian: 'gaussian',
   Mean: 'mean',
+  K1: 'k1',
+  K2: 'k2',
 } as const;
 export type CurvatureType = typeof Curvature[keyof typeof Curvature];
 
@@ -17,14 +19,16 @@ const Surface = (props: {
   curvature?: CurvatureType;
   arrow: boolean;
   isoCurve: boolean;
+  principal?: boolean;
   arrowLength: number;
 }): JSX.Element => {
-  const { tessellation, surface, curvature, arrow, arrowLength, isoCurve } = props;
+  const { tessellation, surface, curvature, arrow, arrowLength, isoCurve, principal } = props;
   const [geometry, setGeometry] = useState<BufferGeometry>(new BufferGeometry());
   const [isNormal, setIsNormal] = useState<boolean>(false);
   const [arrowOrigin, setArrowOrigin] = useState<Vector3[]>([]);
   const [arrowDirection, setArrowDirection] = useState<Vector3[]>([]);
   const [isoGeometry, setIsoGeometry] = useState<BufferGeometry>(new BufferGeometry());
+  const [principalGeometry, setPrincipalGeometry] = useState<BufferGeometry>(new BufferGeometry());
 
   const headLength = arrowLength * 0.2;
   const headWidth = arrowLength * 0.125;
@@ -36,11 +40,19 @@ const Surface = (props: {
 
     const pArray = new Float32Array(memory.buffer, tessellation.points(), pcount * stride);
     const nArray = new Float32Array(memory.buffer, tessellation.normals(), pcount * stride);
-    const cArray = new Float32Array(
-      memory.buffer,
-      curvature === Curvature.Gaussian ? tessellation.gauss_curvature() : tessellation.mean_curvature(),
-      pcount
-    );
+    const channel = (() => {
+      switch (curvature) {
+        case Curvature.Mean:
+          return tessellation.mean_curvature();
+        case Curvature.K1:
+          return tessellation.k1();
+        case Curvature.K2:
+          return tessellation.k2();
+        default:
+          return tessellation.gauss_curvature();
+      }
+    })();
+    const cArray = new Float32Array(memory.buffer, channel, pcount);
 
     const lut = new Lut('cooltowarm');
     const colors: number[] = [];
@@ -117,6 +129,28 @@ const Surface = (props: {
         iColor.push(fu0, fv, 0, fu1, fv, 0);
       }
     }
+    // principal direction glyphs: k1 direction in red, k2 direction in blue
+    const d1Array = new Float32Array(memory.buffer, tessellation.principal_directions1(), pcount * stride);
+    const d2Array = new Float32Array(memory.buffer, tessellation.principal_directions2(), pcount * stride);
+    const pPosition: number[] = [];
+    const pColor: number[] = [];
+    const glyph = arrowLength * 0.5;
+    for (let i = 0; i < pcount; i++) {
+      const p = pts[i];
+      [d1Array, d2Array].forEach((d, j) => {
+        const dx = d[i * 3] * glyph;
+        const dy = d[i * 3 + 1] * glyph;
+        const dz = d[i * 3 + 2] * glyph;
+        pPosition.push(p.x - dx, p.y - dy, p.z - dz, p.x + dx, p.y + dy, p.z + dz);
+        const c = j === 0 ? [1, 0.2, 0] : [0, 0.4, 1];
+        pColor.push(...c, ...c);
+      });
+    }
+    const pg = new BufferGeometry();
+    pg.setAttribute('position', new Float32BufferAttribute(pPosition, 3));
+    pg.setAttribute('color', new Float32BufferAttribute(pColor, 3));
+    setPrincipalGeometry(pg);
+
     const isog = new BufferGeometry();
     isog.setAttribute('position', new Float32BufferAttribute(iPosition, 3));
     isog.setAttribute('color', new Float32BufferAttribute(iColor, 3));
@@ -133,6 +167,14 @@ const Surface = (props: {
           </lineSegments>
         : <></>
       }
+      {
+        principal ?
+          <lineSegments>
+            <primitive object={principalGeometry} attach="geometry" />
+            <lineBasicMaterial attach="material" vertexColors={true}></lineBasicMaterial>
+          </lineSegments>
+        : <></>
+      }
       {
         arrow ? 
           arrowOrigin.map((p, i) => {
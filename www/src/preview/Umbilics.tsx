@@ -0,0 +1,49 @@
+import React from "react";
+import { useEffect, useState } from "react";
+import { Color, Vector3 } from "three";
+import { UmbilicTessellation, UmbilicType } from "../../../wasm/pkg";
+import { memory } from "../../../wasm/pkg/index_bg.wasm";
+
+const colors: { [kind: number]: Color } = {
+  [UmbilicType.Lemon]: new Color(0xffdd00),
+  [UmbilicType.Star]: new Color(0xff2288),
+  [UmbilicType.Monstar]: new Color(0x8822ff),
+  [UmbilicType.Nongeneric]: new Color(0xffffff),
+};
+
+const Umbilics = (props: {
+  umbilics: UmbilicTessellation;
+  radius: number;
+}): JSX.Element => {
+  const { umbilics, radius } = props;
+  const [points, setPoints] = useState<{ position: Vector3, kind: number }[]>([]);
+
+  useEffect(() => {
+    const count = umbilics.count();
+    const stride = umbilics.stride();
+    const point = new Float32Array(memory.buffer, umbilics.points(), count * stride);
+    const kind = new Uint32Array(memory.buffer, umbilics.kinds(), count);
+
+    const p = [];
+    for (let i = 0; i < count; i++) {
+      const ia = i * stride;
+      p.push({ position: new Vector3(point[ia], point[ia + 1], point[ia + 2]), kind: kind[i] });
+    }
+    setPoints(p);
+  }, [umbilics]);
+
+  return <group>
+    {
+      points.map((p, i) => {
+        return <mesh key={ `umbilic-${i}` } position={ p.position }>
+          <sphereGeometry args={ [radius, 16, 8] } />
+          <meshBasicMaterial color={ colors[p.kind] } />
+        </mesh>
+      })
+    }
+  </group>;
+}
+
+export {
+  Umbilics
+};
@@ -0,0 +1,59 @@
+import React from "react";
+import { useEffect, useState } from "react";
+import { BufferGeometry, Color, Float32BufferAttribute, Vector3 } from "three";
+import { VectorFieldTessellation } from "../../../wasm/pkg";
+import { memory } from "../../../wasm/pkg/index_bg.wasm";
+
+const VectorField = (props: {
+  field: VectorFieldTessellation;
+  length: number;
+  color: Color;
+}): JSX.Element => {
+  const { field, length, color } = props;
+  const [lineGeometry, setLineGeometry] = useState<BufferGeometry>(new BufferGeometry());
+  const [origins, setOrigins] = useState<Vector3[]>([]);
+  const [vectors, setVectors] = useState<Vector3[]>([]);
+
+  useEffect(() => {
+    const count = field.count();
+    const stride = field.stride();
+    const point = new Float32Array(memory.buffer, field.points(), count * stride);
+    const vector = new Float32Array(memory.buffer, field.vectors(), count * stride);
+
+    const g = new BufferGeometry();
+    g.setAttribute('position', new Float32BufferAttribute(point, 3));
+    setLineGeometry(g);
+
+    const o: Vector3[] = [];
+    const v: Vector3[] = [];
+    for (let i = 0; i < count; i++) {
+      const ia = i * stride;
+      o.push(new Vector3(point[ia], point[ia + 1], point[ia + 2]));
+      v.push(new Vector3(vector[ia], vector[ia + 1], vector[ia + 2]));
+    }
+    setOrigins(o);
+    setVectors(v);
+  }, [field]);
+
+  const headLength = length * 0.2;
+  const headWidth = length * 0.125;
+
+  return <group>
+    <line>
+      <primitive object={lineGeometry} attach="geometry" />
+      <lineBasicMaterial attach="material" color={ color } />
+    </line>
+    {
+      origins.map((p, i) => {
+        return <arrowHelper
+          key={ `v-${i}` }
+          args={ [vectors[i], origins[i], length, color, headLength, headWidth ] }
+        ></arrowHelper>
+      })
+    }
+  </group>;
+}
+
+export {
+  VectorField
+};